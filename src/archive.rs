@@ -0,0 +1,1056 @@
+use std::collections::BTreeMap;
+use crate::core::Solution;
+use std::collections::HashMap;
+use crate::error::{Error, Result};
+use crate::dominance::{compare_pareto, Dominance, DominanceResult, ParetoDominance};
+use crate::indicators::hypervolume::{exclusive_contributions, hypervolume};
+use crate::math_utils::{euclidean_distance, OrderedF64};
+
+// Leaf capacity and fan-out of the ND-tree (Jaszkiewicz & Lust 2018)
+const ND_TREE_MAX_LEAF_SIZE: usize = 20;
+
+/// Common interface of the archives, so algorithms can switch between unbounded and bounded ones
+pub trait Archive<'a>: std::fmt::Debug {
+    fn dominance(&self) -> &dyn Dominance;
+
+    /// Switches the dominance relation, re-filtering the archived solutions under it
+    fn set_dominance(&mut self, dominance: Box<dyn Dominance>);
+
+    /// Offers a solution to the archive, returning whether it was kept
+    fn add(&mut self, solution: Solution<'a>) -> bool;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn solutions(&self) -> Vec<&Solution<'a>>;
+
+    /// Empties the archive, returning its solutions
+    fn take_solutions(&mut self) -> Vec<Solution<'a>>;
+
+    fn diversity(&self) -> DiversityStatistics;
+}
+
+/// Spread of an archive in objective space (minimization form)
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiversityStatistics {
+    pub size: usize,
+    pub capacity: Option<usize>,
+    pub mean_nearest_neighbour_distance: f64,
+    pub pruning: PruningStatistics,
+}
+
+/// Figures of the measure an archive prunes by
+#[derive(Debug, Clone, PartialEq)]
+pub enum PruningStatistics {
+    None,
+    CrowdingDistance { minimum: f64, mean: f64, boundary_solutions: usize }, // mean over interior solutions
+    AdaptiveGrid { occupied_cells: usize, max_density: usize, mean_density: f64 },
+    Hypervolume { hypervolume: f64, minimum_contribution: f64 },
+}
+
+fn oriented_objectives(solutions: &[&Solution]) -> Vec<Vec<f64>> {
+    solutions
+        .iter()
+        .map(|solution| solution.problem.oriented_objectives(&solution.objective_fitness_values))
+        .collect()
+}
+
+fn mean_nearest_neighbour_distance(objectives: &[Vec<f64>]) -> f64 {
+    if objectives.len() < 2 {
+        return 0.0;
+    }
+    let total: f64 = objectives
+        .iter()
+        .enumerate()
+        .map(|(i, point)| {
+            objectives
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, other)| euclidean_distance(point, other))
+                .fold(f64::INFINITY, f64::min)
+        })
+        .sum();
+    total / objectives.len() as f64
+}
+
+/// Unbounded archive holding only mutually non-dominated solutions.
+/// A candidate is rejected when an archived solution dominates or equals it, and evicts every archived
+/// solution it dominates. Relations with a `sorting_key` are indexed: a sorted map for up to two
+/// objectives and an ND-tree above that. Other relations fall back to a linear scan.
+#[derive(Debug)]
+pub struct NondominatedArchive<'a> {
+    dominance: Box<dyn Dominance>,
+    storage: ArchiveStorage<'a>,
+    tier: Option<usize>,
+}
+
+#[derive(Debug)]
+enum ArchiveStorage<'a> {
+    Empty,
+    Sorted(SortedFront<'a>),
+    Tree(NdTree<'a>),
+    Linear(Vec<Solution<'a>>),
+}
+
+impl<'a> NondominatedArchive<'a> {
+    pub fn new() -> Self {
+        Self::with_dominance(Box::new(ParetoDominance))
+    }
+
+    pub fn with_dominance(dominance: Box<dyn Dominance>) -> Self {
+        Self {
+            dominance,
+            storage: ArchiveStorage::Empty,
+            tier: None,
+        }
+    }
+
+    fn add_linear(&mut self, solution: Solution<'a>) -> bool {
+        if let ArchiveStorage::Empty = self.storage {
+            self.storage = ArchiveStorage::Linear(Vec::new());
+        }
+        let members = match &mut self.storage {
+            ArchiveStorage::Linear(members) => members,
+            _ => panic!("Dominance relation must provide a sorting key for every solution or for none"),
+        };
+        let mut dominated = Vec::new();
+        for (i, member) in members.iter().enumerate() {
+            match self.dominance.compare(&solution, member) {
+                DominanceResult::DominatedBy | DominanceResult::Equal => return false,
+                DominanceResult::Dominates => dominated.push(i),
+                DominanceResult::NonDominated => {}
+            }
+        }
+        for i in dominated.into_iter().rev() {
+            members.swap_remove(i);
+        }
+        members.push(solution);
+        true
+    }
+}
+
+impl<'a> Archive<'a> for NondominatedArchive<'a> {
+    fn dominance(&self) -> &dyn Dominance {
+        self.dominance.as_ref()
+    }
+
+    fn set_dominance(&mut self, dominance: Box<dyn Dominance>) {
+        let solutions = self.take_solutions();
+        self.dominance = dominance;
+        for solution in solutions {
+            self.add(solution);
+        }
+    }
+
+    fn add(&mut self, solution: Solution<'a>) -> bool {
+        let key = match self.dominance.sorting_key(&solution) {
+            Some(key) => key,
+            None => return self.add_linear(solution),
+        };
+        // Solutions of a lower tier dominate every solution of a higher one
+        match self.tier {
+            Some(tier) if key.tier > tier => return false,
+            Some(tier) if key.tier < tier => self.storage = ArchiveStorage::Empty,
+            _ => {}
+        }
+        self.tier = Some(key.tier);
+        if let ArchiveStorage::Empty = self.storage {
+            self.storage = if key.objectives.len() <= 2 {
+                ArchiveStorage::Sorted(SortedFront::new())
+            } else {
+                ArchiveStorage::Tree(NdTree::new(key.objectives.len()))
+            };
+        }
+        match &mut self.storage {
+            ArchiveStorage::Sorted(front) => front.add(key.objectives, solution),
+            ArchiveStorage::Tree(tree) => tree.add(key.objectives, solution),
+            _ => unreachable!("keyed storage was initialized above"),
+        }
+    }
+
+    fn len(&self) -> usize {
+        match &self.storage {
+            ArchiveStorage::Empty => 0,
+            ArchiveStorage::Sorted(front) => front.entries.len(),
+            ArchiveStorage::Tree(tree) => tree.len,
+            ArchiveStorage::Linear(members) => members.len(),
+        }
+    }
+
+    // Sorted by the first objective when at most two objectives are archived
+    fn solutions(&self) -> Vec<&Solution<'a>> {
+        match &self.storage {
+            ArchiveStorage::Empty => Vec::new(),
+            ArchiveStorage::Sorted(front) => front.entries.values().map(|(_, solution)| solution).collect(),
+            ArchiveStorage::Tree(tree) => {
+                let mut solutions = Vec::with_capacity(tree.len);
+                tree.root.collect(&mut solutions);
+                solutions
+            }
+            ArchiveStorage::Linear(members) => members.iter().collect(),
+        }
+    }
+
+    fn take_solutions(&mut self) -> Vec<Solution<'a>> {
+        self.tier = None;
+        match std::mem::replace(&mut self.storage, ArchiveStorage::Empty) {
+            ArchiveStorage::Empty => Vec::new(),
+            ArchiveStorage::Sorted(front) => front.entries.into_values().map(|(_, solution)| solution).collect(),
+            ArchiveStorage::Tree(tree) => {
+                let mut solutions = Vec::with_capacity(tree.len);
+                tree.root.into_solutions(&mut solutions);
+                solutions
+            }
+            ArchiveStorage::Linear(members) => members,
+        }
+    }
+
+    fn diversity(&self) -> DiversityStatistics {
+        let objectives = oriented_objectives(&self.solutions());
+        DiversityStatistics {
+            size: objectives.len(),
+            capacity: None,
+            mean_nearest_neighbour_distance: mean_nearest_neighbour_distance(&objectives),
+            pruning: PruningStatistics::None,
+        }
+    }
+}
+
+impl Default for NondominatedArchive<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Bi-objective front kept sorted by the first objective; the second one then strictly decreases,
+// so a candidate only needs to be checked against its predecessor and the run of successors it dominates.
+// A single objective is stored with a constant second coordinate.
+#[derive(Debug)]
+struct SortedFront<'a> {
+    entries: BTreeMap<OrderedF64, (f64, Solution<'a>)>,
+}
+
+impl<'a> SortedFront<'a> {
+    fn new() -> Self {
+        Self { entries: BTreeMap::new() }
+    }
+
+    fn add(&mut self, objectives: Vec<f64>, solution: Solution<'a>) -> bool {
+        let first = objectives[0];
+        let second = objectives.get(1).copied().unwrap_or(0.0);
+        if let Some((_, (predecessor_second, _))) = self.entries.range(..=OrderedF64(first)).next_back() {
+            if *predecessor_second <= second {
+                return false;
+            }
+        }
+        let dominated: Vec<OrderedF64> = self
+            .entries
+            .range(OrderedF64(first)..)
+            .take_while(|(_, (successor_second, _))| *successor_second >= second)
+            .map(|(key, _)| *key)
+            .collect();
+        for key in dominated {
+            self.entries.remove(&key);
+        }
+        self.entries.insert(OrderedF64(first), (second, solution));
+        true
+    }
+}
+
+// ND-tree: every node keeps an ideal/nadir bounding box of the points below it. Boxes only grow, which
+// keeps them valid (if loose) after removals.
+#[derive(Debug)]
+struct NdTree<'a> {
+    root: NdNode<'a>,
+    number_of_children: usize,
+    len: usize,
+}
+
+#[derive(Debug)]
+struct NdNode<'a> {
+    ideal: Vec<f64>,
+    nadir: Vec<f64>,
+    points: Vec<(Vec<f64>, Solution<'a>)>,
+    children: Vec<NdNode<'a>>,
+}
+
+enum UpdateOutcome {
+    Rejected,
+    Accepted { removed: usize },
+}
+
+impl<'a> NdTree<'a> {
+    fn new(number_of_objectives: usize) -> Self {
+        Self {
+            root: NdNode::empty(number_of_objectives),
+            number_of_children: number_of_objectives + 1,
+            len: 0,
+        }
+    }
+
+    fn add(&mut self, objectives: Vec<f64>, solution: Solution<'a>) -> bool {
+        match self.root.update(&objectives) {
+            UpdateOutcome::Rejected => false,
+            UpdateOutcome::Accepted { removed } => {
+                self.len -= removed;
+                if self.len == 0 {
+                    self.root = NdNode::empty(objectives.len());
+                }
+                self.root.insert(objectives, solution, self.number_of_children);
+                self.len += 1;
+                true
+            }
+        }
+    }
+}
+
+fn weakly_dominates(a: &[f64], b: &[f64]) -> bool {
+    a.iter().zip(b.iter()).all(|(x, y)| x <= y)
+}
+
+fn squared_distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b.iter()).map(|(x, y)| (x - y).powi(2)).sum()
+}
+
+impl<'a> NdNode<'a> {
+    fn empty(number_of_objectives: usize) -> Self {
+        Self {
+            ideal: vec![f64::INFINITY; number_of_objectives],
+            nadir: vec![f64::NEG_INFINITY; number_of_objectives],
+            points: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    fn is_leaf(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.points.is_empty() && self.children.is_empty()
+    }
+
+    fn size(&self) -> usize {
+        self.points.len() + self.children.iter().map(NdNode::size).sum::<usize>()
+    }
+
+    fn extend_bounds(&mut self, objectives: &[f64]) {
+        for (m, &value) in objectives.iter().enumerate() {
+            self.ideal[m] = self.ideal[m].min(value);
+            self.nadir[m] = self.nadir[m].max(value);
+        }
+    }
+
+    fn midpoint_distance(&self, objectives: &[f64]) -> f64 {
+        let midpoint: Vec<f64> = self.ideal.iter().zip(self.nadir.iter()).map(|(i, n)| 0.5 * (i + n)).collect();
+        squared_distance(&midpoint, objectives)
+    }
+
+    // Removes the points dominated by `objectives`, or rejects it if a point dominates or equals it
+    fn update(&mut self, objectives: &[f64]) -> UpdateOutcome {
+        if self.is_empty() {
+            return UpdateOutcome::Accepted { removed: 0 };
+        }
+        if weakly_dominates(&self.nadir, objectives) {
+            return UpdateOutcome::Rejected;
+        }
+        if weakly_dominates(objectives, &self.ideal) && objectives != self.ideal.as_slice() {
+            let removed = self.size();
+            self.points.clear();
+            self.children.clear();
+            return UpdateOutcome::Accepted { removed };
+        }
+        if !weakly_dominates(&self.ideal, objectives) && !weakly_dominates(objectives, &self.nadir) {
+            // The box can neither hold a point dominating the candidate nor one dominated by it
+            return UpdateOutcome::Accepted { removed: 0 };
+        }
+        let mut removed = 0;
+        if self.is_leaf() {
+            let mut i = 0;
+            while i < self.points.len() {
+                match compare_pareto(objectives, &self.points[i].0) {
+                    DominanceResult::DominatedBy | DominanceResult::Equal => return UpdateOutcome::Rejected,
+                    DominanceResult::Dominates => {
+                        self.points.swap_remove(i);
+                        removed += 1;
+                    }
+                    DominanceResult::NonDominated => i += 1,
+                }
+            }
+        } else {
+            for child in self.children.iter_mut() {
+                match child.update(objectives) {
+                    UpdateOutcome::Rejected => return UpdateOutcome::Rejected,
+                    UpdateOutcome::Accepted { removed: count } => removed += count,
+                }
+            }
+            self.children.retain(|child| !child.is_empty());
+            if self.children.len() == 1 {
+                let only_child = self.children.pop().unwrap();
+                self.points = only_child.points;
+                self.children = only_child.children;
+            }
+        }
+        UpdateOutcome::Accepted { removed }
+    }
+
+    fn insert(&mut self, objectives: Vec<f64>, solution: Solution<'a>, number_of_children: usize) {
+        self.extend_bounds(&objectives);
+        if self.is_leaf() {
+            self.points.push((objectives, solution));
+            if self.points.len() > ND_TREE_MAX_LEAF_SIZE {
+                self.split(number_of_children);
+            }
+        } else {
+            let closest = self
+                .children
+                .iter()
+                .enumerate()
+                .map(|(i, child)| (i, child.midpoint_distance(&objectives)))
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(i, _)| i)
+                .unwrap();
+            self.children[closest].insert(objectives, solution, number_of_children);
+        }
+    }
+
+    // Turns an overfull leaf into an internal node: seeds are picked far apart from each other and the
+    // remaining points join the child whose box centre is closest
+    fn split(&mut self, number_of_children: usize) {
+        let mut points = std::mem::take(&mut self.points);
+        let number_of_objectives = self.ideal.len();
+        let average_distance = |index: usize, others: &[(Vec<f64>, Solution<'a>)]| {
+            others.iter().map(|(other, _)| squared_distance(&others[index].0, other).sqrt()).sum::<f64>()
+        };
+        let first_seed = (0..points.len())
+            .max_by(|&a, &b| average_distance(a, &points).total_cmp(&average_distance(b, &points)))
+            .unwrap();
+        let (objectives, solution) = points.swap_remove(first_seed);
+        let mut child = NdNode::empty(number_of_objectives);
+        child.extend_bounds(&objectives);
+        child.points.push((objectives, solution));
+        self.children.push(child);
+
+        while self.children.len() < number_of_children && !points.is_empty() {
+            let farthest = (0..points.len())
+                .max_by(|&a, &b| {
+                    let distance = |i: usize| self.children.iter().map(|c| squared_distance(&c.ideal, &points[i].0).sqrt()).sum::<f64>();
+                    distance(a).total_cmp(&distance(b))
+                })
+                .unwrap();
+            let (objectives, solution) = points.swap_remove(farthest);
+            let mut child = NdNode::empty(number_of_objectives);
+            child.extend_bounds(&objectives);
+            child.points.push((objectives, solution));
+            self.children.push(child);
+        }
+        for (objectives, solution) in points {
+            let closest = self
+                .children
+                .iter()
+                .enumerate()
+                .map(|(i, child)| (i, child.midpoint_distance(&objectives)))
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(i, _)| i)
+                .unwrap();
+            self.children[closest].extend_bounds(&objectives);
+            self.children[closest].points.push((objectives, solution));
+        }
+    }
+
+    fn collect<'s>(&'s self, solutions: &mut Vec<&'s Solution<'a>>) {
+        solutions.extend(self.points.iter().map(|(_, solution)| solution));
+        for child in &self.children {
+            child.collect(solutions);
+        }
+    }
+
+    fn into_solutions(self, solutions: &mut Vec<Solution<'a>>) {
+        solutions.extend(self.points.into_iter().map(|(_, solution)| solution));
+        for child in self.children {
+            child.into_solutions(solutions);
+        }
+    }
+}
+
+/// Crowding distance of every point (NSGA-II): the normalized side lengths of the cuboid spanned by its
+/// neighbours on each objective. Extreme points get an infinite distance.
+pub fn crowding_distances(objectives: &[Vec<f64>]) -> Vec<f64> {
+    let n = objectives.len();
+    let mut distances = vec![0.0; n];
+    if n < 3 {
+        return vec![f64::INFINITY; n];
+    }
+    for m in 0..objectives[0].len() {
+        let values: Vec<f64> = objectives.iter().map(|point| point[m]).collect();
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+        let range = values[order[n - 1]] - values[order[0]];
+        distances[order[0]] = f64::INFINITY;
+        distances[order[n - 1]] = f64::INFINITY;
+        if range <= 0.0 {
+            continue;
+        }
+        for k in 1..(n - 1) {
+            distances[order[k]] += (values[order[k + 1]] - values[order[k - 1]]) / range;
+        }
+    }
+    distances
+}
+
+// Members of a bounded archive: insertion filters by dominance, pruning is left to the owner
+#[derive(Debug)]
+struct BoundedFront<'a> {
+    dominance: Box<dyn Dominance>,
+    members: Vec<Solution<'a>>,
+    capacity: usize,
+}
+
+impl<'a> BoundedFront<'a> {
+    fn try_new(capacity: usize) -> Result<Self> {
+        if capacity == 0 {
+            return Err(Error::InvalidConfiguration("Archive capacity must be greater than zero"));
+        }
+        Ok(Self { dominance: Box::new(ParetoDominance), members: Vec::with_capacity(capacity + 1), capacity })
+    }
+
+    // Appends the solution unless it is dominated or duplicated, evicting the members it dominates
+    fn insert(&mut self, solution: Solution<'a>) -> bool {
+        let mut dominated = Vec::new();
+        for (i, member) in self.members.iter().enumerate() {
+            match self.dominance.compare(&solution, member) {
+                DominanceResult::DominatedBy | DominanceResult::Equal => return false,
+                DominanceResult::Dominates => dominated.push(i),
+                DominanceResult::NonDominated => {}
+            }
+        }
+        for i in dominated.into_iter().rev() {
+            self.members.remove(i);
+        }
+        self.members.push(solution);
+        true
+    }
+
+    fn is_over_capacity(&self) -> bool {
+        self.members.len() > self.capacity
+    }
+
+    // Removes the chosen member; the candidate survives unless it was the last one appended
+    fn evict(&mut self, index: usize) -> bool {
+        let candidate_kept = index != self.members.len() - 1;
+        self.members.remove(index);
+        candidate_kept
+    }
+
+    fn objectives(&self) -> Vec<Vec<f64>> {
+        oriented_objectives(&self.members.iter().collect::<Vec<_>>())
+    }
+
+    fn statistics(&self, pruning: PruningStatistics) -> DiversityStatistics {
+        DiversityStatistics {
+            size: self.members.len(),
+            capacity: Some(self.capacity),
+            mean_nearest_neighbour_distance: mean_nearest_neighbour_distance(&self.objectives()),
+            pruning,
+        }
+    }
+
+    fn refilter(&mut self, dominance: Box<dyn Dominance>) {
+        let members = std::mem::take(&mut self.members);
+        self.dominance = dominance;
+        for member in members {
+            self.insert(member);
+        }
+    }
+}
+
+/// Fixed-capacity archive dropping the solution with the smallest crowding distance when full (NSGA-II)
+#[derive(Debug)]
+pub struct CrowdingDistanceArchive<'a> {
+    front: BoundedFront<'a>,
+}
+
+impl<'a> CrowdingDistanceArchive<'a> {
+    pub fn new(capacity: usize) -> Self {
+        Self::try_new(capacity).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Like `new`, but reports a zero capacity as an error
+    pub fn try_new(capacity: usize) -> Result<Self> {
+        Ok(Self { front: BoundedFront::try_new(capacity)? })
+    }
+}
+
+impl<'a> Archive<'a> for CrowdingDistanceArchive<'a> {
+    fn dominance(&self) -> &dyn Dominance {
+        self.front.dominance.as_ref()
+    }
+
+    fn set_dominance(&mut self, dominance: Box<dyn Dominance>) {
+        self.front.refilter(dominance);
+    }
+
+    fn add(&mut self, solution: Solution<'a>) -> bool {
+        if !self.front.insert(solution) {
+            return false;
+        }
+        if !self.front.is_over_capacity() {
+            return true;
+        }
+        let distances = crowding_distances(&self.front.objectives());
+        let most_crowded = (0..distances.len())
+            .min_by(|&a, &b| distances[a].total_cmp(&distances[b]))
+            .unwrap();
+        self.front.evict(most_crowded)
+    }
+
+    fn len(&self) -> usize {
+        self.front.members.len()
+    }
+
+    fn solutions(&self) -> Vec<&Solution<'a>> {
+        self.front.members.iter().collect()
+    }
+
+    fn take_solutions(&mut self) -> Vec<Solution<'a>> {
+        std::mem::take(&mut self.front.members)
+    }
+
+    fn diversity(&self) -> DiversityStatistics {
+        let distances = crowding_distances(&self.front.objectives());
+        let interior: Vec<f64> = distances.iter().cloned().filter(|d| d.is_finite()).collect();
+        let minimum = distances.iter().cloned().fold(f64::INFINITY, f64::min);
+        let mean = if interior.is_empty() { 0.0 } else { interior.iter().sum::<f64>() / interior.len() as f64 };
+        self.front.statistics(PruningStatistics::CrowdingDistance {
+            minimum,
+            mean,
+            boundary_solutions: distances.len() - interior.len(),
+        })
+    }
+}
+
+/// Fixed-capacity archive with the adaptive grid of PAES (Knowles & Corne 2000). The grid spans the
+/// current members with 2^bisections cells per objective; when full, a candidate replaces a member of the
+/// most crowded cell unless it lies in such a cell itself.
+#[derive(Debug)]
+pub struct AdaptiveGridArchive<'a> {
+    front: BoundedFront<'a>,
+    pub divisions: usize,
+}
+
+impl<'a> AdaptiveGridArchive<'a> {
+    pub fn new(capacity: usize, bisections: Option<u32>) -> Self {
+        Self::try_new(capacity, bisections).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Like `new`, but reports a zero capacity or too many bisections as an error
+    pub fn try_new(capacity: usize, bisections: Option<u32>) -> Result<Self> {
+        let divisions = 2usize
+            .checked_pow(bisections.unwrap_or(5))
+            .ok_or(Error::InvalidConfiguration("Too many grid bisections"))?;
+        Ok(Self { front: BoundedFront::try_new(capacity)?, divisions })
+    }
+
+    /// Grid cell of every member
+    fn cells(&self) -> Vec<Vec<usize>> {
+        let objectives = self.front.objectives();
+        if objectives.is_empty() {
+            return Vec::new();
+        }
+        let number_of_objectives = objectives[0].len();
+        let lower: Vec<f64> = (0..number_of_objectives)
+            .map(|m| objectives.iter().map(|o| o[m]).fold(f64::INFINITY, f64::min))
+            .collect();
+        let upper: Vec<f64> = (0..number_of_objectives)
+            .map(|m| objectives.iter().map(|o| o[m]).fold(f64::NEG_INFINITY, f64::max))
+            .collect();
+        objectives
+            .iter()
+            .map(|point| {
+                (0..number_of_objectives)
+                    .map(|m| {
+                        let range = upper[m] - lower[m];
+                        if range <= 0.0 {
+                            0
+                        } else {
+                            (((point[m] - lower[m]) / range * self.divisions as f64) as usize).min(self.divisions - 1)
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn densities(cells: &[Vec<usize>]) -> HashMap<&Vec<usize>, usize> {
+        let mut densities = HashMap::new();
+        for cell in cells {
+            *densities.entry(cell).or_insert(0) += 1;
+        }
+        densities
+    }
+}
+
+impl<'a> Archive<'a> for AdaptiveGridArchive<'a> {
+    fn dominance(&self) -> &dyn Dominance {
+        self.front.dominance.as_ref()
+    }
+
+    fn set_dominance(&mut self, dominance: Box<dyn Dominance>) {
+        self.front.refilter(dominance);
+    }
+
+    fn add(&mut self, solution: Solution<'a>) -> bool {
+        if !self.front.insert(solution) {
+            return false;
+        }
+        if !self.front.is_over_capacity() {
+            return true;
+        }
+        let cells = self.cells();
+        let densities = Self::densities(&cells);
+        let candidate = cells.len() - 1;
+        let max_density = *densities.values().max().unwrap();
+        if densities[&cells[candidate]] == max_density {
+            return self.front.evict(candidate);
+        }
+        let victim = (0..candidate).find(|&i| densities[&cells[i]] == max_density).unwrap();
+        self.front.evict(victim)
+    }
+
+    fn len(&self) -> usize {
+        self.front.members.len()
+    }
+
+    fn solutions(&self) -> Vec<&Solution<'a>> {
+        self.front.members.iter().collect()
+    }
+
+    fn take_solutions(&mut self) -> Vec<Solution<'a>> {
+        std::mem::take(&mut self.front.members)
+    }
+
+    fn diversity(&self) -> DiversityStatistics {
+        let cells = self.cells();
+        let densities = Self::densities(&cells);
+        let occupied_cells = densities.len();
+        self.front.statistics(PruningStatistics::AdaptiveGrid {
+            occupied_cells,
+            max_density: densities.values().cloned().max().unwrap_or(0),
+            mean_density: if occupied_cells == 0 { 0.0 } else { cells.len() as f64 / occupied_cells as f64 },
+        })
+    }
+}
+
+/// Fixed-capacity archive dropping the solution contributing the least hypervolume when full (SMS-EMOA).
+/// Without an explicit reference point the nadir of the members plus one is used.
+#[derive(Debug)]
+pub struct HypervolumeArchive<'a> {
+    front: BoundedFront<'a>,
+    pub reference_point: Option<Vec<f64>>, // minimization form
+}
+
+impl<'a> HypervolumeArchive<'a> {
+    pub fn new(capacity: usize, reference_point: Option<Vec<f64>>) -> Self {
+        Self::try_new(capacity, reference_point).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Like `new`, but reports a zero capacity as an error
+    pub fn try_new(capacity: usize, reference_point: Option<Vec<f64>>) -> Result<Self> {
+        Ok(Self { front: BoundedFront::try_new(capacity)?, reference_point })
+    }
+
+    fn reference(&self, objectives: &[Vec<f64>]) -> Vec<f64> {
+        self.reference_point.clone().unwrap_or_else(|| {
+            (0..objectives[0].len())
+                .map(|m| objectives.iter().map(|o| o[m]).fold(f64::NEG_INFINITY, f64::max) + 1.0)
+                .collect()
+        })
+    }
+
+    /// Hypervolume each member alone accounts for
+    pub fn contributions(&self) -> Vec<f64> {
+        let objectives = self.front.objectives();
+        if objectives.is_empty() {
+            return Vec::new();
+        }
+        exclusive_contributions(&objectives, &self.reference(&objectives))
+    }
+}
+
+impl<'a> Archive<'a> for HypervolumeArchive<'a> {
+    fn dominance(&self) -> &dyn Dominance {
+        self.front.dominance.as_ref()
+    }
+
+    fn set_dominance(&mut self, dominance: Box<dyn Dominance>) {
+        self.front.refilter(dominance);
+    }
+
+    fn add(&mut self, solution: Solution<'a>) -> bool {
+        if !self.front.insert(solution) {
+            return false;
+        }
+        if !self.front.is_over_capacity() {
+            return true;
+        }
+        let contributions = self.contributions();
+        let least = (0..contributions.len())
+            .min_by(|&a, &b| contributions[a].total_cmp(&contributions[b]))
+            .unwrap();
+        self.front.evict(least)
+    }
+
+    fn len(&self) -> usize {
+        self.front.members.len()
+    }
+
+    fn solutions(&self) -> Vec<&Solution<'a>> {
+        self.front.members.iter().collect()
+    }
+
+    fn take_solutions(&mut self) -> Vec<Solution<'a>> {
+        std::mem::take(&mut self.front.members)
+    }
+
+    fn diversity(&self) -> DiversityStatistics {
+        let objectives = self.front.objectives();
+        let hypervolume = if objectives.is_empty() { 0.0 } else { hypervolume(&objectives, &self.reference(&objectives)) };
+        let minimum_contribution = self.contributions().into_iter().fold(f64::INFINITY, f64::min);
+        self.front.statistics(PruningStatistics::Hypervolume {
+            hypervolume,
+            minimum_contribution: if minimum_contribution.is_finite() { minimum_contribution } else { 0.0 },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Problem;
+    use crate::dominance::{EpsilonDominance, WeightedSumDominance};
+    use crate::nondominated_sort::nondominated_sort;
+    use crate::test_utils::{evaluated_solution, minimizing_problem};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn sorted_objectives(solutions: &[&Solution]) -> Vec<Vec<f64>> {
+        let mut objectives: Vec<Vec<f64>> = solutions.iter().map(|s| s.objective_fitness_values.clone()).collect();
+        objectives.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        objectives
+    }
+
+    // The archive must end up holding exactly the distinct first front of everything offered to it
+    fn assert_matches_first_front(number_of_objectives: usize, count: usize, seed: u64) {
+        let problem = minimizing_problem(number_of_objectives);
+        let mut rng = StdRng::seed_from_u64(seed);
+        let offered: Vec<Solution> = (0..count)
+            .map(|_| {
+                let objectives: Vec<f64> = (0..number_of_objectives).map(|_| rng.gen_range(0..20) as f64).collect();
+                evaluated_solution(&problem, objectives)
+            })
+            .collect();
+        let mut archive = NondominatedArchive::new();
+        for solution in &offered {
+            archive.add(solution.clone());
+        }
+        let fronts = nondominated_sort(&offered);
+        let mut expected: Vec<Vec<f64>> = fronts[0].iter().map(|&i| offered[i].objective_fitness_values.clone()).collect();
+        expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        expected.dedup();
+        assert_eq!(sorted_objectives(&archive.solutions()), expected);
+        assert_eq!(archive.len(), expected.len());
+    }
+
+    #[test]
+    fn test_archive_rejects_dominated_and_evicts() {
+        let problem = minimizing_problem(2);
+        let mut archive = NondominatedArchive::new();
+        assert!(archive.add(evaluated_solution(&problem, vec![2.0, 2.0])));
+        assert!(!archive.add(evaluated_solution(&problem, vec![3.0, 3.0])));
+        assert!(!archive.add(evaluated_solution(&problem, vec![2.0, 2.0])));
+        assert!(archive.add(evaluated_solution(&problem, vec![1.0, 5.0])));
+        assert!(archive.add(evaluated_solution(&problem, vec![1.0, 1.0])));
+        assert_eq!(archive.len(), 1);
+        assert_eq!(archive.solutions()[0].objective_fitness_values, vec![1.0, 1.0]);
+    }
+
+    #[test]
+    fn test_archive_two_objectives_matches_first_front() {
+        assert_matches_first_front(2, 2000, 1);
+    }
+
+    #[test]
+    fn test_archive_single_objective_keeps_best() {
+        let problem = minimizing_problem(1);
+        let mut archive = NondominatedArchive::new();
+        for value in [5.0, 3.0, 4.0, 3.0] {
+            archive.add(evaluated_solution(&problem, vec![value]));
+        }
+        assert_eq!(archive.len(), 1);
+        assert_eq!(archive.solutions()[0].objective_fitness_values, vec![3.0]);
+    }
+
+    #[test]
+    fn test_archive_nd_tree_matches_first_front() {
+        assert_matches_first_front(3, 3000, 2);
+        assert_matches_first_front(5, 3000, 3);
+    }
+
+    #[test]
+    fn test_archive_constraint_tiers() {
+        let mut problem = minimizing_problem(2);
+        problem.objective_constraint = Some(vec![Some(1.0), None]);
+        problem.objective_constraint_operands = Some(vec![Some("<".to_string()), None]);
+        let mut archive = NondominatedArchive::new();
+        let mut infeasible = evaluated_solution(&problem, vec![0.0, 0.0]);
+        infeasible.constraint_violation = 1;
+        assert!(archive.add(infeasible.clone()));
+        assert!(archive.add(evaluated_solution(&problem, vec![5.0, 5.0])));
+        assert!(!archive.add(infeasible));
+        assert_eq!(archive.len(), 1);
+    }
+
+    #[test]
+    fn test_archive_with_scalarizing_dominance() {
+        let problem = minimizing_problem(2);
+        let mut archive = NondominatedArchive::with_dominance(Box::new(WeightedSumDominance::new(vec![1.0, 1.0])));
+        archive.add(evaluated_solution(&problem, vec![1.0, 4.0]));
+        archive.add(evaluated_solution(&problem, vec![2.0, 2.0]));
+        archive.add(evaluated_solution(&problem, vec![0.0, 5.0]));
+        assert_eq!(archive.len(), 1);
+        assert_eq!(archive.solutions()[0].objective_fitness_values, vec![2.0, 2.0]);
+    }
+
+    #[test]
+    fn test_archive_without_sorting_key_uses_linear_scan() {
+        let problem = minimizing_problem(2);
+        let mut archive = NondominatedArchive::with_dominance(Box::new(EpsilonDominance::new(vec![1.0])));
+        archive.add(evaluated_solution(&problem, vec![1.5, 1.5]));
+        archive.add(evaluated_solution(&problem, vec![1.1, 1.1]));
+        archive.add(evaluated_solution(&problem, vec![0.5, 3.5]));
+        assert_eq!(sorted_objectives(&archive.solutions()), vec![vec![0.5, 3.5], vec![1.1, 1.1]]);
+    }
+
+    #[test]
+    fn test_archive_set_dominance_refilters() {
+        let problem = minimizing_problem(2);
+        let mut archive = NondominatedArchive::new();
+        archive.add(evaluated_solution(&problem, vec![1.0, 4.0]));
+        archive.add(evaluated_solution(&problem, vec![2.0, 2.0]));
+        assert_eq!(archive.len(), 2);
+        archive.set_dominance(Box::new(WeightedSumDominance::new(vec![1.0, 0.0])));
+        assert_eq!(archive.len(), 1);
+        assert_eq!(archive.solutions()[0].objective_fitness_values, vec![1.0, 4.0]);
+    }
+
+    fn bi_objective_front(problem: &Problem) -> Vec<Solution<'_>> {
+        // Points on the line f1 + f2 = 10, clustered near f1 = 5
+        [0.0, 4.8, 4.9, 5.0, 5.1, 5.2, 10.0]
+            .iter()
+            .map(|&x| evaluated_solution(problem, vec![x, 10.0 - x]))
+            .collect()
+    }
+
+    #[test]
+    fn test_crowding_distances() {
+        let distances = crowding_distances(&[vec![0.0, 4.0], vec![1.0, 2.0], vec![3.0, 1.0], vec![4.0, 0.0]]);
+        assert!(distances[0].is_infinite() && distances[3].is_infinite());
+        assert!((distances[1] - (0.75 + 0.75)).abs() < 1e-12);
+        assert!((distances[2] - (0.75 + 0.5)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_crowding_distance_archive_prunes_cluster() {
+        let problem = minimizing_problem(2);
+        let mut archive = CrowdingDistanceArchive::new(4);
+        for solution in bi_objective_front(&problem) {
+            archive.add(solution);
+        }
+        assert_eq!(archive.len(), 4);
+        let kept = sorted_objectives(&archive.solutions());
+        assert_eq!(kept.first().unwrap(), &vec![0.0, 10.0]);
+        assert_eq!(kept.last().unwrap(), &vec![10.0, 0.0]);
+        assert!(!archive.add(evaluated_solution(&problem, vec![11.0, 11.0])));
+        match archive.diversity().pruning {
+            PruningStatistics::CrowdingDistance { boundary_solutions, .. } => assert_eq!(boundary_solutions, 2),
+            other => panic!("unexpected statistics {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_adaptive_grid_archive_rejects_crowded_candidate() {
+        let problem = minimizing_problem(2);
+        let mut archive = AdaptiveGridArchive::new(4, Some(2));
+        assert!(archive.add(evaluated_solution(&problem, vec![0.0, 10.0])));
+        assert!(archive.add(evaluated_solution(&problem, vec![10.0, 0.0])));
+        assert!(archive.add(evaluated_solution(&problem, vec![6.0, 4.0])));
+        assert!(archive.add(evaluated_solution(&problem, vec![6.5, 3.5])));
+        // Joins (6, 4) and (6.5, 3.5) in the most crowded cell
+        assert!(!archive.add(evaluated_solution(&problem, vec![7.0, 3.0])));
+        // Lands in an empty cell, so a member of the crowded cell makes way
+        assert!(archive.add(evaluated_solution(&problem, vec![3.0, 6.0])));
+        assert_eq!(archive.len(), 4);
+        let diversity = archive.diversity();
+        assert_eq!(diversity.capacity, Some(4));
+        assert_eq!(diversity.pruning, PruningStatistics::AdaptiveGrid { occupied_cells: 4, max_density: 1, mean_density: 1.0 });
+    }
+
+    #[test]
+    fn test_hypervolume_archive_drops_least_contributor() {
+        let problem = minimizing_problem(2);
+        let mut archive = HypervolumeArchive::new(3, Some(vec![11.0, 11.0]));
+        archive.add(evaluated_solution(&problem, vec![0.0, 10.0]));
+        archive.add(evaluated_solution(&problem, vec![10.0, 0.0]));
+        archive.add(evaluated_solution(&problem, vec![5.0, 5.0]));
+        assert!(!archive.add(evaluated_solution(&problem, vec![5.5, 4.6])));
+        assert_eq!(sorted_objectives(&archive.solutions()), vec![vec![0.0, 10.0], vec![5.0, 5.0], vec![10.0, 0.0]]);
+        match archive.diversity().pruning {
+            PruningStatistics::Hypervolume { hypervolume, minimum_contribution } => {
+                assert_eq!(hypervolume, 5.0 + 30.0 + 11.0);
+                assert!(minimum_contribution > 0.0);
+            }
+            other => panic!("unexpected statistics {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_archives_are_interchangeable() {
+        let problem = minimizing_problem(3);
+        let mut rng = StdRng::seed_from_u64(5);
+        let offered: Vec<Solution> = (0..200)
+            .map(|_| {
+                let x: f64 = rng.gen();
+                let y: f64 = rng.gen();
+                evaluated_solution(&problem, vec![x, y, 2.0 - x - y])
+            })
+            .collect();
+        let archives: Vec<Box<dyn Archive>> = vec![
+            Box::new(NondominatedArchive::new()),
+            Box::new(CrowdingDistanceArchive::new(20)),
+            Box::new(AdaptiveGridArchive::new(20, None)),
+            Box::new(HypervolumeArchive::new(20, None)),
+        ];
+        for mut archive in archives {
+            for solution in &offered {
+                archive.add(solution.clone());
+            }
+            let diversity = archive.diversity();
+            assert_eq!(diversity.size, archive.len());
+            assert!(archive.len() <= diversity.capacity.unwrap_or(usize::MAX));
+            assert!(diversity.mean_nearest_neighbour_distance > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_archives_reject_zero_capacity() {
+        assert_eq!(CrowdingDistanceArchive::try_new(0).unwrap_err().to_string(), "Archive capacity must be greater than zero");
+        assert!(AdaptiveGridArchive::try_new(0, None).is_err());
+        assert!(AdaptiveGridArchive::try_new(20, Some(64)).is_err());
+        assert!(HypervolumeArchive::try_new(0, None).is_err());
+        assert!(HypervolumeArchive::try_new(1, None).is_ok());
+    }
+}
@@ -1,4 +1,8 @@
 use std::f64::consts::PI;
+use crate::error::{Error, Result};
+use crate::indicators::ReferenceFront;
+use crate::math_utils::euclidean_distance;
+use crate::reference_directions::{das_dennis, project_to_sphere};
 
 pub fn parabloid_hyper_5(input: &Vec<f64>) -> Vec<f64> {
     let offset = vec![1.0, 2.0, 3.0, 4.0, 1234.0];
@@ -49,95 +53,239 @@ pub fn xyz_objective(input: &Vec<f64>) -> Vec<f64> {
     result 
 }
 
+// DTLZ problems (Deb et al. 2005) with every variable in [0, 1]. DTLZ1-6 have input.len() - 1 objectives and
+// the last two variables as distance variables; DTLZ7 has input.len() objectives and the last variable as
+// distance variable. The remaining variables position the solution along the front.
+
+// Distance function of DTLZ1 and DTLZ3, zero when every distance variable is 0.5
+fn rastrigin_distance(distance: &[f64]) -> f64 {
+    100.0 * (distance.len() as f64 + distance.iter().map(|&x| (x - 0.5) * (x - 0.5) - (20.0 * PI * (x - 0.5)).cos()).sum::<f64>())
+}
+
+// Distance function of DTLZ2, DTLZ4 and DTLZ5, zero when every distance variable is 0.5
+fn sphere_distance(distance: &[f64]) -> f64 {
+    distance.iter().map(|&x| (x - 0.5) * (x - 0.5)).sum::<f64>()
+}
+
+// Position and distance variables of DTLZ1-6
+fn split_variables(input: &[f64]) -> (&[f64], &[f64]) {
+    input.split_at(input.len() - 2)
+}
+
 pub fn dtlz1(input: &Vec<f64>) -> Vec<f64> {
-    let n = input.len();
-    let k = n - 5;
-    let g = 100.0 * (k as f64 + input.iter().skip(n - k).map(|&x| (x - 0.5) * (x - 0.5) - (20.0 * PI * (x - 0.5)).cos()).sum::<f64>());
-    let mut f = vec![0.5 * (1.0 + g); n - 1];
-    for i in 0..(n - 1) {
-        f[i] = 0.5 * (1.0 + g) * input.iter().take(n - i - 1).product::<f64>();
-    }
-    f
+    let (position, distance) = split_variables(input);
+    let g = rastrigin_distance(distance);
+    let number_of_objectives = position.len() + 1;
+    (0..number_of_objectives)
+        .map(|m| {
+            let product: f64 = position[..number_of_objectives - 1 - m].iter().product();
+            let last = if m == 0 { 1.0 } else { 1.0 - position[number_of_objectives - 1 - m] };
+            0.5 * (1.0 + g) * product * last
+        })
+        .collect()
 }
 
 pub fn dtlz2(input: &Vec<f64>) -> Vec<f64> {
-    let n = input.len();
-    let k = n - 5;
-    let g = input.iter().skip(n - k).map(|&x| (x - 0.5) * (x - 0.5)).sum::<f64>();
-    let mut f = vec![1.0 + g; n - 1];
-    for i in 0..(n - 1) {
-        f[i] = (1.0 + g) * input.iter().take(n - i - 1).map(|&x| x.cos()).product::<f64>() * input[n - i - 2].sin();
-    }
-    f
+    let (position, distance) = split_variables(input);
+    let theta: Vec<f64> = position.iter().map(|&x| x * PI / 2.0).collect();
+    scaled(spherical_objectives(&theta), sphere_distance(distance))
 }
 
 pub fn dtlz3(input: &Vec<f64>) -> Vec<f64> {
-    let n = input.len();
-    let k = n - 5;
-    let g = 100.0 * (k as f64 + input.iter().skip(n - k).map(|&x| (x - 0.5) * (x - 0.5) - (20.0 * PI * (x - 0.5)).cos()).sum::<f64>());
-    let mut f = vec![1.0 + g; n - 1];
-    for i in 0..(n - 1) {
-        f[i] = (1.0 + g) * input.iter().take(n - i - 1).map(|&x| x.cos()).product::<f64>() * input[n - i - 2].sin();
-    }
-    f
+    let (position, distance) = split_variables(input);
+    let theta: Vec<f64> = position.iter().map(|&x| x * PI / 2.0).collect();
+    scaled(spherical_objectives(&theta), rastrigin_distance(distance))
 }
 
 pub fn dtlz4(input: &Vec<f64>, alpha: f64) -> Vec<f64> {
-    let n = input.len();
-    let k = n - 5;
-    let g = input.iter().skip(n - k).map(|&x| (x - 0.5) * (x - 0.5)).sum::<f64>();
-    let mut f = vec![1.0 + g; n - 1];
-    for i in 0..(n - 1) {
-        f[i] = (1.0 + g) * input.iter().take(n - i - 1).map(|&x| x.powf(alpha).cos()).product::<f64>() * input[n - i - 2].powf(alpha).sin();
-    }
-    f
+    let (position, distance) = split_variables(input);
+    let theta: Vec<f64> = position.iter().map(|&x| x.powf(alpha) * PI / 2.0).collect();
+    scaled(spherical_objectives(&theta), sphere_distance(distance))
 }
 
 pub fn dtlz5(input: &Vec<f64>) -> Vec<f64> {
-    let n = input.len();
-    let k = n - 5;
-    let g = input.iter().skip(n - k).map(|&x| (x - 0.5) * (x - 0.5)).sum::<f64>();
-    let mut theta = vec![0.0; n - 1];
-    theta[0] = input[0] * PI / 2.0;
-    for i in 1..(n - 1) {
-        theta[i] = (1.0 + 2.0 * g * input[i]).atan() / (4.0 * (1.0 + g));
-    }
-    let mut f = vec![1.0 + g; n - 1];
-    for i in 0..(n - 1) {
-        f[i] = (1.0 + g) * theta.iter().take(n - i - 1).map(|&t| t.cos()).product::<f64>() * theta[n - i - 2].sin();
-    }
-    f
+    let (position, distance) = split_variables(input);
+    degenerate_objectives(position, sphere_distance(distance))
 }
 
 pub fn dtlz6(input: &Vec<f64>) -> Vec<f64> {
-    let n = input.len();
-    let k = n - 5;
-    let g = input.iter().skip(n - k).map(|&x| x.powf(0.1)).sum::<f64>();
-    let mut theta = vec![0.0; n - 1];
-    theta[0] = input[0] * PI / 2.0;
-    for i in 1..(n - 1) {
-        theta[i] = (1.0 + 2.0 * g * input[i]).atan() / (4.0 * (1.0 + g));
-    }
-    let mut f = vec![1.0 + g; n - 1];
-    for i in 0..(n - 1) {
-        f[i] = (1.0 + g) * theta.iter().take(n - i - 1).map(|&t| t.cos()).product::<f64>() * theta[n - i - 2].sin();
-    }
-    f
+    let (position, distance) = split_variables(input);
+    degenerate_objectives(position, distance.iter().map(|&x| x.powf(0.1)).sum::<f64>())
 }
 
 pub fn dtlz7(input: &Vec<f64>) -> Vec<f64> {
-    let n = input.len();
-    let k = n - 5;
-    let g = 1.0 + 9.0 * input.iter().skip(n - k).sum::<f64>() / k as f64;
-    let mut f = vec![0.0; n - 1];
-    for i in 0..(n - 1) {
-        f[i] = input[i];
-    }
-    let h = (n - 1) as f64 - f.iter().map(|&x| x / (1.0 + g) * (1.0 + (2.0 * PI * x).sin())).sum::<f64>();
+    let (position, distance) = input.split_at(input.len() - 1);
+    let g = 1.0 + 9.0 * distance.iter().sum::<f64>() / distance.len() as f64;
+    let h = input.len() as f64 - position.iter().map(|&f| f / (1.0 + g) * (1.0 + (3.0 * PI * f).sin())).sum::<f64>();
+    let mut f = position.to_vec();
     f.push((1.0 + g) * h);
     f
 }
 
+fn scaled(objectives: Vec<f64>, g: f64) -> Vec<f64> {
+    objectives.into_iter().map(|f| (1.0 + g) * f).collect()
+}
+
+// DTLZ5 and DTLZ6: only the first angle follows its variable, the others tend to pi / 4 as g reaches zero
+fn degenerate_objectives(position: &[f64], g: f64) -> Vec<f64> {
+    let theta: Vec<f64> = position
+        .iter()
+        .enumerate()
+        .map(|(i, &x)| if i == 0 { x * PI / 2.0 } else { PI / (4.0 * (1.0 + g)) * (1.0 + 2.0 * g * x) })
+        .collect();
+    scaled(spherical_objectives(&theta), g)
+}
+
+// Analytical Pareto fronts of the DTLZ problems (Deb et al. 2005), sampled with a given number of points
+// so indicators such as IGD have a true front to compare against. The front is reached at g = 0 (g = 1 for
+// DTLZ7).
+
+// Bounds of the two x-intervals on which x * (1 + sin(3 pi x)) is a running maximum: the Pareto optimal
+// values of DTLZ7's position variables
+const DTLZ7_REGIONS: [(f64, f64); 2] = [(0.0, 0.2514118360889854), (0.6316265307000614, 0.8594008566447239)];
+
+fn check_front_size(number_of_objectives: usize, number_of_points: usize) -> Result<()> {
+    if number_of_objectives < 2 {
+        return Err(Error::InvalidConfiguration("Pareto fronts need at least 2 objectives"));
+    }
+    if number_of_points == 0 {
+        return Err(Error::InvalidConfiguration("number_of_points must be greater than zero"));
+    }
+    Ok(())
+}
+
+// Smallest lattice on the unit simplex with at least the requested number of points
+fn covering_simplex_lattice(number_of_objectives: usize, number_of_points: usize) -> Vec<Vec<f64>> {
+    let mut divisions = 1;
+    loop {
+        let lattice = das_dennis(number_of_objectives, divisions);
+        if lattice.len() >= number_of_points {
+            return lattice;
+        }
+        divisions += 1;
+    }
+}
+
+// Greedily keeps the candidate farthest from those already kept, starting from the first one
+fn farthest_point_selection(candidates: Vec<Vec<f64>>, number_of_points: usize) -> Vec<Vec<f64>> {
+    if candidates.len() <= number_of_points {
+        return candidates;
+    }
+    let mut closest = vec![f64::INFINITY; candidates.len()];
+    let mut selected = vec![0];
+    while selected.len() < number_of_points {
+        let last = &candidates[*selected.last().unwrap()];
+        for (distance, candidate) in closest.iter_mut().zip(candidates.iter()) {
+            *distance = distance.min(euclidean_distance(candidate, last));
+        }
+        let next = (0..candidates.len())
+            .max_by(|&a, &b| closest[a].total_cmp(&closest[b]))
+            .unwrap();
+        selected.push(next);
+    }
+    selected.into_iter().map(|i| candidates[i].clone()).collect()
+}
+
+// Objectives of the DTLZ2 family from the angles theta (radius 1)
+fn spherical_objectives(theta: &[f64]) -> Vec<f64> {
+    let number_of_objectives = theta.len() + 1;
+    (0..number_of_objectives)
+        .map(|m| {
+            let cosines: f64 = theta[..number_of_objectives - 1 - m].iter().map(|t| t.cos()).product();
+            if m == 0 { cosines } else { cosines * theta[number_of_objectives - 1 - m].sin() }
+        })
+        .collect()
+}
+
+/// Linear front of DTLZ1: the simplex where the objectives sum to 0.5
+pub fn dtlz1_pareto_front(number_of_objectives: usize, number_of_points: usize) -> Result<ReferenceFront> {
+    check_front_size(number_of_objectives, number_of_points)?;
+    let lattice = covering_simplex_lattice(number_of_objectives, number_of_points);
+    let points = farthest_point_selection(lattice, number_of_points);
+    ReferenceFront::try_new(points.into_iter().map(|point| point.iter().map(|f| 0.5 * f).collect()).collect())
+}
+
+/// Spherical front of DTLZ2: the positive part of the unit hypersphere
+pub fn dtlz2_pareto_front(number_of_objectives: usize, number_of_points: usize) -> Result<ReferenceFront> {
+    check_front_size(number_of_objectives, number_of_points)?;
+    let sphere = project_to_sphere(&covering_simplex_lattice(number_of_objectives, number_of_points));
+    ReferenceFront::try_new(farthest_point_selection(sphere, number_of_points))
+}
+
+/// DTLZ3 shares DTLZ2's front
+pub fn dtlz3_pareto_front(number_of_objectives: usize, number_of_points: usize) -> Result<ReferenceFront> {
+    dtlz2_pareto_front(number_of_objectives, number_of_points)
+}
+
+/// DTLZ4 shares DTLZ2's front
+pub fn dtlz4_pareto_front(number_of_objectives: usize, number_of_points: usize) -> Result<ReferenceFront> {
+    dtlz2_pareto_front(number_of_objectives, number_of_points)
+}
+
+/// Degenerate front of DTLZ5: a quarter of a great circle of the unit hypersphere, sampled evenly by arc length
+pub fn dtlz5_pareto_front(number_of_objectives: usize, number_of_points: usize) -> Result<ReferenceFront> {
+    check_front_size(number_of_objectives, number_of_points)?;
+    let points = (0..number_of_points)
+        .map(|i| {
+            let position = if number_of_points == 1 { 0.5 } else { i as f64 / (number_of_points - 1) as f64 };
+            let mut theta = vec![PI / 4.0; number_of_objectives - 1];
+            theta[0] = position * PI / 2.0;
+            spherical_objectives(&theta)
+        })
+        .collect();
+    ReferenceFront::try_new(points)
+}
+
+/// DTLZ6 shares DTLZ5's front
+pub fn dtlz6_pareto_front(number_of_objectives: usize, number_of_points: usize) -> Result<ReferenceFront> {
+    dtlz5_pareto_front(number_of_objectives, number_of_points)
+}
+
+/// Disconnected front of DTLZ7: 2^(M-1) regions, one per combination of the optimal intervals of the
+/// position variables
+pub fn dtlz7_pareto_front(number_of_objectives: usize, number_of_points: usize) -> Result<ReferenceFront> {
+    check_front_size(number_of_objectives, number_of_points)?;
+    let dimensions = number_of_objectives - 1;
+    let mut resolution: usize = 2;
+    while resolution.pow(dimensions as u32) < number_of_points {
+        resolution += 1;
+    }
+    // Evenly spaced positions along the two intervals laid end to end
+    let total_length: f64 = DTLZ7_REGIONS.iter().map(|(low, high)| high - low).sum();
+    let positions: Vec<f64> = (0..resolution)
+        .map(|i| {
+            let mut offset = total_length * i as f64 / (resolution - 1) as f64;
+            let (low, high) = DTLZ7_REGIONS[0];
+            if offset <= high - low {
+                low + offset
+            } else {
+                offset -= high - low;
+                (DTLZ7_REGIONS[1].0 + offset).min(DTLZ7_REGIONS[1].1)
+            }
+        })
+        .collect();
+    let mut candidates = Vec::with_capacity(resolution.pow(dimensions as u32));
+    let mut indices = vec![0; dimensions];
+    loop {
+        let mut point: Vec<f64> = indices.iter().map(|&i| positions[i]).collect();
+        let h = number_of_objectives as f64
+            - point.iter().map(|&f| f / 2.0 * (1.0 + (3.0 * PI * f).sin())).sum::<f64>();
+        point.push(2.0 * h);
+        candidates.push(point);
+        // Next grid index, odometer style
+        let mut d = 0;
+        while d < dimensions && indices[d] == resolution - 1 {
+            indices[d] = 0;
+            d += 1;
+        }
+        if d == dimensions {
+            break;
+        }
+        indices[d] += 1;
+    }
+    ReferenceFront::try_new(farthest_point_selection(candidates, number_of_points))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -231,6 +379,85 @@ mod tests {
 
         // Add specific checks based on expected results
     }
-}
 
+    fn assert_front_shape(front: &ReferenceFront, number_of_points: usize, number_of_objectives: usize) {
+        assert_eq!(front.points.len(), number_of_points);
+        assert!(front.points.iter().all(|point| point.len() == number_of_objectives));
+    }
+
+    #[test]
+    fn test_dtlz1_pareto_front() {
+        for number_of_objectives in 2..=5 {
+            let front = dtlz1_pareto_front(number_of_objectives, 50).unwrap();
+            assert_front_shape(&front, 50, number_of_objectives);
+            assert!(front.points.iter().all(|p| (p.iter().sum::<f64>() - 0.5).abs() < 1e-12 && p.iter().all(|&f| f >= 0.0)));
+        }
+        // Thinning keeps the corners of the simplex
+        let front = dtlz1_pareto_front(3, 10).unwrap();
+        for m in 0..3 {
+            assert!(front.points.iter().any(|p| p[m] == 0.5));
+        }
+    }
+
+    #[test]
+    fn test_dtlz2_to_dtlz4_pareto_fronts() {
+        for generator in [dtlz2_pareto_front, dtlz3_pareto_front, dtlz4_pareto_front] {
+            let front = generator(3, 91).unwrap();
+            assert_front_shape(&front, 91, 3);
+            assert!(front.points.iter().all(|p| (p.iter().map(|f| f * f).sum::<f64>() - 1.0).abs() < 1e-12));
+        }
+    }
+
+    #[test]
+    fn test_dtlz5_and_dtlz6_pareto_fronts() {
+        for generator in [dtlz5_pareto_front, dtlz6_pareto_front] {
+            let front = generator(4, 20).unwrap();
+            assert_front_shape(&front, 20, 4);
+            for p in &front.points {
+                assert!((p.iter().map(|f| f * f).sum::<f64>() - 1.0).abs() < 1e-12);
+                // Degenerate: the first two objectives stay equal along the curve
+                assert!((p[0] - p[1]).abs() < 1e-12);
+            }
+            // Evenly spread along the arc
+            let gaps: Vec<f64> = front.points.windows(2).map(|w| euclidean_distance(&w[0], &w[1])).collect();
+            assert!(gaps.iter().all(|g| (g - gaps[0]).abs() < 1e-9));
+        }
+    }
 
+    #[test]
+    fn test_dtlz7_pareto_front() {
+        let front = dtlz7_pareto_front(2, 100).unwrap();
+        assert_front_shape(&front, 100, 2);
+        // Mutually non-dominated and split into two regions
+        for a in &front.points {
+            for b in &front.points {
+                assert!(!(b[0] <= a[0] && b[1] <= a[1] && b != a));
+            }
+        }
+        assert!(front.points.iter().all(|p| p[0] <= DTLZ7_REGIONS[0].1 || p[0] >= DTLZ7_REGIONS[1].0));
+        let front = dtlz7_pareto_front(3, 40).unwrap();
+        assert_front_shape(&front, 40, 3);
+    }
+
+    #[test]
+    fn test_pareto_front_size() {
+        assert_eq!(dtlz2_pareto_front(1, 10).unwrap_err().to_string(), "Pareto fronts need at least 2 objectives");
+        assert!(dtlz7_pareto_front(3, 0).is_err());
+    }
+
+    #[test]
+    fn test_optimal_solutions_lie_on_pareto_fronts() {
+        // Three objectives; each decision vector has its distance variables at the optimum and its position
+        // variables chosen to land on one of the sampled points
+        let on_front = |objectives: Vec<f64>, front: Result<ReferenceFront>| {
+            assert!(front.unwrap().points.iter().any(|point| euclidean_distance(point, &objectives) < 1e-9), "{:?}", objectives);
+        };
+        on_front(dtlz1(&vec![1.0, 0.5, 0.5, 0.5]), dtlz1_pareto_front(3, 6));
+        on_front(dtlz2(&vec![0.5, 1.0, 0.5, 0.5]), dtlz2_pareto_front(3, 6));
+        on_front(dtlz3(&vec![0.5, 1.0, 0.5, 0.5]), dtlz3_pareto_front(3, 6));
+        on_front(dtlz4(&vec![0.5f64.powf(0.01), 1.0, 0.5, 0.5], 100.0), dtlz4_pareto_front(3, 6));
+        on_front(dtlz5(&vec![0.5, 0.3, 0.5, 0.5]), dtlz5_pareto_front(3, 3));
+        on_front(dtlz6(&vec![0.5, 0.3, 0.0, 0.0]), dtlz6_pareto_front(3, 3));
+        on_front(dtlz7(&vec![0.0, DTLZ7_REGIONS[1].1, 0.0]), dtlz7_pareto_front(3, 4));
+    }
+}
@@ -0,0 +1,76 @@
+// Activation conditions of hierarchical decision variables: a conditional variable only takes part in the
+// search while the single-gene variable controlling it holds a value that activates it.
+use crate::error::{Error, Result};
+
+/// Values of the controlling variable that activate a conditional variable
+#[derive(Debug, Clone, PartialEq)]
+pub enum Activation {
+    Equals(f64),
+    OneOf(Vec<f64>),
+    /// At least the given value, e.g. layer k is active while the number of layers is at least k
+    AtLeast(f64),
+    /// Between the two values, both included
+    Between(f64, f64),
+}
+
+impl Activation {
+    pub fn is_satisfied(&self, value: f64) -> bool {
+        match self {
+            Activation::Equals(expected) => value == *expected,
+            Activation::OneOf(values) => values.contains(&value),
+            Activation::AtLeast(minimum) => value >= *minimum,
+            Activation::Between(minimum, maximum) => value >= *minimum && value <= *maximum,
+        }
+    }
+}
+
+/// The variable at `variable` is active only while the variable at `controller` is itself active and
+/// satisfies `activation`. Several conditions on the same variable must all hold.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Condition {
+    pub variable: usize,
+    pub controller: usize,
+    pub activation: Activation,
+}
+
+impl Condition {
+    pub fn new(variable: usize, controller: usize, activation: Activation) -> Self {
+        Self::try_new(variable, controller, activation).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Like `new`, but reports a self-controlled variable as an error
+    pub fn try_new(variable: usize, controller: usize, activation: Activation) -> Result<Self> {
+        if variable == controller {
+            return Err(Error::InvalidConfiguration("A variable cannot control its own activation"));
+        }
+        Ok(Self { variable, controller, activation })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_activation() {
+        assert!(Activation::Equals(2.0).is_satisfied(2.0));
+        assert!(!Activation::Equals(2.0).is_satisfied(3.0));
+        assert!(Activation::OneOf(vec![1.0, 3.0]).is_satisfied(3.0));
+        assert!(Activation::AtLeast(2.0).is_satisfied(4.0));
+        assert!(!Activation::AtLeast(2.0).is_satisfied(1.0));
+        assert!(Activation::Between(1.0, 2.0).is_satisfied(2.0));
+        assert!(!Activation::Between(1.0, 2.0).is_satisfied(2.5));
+    }
+
+    #[test]
+    #[should_panic(expected = "A variable cannot control its own activation")]
+    fn test_self_controlled_condition() {
+        Condition::new(1, 1, Activation::Equals(0.0));
+    }
+
+    #[test]
+    fn test_try_new() {
+        assert!(Condition::try_new(1, 1, Activation::Equals(0.0)).is_err());
+        assert_eq!(Condition::try_new(1, 0, Activation::Equals(0.0)).unwrap().controller, 0);
+    }
+}
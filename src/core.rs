@@ -1,8 +1,26 @@
 // import SolutionTypes, BitBinary, Integer, Real from  gatypes.rs
 // use crate::gatypes::{SolutionType, BitBinary, Integer, Real};
+use std::fmt;
+use std::ops::Range;
+use rand::Rng;
+use crate::conditions::Condition;
+use crate::expression_tree::ExpressionTree;
+use crate::metadata::Metadata;
+use crate::error::{Error, Result};
 use crate::constraints::ComparisonFunctions;
 
-use crate::gatypes::SolutionDataTypes;
+use crate::gatypes::{Gene, NativeValue, SolutionDataTypes};
+
+/// Objective function over the typed genes of a solution
+pub type TypedObjectiveFunction = fn(genes: &[Gene]) -> Vec<f64>;
+
+/// Objective function of a problem, over the numeric values of the genes or over the typed genes
+#[derive(Debug, Clone, Copy)]
+pub enum ObjectiveFunction {
+    Numeric(fn(&Vec<f64>) -> Vec<f64>),
+    /// Integers beyond 2^53, bits and element indices reach the objectives exactly
+    Typed(TypedObjectiveFunction),
+}
 
 #[derive(Debug)]
 pub struct Problem {
@@ -12,7 +30,31 @@ pub struct Problem {
     pub objective_constraint_operands: Option<Vec<Option<String>>>, // Operands for Greater than or less than the objective constraint eg. ["<", ">"]
     pub direction: Option<Vec<i8>>, // Defaults vector to -1 with length of number_of_objectives eg. [-1, -1]
     pub solution_data_types: Vec<SolutionDataTypes>,     // solution type is a vector of the solution types eg. [BitBinary, Integer(lower_bound:Some(10), upper_bound:Some(20)), Real(lower_bound:Some(1.0), upper_bound:Some(20.0))]
-    pub objective_function: fn(solution: &Vec<f64>) -> Vec<f64> // Objective function that takes the SolutionTypes vector values and returns a vector of f64 values
+    pub conditions: Vec<Condition>, // Activation conditions of variables, see `with_condition`
+    pub variable_length: Option<VariableLength>, // Repeats solution_data_types as a template, see `with_variable_length`
+    pub variable_metadata: Vec<Metadata>, // Names, units and descriptions of the variables, see `with_variable_metadata`
+    pub objective_metadata: Vec<Metadata>, // Names, units and descriptions of the objectives, see `with_objective_metadata`
+    pub objective_function: ObjectiveFunction // Objective function that takes the SolutionTypes vector values, or the typed genes, and returns a vector of f64 values
+}
+
+/// Problem without variables or objectives, for filling in only the relevant fields of a struct literal
+/// with `..Default::default()`
+impl Default for Problem {
+    fn default() -> Self {
+        Self {
+            solution_length: 0,
+            number_of_objectives: 0,
+            objective_constraint: None,
+            objective_constraint_operands: None,
+            direction: None,
+            solution_data_types: Vec::new(),
+            conditions: Vec::new(),
+            variable_length: None,
+            variable_metadata: Vec::new(),
+            objective_metadata: Vec::new(),
+            objective_function: ObjectiveFunction::Numeric(|_| Vec::new()),
+        }
+    }
 }
 
 impl Problem {
@@ -25,21 +67,118 @@ impl Problem {
         solution_data_types: Vec<SolutionDataTypes>,// Vec of Binary or Integer or Real
         objective_function: fn(&Vec<f64>) -> Vec<f64>
     ) -> Self {
-        // If solution_length != solution_data_types.len() panic
+        Self::try_new(
+            solution_length,
+            number_of_objectives,
+            objective_constraint,
+            objective_constraint_operands,
+            direction,
+            solution_data_types,
+            objective_function,
+        )
+        .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Like `new`, but reports mismatched lengths, invalid operands and constraints without an operand as
+    /// an error
+    pub fn try_new(
+        solution_length: usize,
+        number_of_objectives: usize,
+        objective_constraint: Option<Vec<Option<f64>>>,
+        objective_constraint_operands: Option<Vec<Option<String>>>,
+        direction: Option<Vec<i8>>,
+        solution_data_types: Vec<SolutionDataTypes>,
+        objective_function: fn(&Vec<f64>) -> Vec<f64>
+    ) -> Result<Self> {
+        Self::try_with_objective_function(
+            solution_length,
+            number_of_objectives,
+            objective_constraint,
+            objective_constraint_operands,
+            direction,
+            solution_data_types,
+            ObjectiveFunction::Numeric(objective_function),
+        )
+    }
+
+    /// Like `new`, but with an objective function over the typed genes
+    pub fn new_typed(
+        solution_length: usize,
+        number_of_objectives: usize,
+        objective_constraint: Option<Vec<Option<f64>>>,
+        objective_constraint_operands: Option<Vec<Option<String>>>,
+        direction: Option<Vec<i8>>,
+        solution_data_types: Vec<SolutionDataTypes>,
+        typed_objective_function: TypedObjectiveFunction
+    ) -> Self {
+        Self::try_new_typed(
+            solution_length,
+            number_of_objectives,
+            objective_constraint,
+            objective_constraint_operands,
+            direction,
+            solution_data_types,
+            typed_objective_function,
+        )
+        .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Like `new_typed`, but reports invalid settings as `try_new` does
+    pub fn try_new_typed(
+        solution_length: usize,
+        number_of_objectives: usize,
+        objective_constraint: Option<Vec<Option<f64>>>,
+        objective_constraint_operands: Option<Vec<Option<String>>>,
+        direction: Option<Vec<i8>>,
+        solution_data_types: Vec<SolutionDataTypes>,
+        typed_objective_function: TypedObjectiveFunction
+    ) -> Result<Self> {
+        Self::try_with_objective_function(
+            solution_length,
+            number_of_objectives,
+            objective_constraint,
+            objective_constraint_operands,
+            direction,
+            solution_data_types,
+            ObjectiveFunction::Typed(typed_objective_function),
+        )
+    }
+
+    fn try_with_objective_function(
+        solution_length: usize,
+        number_of_objectives: usize,
+        objective_constraint: Option<Vec<Option<f64>>>,
+        objective_constraint_operands: Option<Vec<Option<String>>>,
+        direction: Option<Vec<i8>>,
+        solution_data_types: Vec<SolutionDataTypes>,
+        objective_function: ObjectiveFunction
+    ) -> Result<Self> {
         if solution_length != solution_data_types.len() {
-            panic!("solution_length does not match solution_data_types length");
+            return Err(Error::LengthMismatch {
+                what: "solution_length does not match solution_data_types length",
+                expected: solution_data_types.len(),
+                found: solution_length,
+            });
         }
 
         // Check if lengths match number_of_objectives
         if let Some(ref constraints) = objective_constraint {
             if constraints.len() != number_of_objectives {
-                panic!("objective_constraint length does not match number_of_objectives");
+                return Err(Error::LengthMismatch {
+                    what: "objective_constraint length does not match number_of_objectives",
+                    expected: number_of_objectives,
+                    found: constraints.len(),
+                });
             }
         }
 
         if let Some(ref operands) = objective_constraint_operands {
             if operands.len() != number_of_objectives {
-                panic!("objective_constraint_operands length does not match number_of_objectives");
+                return Err(Error::LengthMismatch {
+                    what: "objective_constraint_operands length does not match number_of_objectives",
+                    expected: number_of_objectives,
+                    found: operands.len(),
+                });
             }
         }
 
@@ -47,19 +186,185 @@ impl Problem {
 
         if let Some(ref dirs) = direction {
             if dirs.len() != number_of_objectives {
-                panic!("direction length does not match number_of_objectives");
+                return Err(Error::LengthMismatch {
+                    what: "direction length does not match number_of_objectives",
+                    expected: number_of_objectives,
+                    found: dirs.len(),
+                });
             }
         }
 
-        Problem {
+        let problem = Problem {
             solution_length,
             number_of_objectives,
             objective_constraint,
             objective_constraint_operands,
             direction,
             solution_data_types,
-            objective_function
+            objective_function,
+            ..Default::default()
+        };
+        problem.try_objective_constraint_operands()?;
+        Ok(problem)
+    }
+
+    /// Names the variables of `solution_data_types`, one entry per variable; names must be unique
+    pub fn with_variable_metadata(self, variable_metadata: Vec<Metadata>) -> Self {
+        self.try_with_variable_metadata(variable_metadata).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_with_variable_metadata(mut self, variable_metadata: Vec<Metadata>) -> Result<Self> {
+        if variable_metadata.len() != self.solution_data_types.len() {
+            return Err(Error::LengthMismatch {
+                what: "variable_metadata length does not match solution_data_types length",
+                expected: self.solution_data_types.len(),
+                found: variable_metadata.len(),
+            });
+        }
+        if let Some(name) = duplicate_name(&variable_metadata) {
+            return Err(Error::DuplicateName { kind: "Variable", name: name.to_string() });
+        }
+        self.variable_metadata = variable_metadata;
+        Ok(self)
+    }
+
+    /// Names the objectives, one entry per objective; names must be unique
+    pub fn with_objective_metadata(self, objective_metadata: Vec<Metadata>) -> Self {
+        self.try_with_objective_metadata(objective_metadata).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_with_objective_metadata(mut self, objective_metadata: Vec<Metadata>) -> Result<Self> {
+        if objective_metadata.len() != self.number_of_objectives {
+            return Err(Error::LengthMismatch {
+                what: "objective_metadata length does not match number_of_objectives",
+                expected: self.number_of_objectives,
+                found: objective_metadata.len(),
+            });
+        }
+        if let Some(name) = duplicate_name(&objective_metadata) {
+            return Err(Error::DuplicateName { kind: "Objective", name: name.to_string() });
         }
+        self.objective_metadata = objective_metadata;
+        Ok(self)
+    }
+
+    /// Name of the variable at `index`, `x<index>` when unnamed. Repetitions of a variable-length template
+    /// are told apart by their repetition, e.g. `turbine_x[2]`.
+    pub fn variable_name(&self, index: usize) -> String {
+        let count = self.solution_data_types.len();
+        match (self.variable_metadata.get(index % count.max(1)), &self.variable_length) {
+            (Some(metadata), Some(_)) => format!("{}[{}]", metadata.name, index / count),
+            (Some(metadata), None) => metadata.name.clone(),
+            (None, _) => format!("x{}", index),
+        }
+    }
+
+    /// Name of the objective at `index`, `f<index>` when unnamed
+    pub fn objective_name(&self, index: usize) -> String {
+        match self.objective_metadata.get(index) {
+            Some(metadata) => metadata.name.clone(),
+            None => format!("f{}", index),
+        }
+    }
+
+    /// Index in `solution_data_types` of the variable named `name`
+    pub fn variable_index(&self, name: &str) -> Option<usize> {
+        self.variable_metadata.iter().position(|metadata| metadata.name == name)
+    }
+
+    /// Index of the objective named `name`
+    pub fn objective_index(&self, name: &str) -> Option<usize> {
+        self.objective_metadata.iter().position(|metadata| metadata.name == name)
+    }
+
+    /// Switches to variable-length genomes: a genome holds between `min_length` and `max_length` repetitions
+    /// of `solution_data_types`, which act as a per-gene type template (e.g. the x and y of one wind turbine)
+    pub fn with_variable_length(self, min_length: usize, max_length: usize) -> Self {
+        self.try_with_variable_length(min_length, max_length).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_with_variable_length(mut self, min_length: usize, max_length: usize) -> Result<Self> {
+        if min_length == 0 || min_length > max_length {
+            return Err(Error::InvalidConfiguration("Variable length requires 0 < min_length <= max_length"));
+        }
+        if self.solution_data_types.iter().any(|solution_type| solution_type.gene_count() != 1) {
+            return Err(Error::InvalidConfiguration("Variable-length templates must hold single-gene types"));
+        }
+        if !self.conditions.is_empty() {
+            return Err(Error::InvalidConfiguration("Conditions are not supported on variable-length genomes"));
+        }
+        self.variable_length = Some(VariableLength { min_length, max_length });
+        Ok(self)
+    }
+
+    /// Number of template repetitions in a genome, 1 for fixed-length problems
+    pub fn repetitions(&self, genome: &[Gene]) -> usize {
+        match self.variable_length {
+            Some(_) => genome.len() / self.solution_data_types.len(),
+            None => 1,
+        }
+    }
+
+    /// Type of the variable at `index`; in variable-length genomes the template repeats
+    pub fn variable_type(&self, index: usize) -> &SolutionDataTypes {
+        match self.variable_length {
+            Some(_) => &self.solution_data_types[index % self.solution_data_types.len()],
+            None => &self.solution_data_types[index],
+        }
+    }
+
+    /// Declares an activation condition. The controlling variable must be another variable holding a single
+    /// gene, conditions must not form a cycle and the genome must have a fixed length. The objective function
+    /// then receives only the active variables.
+    pub fn with_condition(self, condition: Condition) -> Self {
+        self.try_with_condition(condition).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_with_condition(mut self, condition: Condition) -> Result<Self> {
+        let count = self.solution_data_types.len();
+        if condition.variable >= count || condition.controller >= count {
+            return Err(Error::InvalidConfiguration("Condition refers to a variable that does not exist"));
+        }
+        if condition.variable == condition.controller {
+            return Err(Error::InvalidConfiguration("A variable cannot control its own activation"));
+        }
+        if self.solution_data_types[condition.controller].gene_count() != 1 {
+            return Err(Error::InvalidConfiguration("A controlling variable must hold a single gene"));
+        }
+        if self.depends_on(condition.controller, condition.variable) {
+            return Err(Error::InvalidConfiguration("Conditions must not form a cycle"));
+        }
+        if self.variable_length.is_some() {
+            return Err(Error::InvalidConfiguration("Conditions are not supported on variable-length genomes"));
+        }
+        self.conditions.push(condition);
+        Ok(self)
+    }
+
+    // Whether the activation of `variable` depends, possibly through other conditions, on `target`
+    fn depends_on(&self, variable: usize, target: usize) -> bool {
+        self.conditions
+            .iter()
+            .filter(|condition| condition.variable == variable)
+            .any(|condition| condition.controller == target || self.depends_on(condition.controller, target))
+    }
+
+    /// Whether the variable at `index` is active in the genome; variables without conditions always are
+    pub fn is_active(&self, index: usize, genome: &[Gene]) -> bool {
+        self.conditions.iter().filter(|condition| condition.variable == index).all(|condition| {
+            self.is_active(condition.controller, genome)
+                && condition.activation.is_satisfied(genome[self.gene_range(condition.controller).start].as_f64())
+        })
+    }
+
+    /// Genes of the active variables only, in variable order. This is what the objective function
+    /// receives when conditions are declared.
+    pub fn active_genes(&self, genome: &[Gene]) -> Vec<Gene> {
+        self.variables_in(genome)
+            .enumerate()
+            .filter(|(index, _)| self.is_active(*index, genome))
+            .flat_map(|(_, (genes, _))| genome[genes].iter().copied())
+            .collect()
     }
 
     pub fn solution_length(&self) -> &usize {
@@ -75,19 +380,27 @@ impl Problem {
     }
 
     pub fn objective_constraint_operands(&self) -> &Option<Vec<Option<String>>> {
-        // Check if Operatnds are are <, >, <=, >=, ==, !=
-        let operands = &self.objective_constraint_operands;
-        if operands.is_some() {
-            let operands  = operands.as_ref().unwrap();
-            for operand in operands {
-                let operand = operand.as_ref().unwrap();
-                if operand != "<" && operand != ">" && operand != "<=" && operand != ">=" && operand != "==" && operand != "!=" {
-                    panic!("Invalid operand: {}", operand);
+        self.try_objective_constraint_operands().unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// The operands after checking that they are <, >, <=, >=, == or != and that every objective has either
+    /// both a constraint value and an operand or neither
+    pub fn try_objective_constraint_operands(&self) -> Result<&Option<Vec<Option<String>>>> {
+        if let Some(operands) = &self.objective_constraint_operands {
+            for operand in operands.iter().flatten() {
+                if !["<", ">", "<=", ">=", "==", "!="].contains(&operand.as_str()) {
+                    return Err(Error::InvalidOperator(operand.clone()));
                 }
             }
         }
-        
-        &self.objective_constraint_operands
+        for objective in 0..self.number_of_objectives {
+            let has_constraint = self.objective_constraint.as_ref().and_then(|values| values.get(objective)).is_some_and(Option::is_some);
+            let has_operand = self.objective_constraint_operands.as_ref().and_then(|values| values.get(objective)).is_some_and(Option::is_some);
+            if has_constraint != has_operand {
+                return Err(Error::UnpairedConstraint { objective });
+            }
+        }
+        Ok(&self.objective_constraint_operands)
     }
 
     pub fn direction(&self) -> &Option<Vec<i8>> {
@@ -98,22 +411,99 @@ impl Problem {
         &self.solution_data_types
     }
 
-    pub fn objective_function(&self) -> &fn(&Vec<f64>) -> Vec<f64> {
+    pub fn objective_function(&self) -> &ObjectiveFunction {
         &self.objective_function
     }
 
-    pub fn generate_solution(&self) -> Vec<f64> {
-        let mut solution: Vec<f64> = Vec::new();
+    /// Returns the objective values in minimization form: objectives with direction 1 (maximize) are negated.
+    pub fn oriented_objectives(&self, objective_values: &[f64]) -> Vec<f64> {
+        match &self.direction {
+            Some(direction) => objective_values
+                .iter()
+                .zip(direction.iter())
+                .map(|(&value, &dir)| if dir == 1 { -value } else { value })
+                .collect(),
+            None => objective_values.to_vec(),
+        }
+    }
+
+    /// Range of genes in the genome taken by the variable at `index`
+    pub fn gene_range(&self, index: usize) -> Range<usize> {
+        if self.variable_length.is_some() {
+            return index..index + 1;
+        }
+        let start: usize = self.solution_data_types[..index].iter().map(|t| t.gene_count()).sum();
+        start..start + self.solution_data_types[index].gene_count()
+    }
+
+    /// Total number of genes, which differs from `solution_length` when a variable spans several genes
+    pub fn genome_length(&self) -> usize {
+        self.solution_data_types.iter().map(|t| t.gene_count()).sum()
+    }
+
+    /// Every variable together with its range of genes
+    pub fn variables(&self) -> impl Iterator<Item = (Range<usize>, &SolutionDataTypes)> {
+        self.solution_data_types.iter().scan(0, |start, solution_type| {
+            let genes = *start..*start + solution_type.gene_count();
+            *start = genes.end;
+            Some((genes, solution_type))
+        })
+    }
+
+    /// Every variable of a particular genome together with its range of genes; unlike `variables` this
+    /// covers all template repetitions of a variable-length genome
+    pub fn variables_in(&self, genome: &[Gene]) -> impl Iterator<Item = (Range<usize>, &SolutionDataTypes)> {
+        let count = match self.variable_length {
+            Some(_) => genome.len(),
+            None => self.solution_data_types.len(),
+        };
+        (0..count).map(move |index| (self.gene_range(index), self.variable_type(index)))
+    }
+
+    /// Variables present in both genomes, i.e. those of the shorter one
+    pub fn shared_variables(&self, genome1: &[Gene], genome2: &[Gene]) -> impl Iterator<Item = (Range<usize>, &SolutionDataTypes)> {
+        self.variables_in(if genome1.len() <= genome2.len() { genome1 } else { genome2 })
+    }
+
+    pub fn generate_solution(&self) -> Vec<Gene> {
+        match &self.variable_length {
+            Some(variable_length) => {
+                let repetitions = rand::thread_rng().gen_range(variable_length.min_length..=variable_length.max_length);
+                (0..repetitions).flat_map(|_| self.generate_unit()).collect()
+            }
+            None => self.generate_unit(),
+        }
+    }
+
+    /// Random genes for one pass over `solution_data_types`: the whole genome of a fixed-length problem, or
+    /// one template repetition of a variable-length one
+    pub fn generate_unit(&self) -> Vec<Gene> {
+        let mut solution: Vec<Gene> = Vec::new();
         for solution_type in &self.solution_data_types {
             match solution_type {
                 SolutionDataTypes::BitBinary(binary) => {
-                    solution.push(binary.generate_value().unwrap() as f64);
+                    solution.push(Gene::Bit(binary.generate_value().unwrap() == 1));
                 }
                 SolutionDataTypes::Integer(integer) => {
-                    solution.push(integer.generate_value().unwrap() as f64);
+                    solution.push(Gene::Integer(integer.generate_value().unwrap()));
                 }
                 SolutionDataTypes::Real(real) => {
-                    solution.push(real.generate_value().unwrap());
+                    solution.push(Gene::Real(real.generate_value().unwrap()));
+                }
+                SolutionDataTypes::Permutation(permutation) => {
+                    solution.extend(permutation.generate_value());
+                }
+                SolutionDataTypes::Subset(subset) => {
+                    solution.extend(subset.generate_value());
+                }
+                SolutionDataTypes::BitString(bit_string) => {
+                    solution.extend(bit_string.generate_value());
+                }
+                SolutionDataTypes::Categorical(categorical) => {
+                    solution.push(Gene::Category(categorical.generate_value()));
+                }
+                SolutionDataTypes::ExpressionTree(tree) => {
+                    solution.extend(tree.generate_value());
                 }
             }
         }
@@ -121,10 +511,25 @@ impl Problem {
     }
 }
 
-#[derive(Debug, Clone)]
+fn duplicate_name(metadata: &[Metadata]) -> Option<&str> {
+    metadata
+        .iter()
+        .enumerate()
+        .find(|(i, a)| metadata[..*i].iter().any(|b| a.name == b.name))
+        .map(|(_, metadata)| metadata.name.as_str())
+}
+
+/// Bounds on the number of template repetitions of a variable-length genome
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariableLength {
+    pub min_length: usize,
+    pub max_length: usize,
+}
+
+#[derive(Clone)]
 pub struct Solution<'a> { 
     pub problem: &'a Problem,
-    pub solution: Vec<f64>, // Derived from Problem.solution_data_types
+    pub solution: Vec<Gene>, // Derived from Problem.solution_data_types
     pub objective_fitness_values: Vec<f64>,
     pub constraint_values: Vec<f64>,
     pub evaluated: bool, // default false
@@ -158,10 +563,146 @@ impl<'a> Solution<'a> {
         &self.problem
     }
 
-    pub fn solution(&self) -> &Vec<f64> {
+    pub fn solution(&self) -> &Vec<Gene> {
         &self.solution
     }
 
+    /// Numeric values of the genes, as passed to `objective_function`
+    pub fn values(&self) -> Vec<f64> {
+        self.solution.iter().map(Gene::as_f64).collect()
+    }
+
+    /// Genes of the variable at `index`
+    pub fn variable(&self, index: usize) -> &[Gene] {
+        &self.solution[self.problem.gene_range(index)]
+    }
+
+    /// Value of the Real variable at `index`, None for other variables
+    pub fn real(&self, index: usize) -> Option<f64> {
+        match self.problem.variable_type(index) {
+            SolutionDataTypes::Real(_) => Some(self.variable(index)[0].as_f64()),
+            _ => None,
+        }
+    }
+
+    /// Exact value of the Integer variable at `index`, None for other variables
+    pub fn integer(&self, index: usize) -> Option<i64> {
+        match self.problem.variable_type(index) {
+            SolutionDataTypes::Integer(_) => Some(self.variable(index)[0].as_i64()),
+            _ => None,
+        }
+    }
+
+    /// Value of the BitBinary variable at `index`, None for other variables
+    pub fn bit(&self, index: usize) -> Option<bool> {
+        match self.problem.variable_type(index) {
+            SolutionDataTypes::BitBinary(_) => Some(self.variable(index)[0].as_bool()),
+            _ => None,
+        }
+    }
+
+    /// Elements of the Permutation or Subset variable at `index`, None for other variables
+    pub fn elements(&self, index: usize) -> Option<Vec<usize>> {
+        match self.problem.variable_type(index) {
+            SolutionDataTypes::Permutation(_) | SolutionDataTypes::Subset(_) => {
+                Some(self.variable(index).iter().map(Gene::as_usize).collect())
+            }
+            _ => None,
+        }
+    }
+
+    /// Bits of the BitString variable at `index`, None for other variables
+    pub fn bits(&self, index: usize) -> Option<Vec<bool>> {
+        match self.problem.variable_type(index) {
+            SolutionDataTypes::BitString(bit_string) => Some(bit_string.to_bits(self.variable(index))),
+            _ => None,
+        }
+    }
+
+    /// Label chosen by the Categorical variable at `index`, None for other variables
+    pub fn category(&self, index: usize) -> Option<&str> {
+        match self.problem.variable_type(index) {
+            SolutionDataTypes::Categorical(categorical) => Some(categorical.label(self.variable(index)[0])),
+            _ => None,
+        }
+    }
+
+    /// Readable form of the ExpressionTree variable at `index`, None for other variables
+    pub fn expression(&self, index: usize) -> Option<String> {
+        match self.problem.variable_type(index) {
+            SolutionDataTypes::ExpressionTree(_) => Some(ExpressionTree::format(self.variable(index))),
+            _ => None,
+        }
+    }
+
+    /// Index of the variable named `name` in this genome, see `Problem::variable_name`
+    pub fn variable_index(&self, name: &str) -> Option<usize> {
+        match self.problem.variable_length {
+            Some(_) => (0..self.solution.len()).find(|&index| self.problem.variable_name(index) == name),
+            None => self.problem.variable_index(name),
+        }
+    }
+
+    /// Gene of the single-gene variable named `name`, e.g. `solution.get("thickness")`; None for unknown
+    /// names and for variables spanning several genes, which `variable_named` returns
+    pub fn get(&self, name: &str) -> Option<Gene> {
+        match self.variable_named(name)? {
+            [gene] => Some(*gene),
+            _ => None,
+        }
+    }
+
+    /// Genes of the variable named `name`
+    pub fn variable_named(&self, name: &str) -> Option<&[Gene]> {
+        self.variable_index(name).map(|index| self.variable(index))
+    }
+
+    /// Value of the objective named `name`, None until the solution is evaluated
+    pub fn objective(&self, name: &str) -> Option<f64> {
+        self.problem.objective_index(name).and_then(|index| self.objective_fitness_values.get(index).copied())
+    }
+
+    /// Readable value of the variable at `index`: labels for categories, expressions for trees, 0/1 strings
+    /// for bit strings and element lists for permutations and subsets
+    pub fn format_variable(&self, index: usize) -> String {
+        let genes = self.variable(index);
+        match self.problem.variable_type(index) {
+            SolutionDataTypes::Categorical(categorical) => categorical.label(genes[0]).to_string(),
+            SolutionDataTypes::ExpressionTree(_) => ExpressionTree::format(genes),
+            SolutionDataTypes::BitString(bit_string) => {
+                bit_string.to_bits(genes).iter().map(|&bit| if bit { '1' } else { '0' }).collect()
+            }
+            SolutionDataTypes::Permutation(_) | SolutionDataTypes::Subset(_) => format!("{:?}", self.elements(index).unwrap()),
+            SolutionDataTypes::Real(_) | SolutionDataTypes::Integer(_) => self.native_value(index).unwrap().to_string(),
+            SolutionDataTypes::BitBinary(_) => genes[0].to_string(),
+        }
+    }
+
+    /// Column names for exporting the solution: variable names followed by objective names
+    pub fn header(&self) -> Vec<String> {
+        let variables = (0..self.problem.variables_in(&self.solution).count()).map(|index| self.problem.variable_name(index));
+        let objectives = (0..self.problem.number_of_objectives).map(|index| self.problem.objective_name(index));
+        variables.chain(objectives).collect()
+    }
+
+    /// Values for exporting the solution in the order of `header`; objectives are empty until evaluated
+    pub fn record(&self) -> Vec<String> {
+        let variables = (0..self.problem.variables_in(&self.solution).count()).map(|index| self.format_variable(index));
+        let objectives = (0..self.problem.number_of_objectives)
+            .map(|index| self.objective_fitness_values.get(index).map(f64::to_string).unwrap_or_default());
+        variables.chain(objectives).collect()
+    }
+
+    /// Value of the Real or Integer variable at `index` in its storage type, None for other variables
+    pub fn native_value(&self, index: usize) -> Option<NativeValue> {
+        let gene = self.variable(index)[0];
+        match self.problem.variable_type(index) {
+            SolutionDataTypes::Real(real) => Some(real.native(gene.as_f64())),
+            SolutionDataTypes::Integer(integer) => Some(integer.native(gene.as_i64())),
+            _ => None,
+        }
+    }
+
     pub fn objective_fitness_values(&self) -> &Vec<f64> {
         &self.objective_fitness_values
     }
@@ -183,28 +724,39 @@ impl<'a> Solution<'a> {
     }
 
     pub fn evaluate_constraints(&mut self) -> Vec<f64> {
+        self.try_evaluate_constraints().unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// One value per objective, 1 when its constraint holds or it has none and 0 when it is violated;
+    /// empty when the problem has no constraints. Unpaired constraints and invalid operands are errors.
+    pub fn try_evaluate_constraints(&mut self) -> Result<Vec<f64>> {
         let mut constraint_values: Vec<f64> = Vec::new();
-        let objective_constraint = self.problem.objective_constraint();
-        let objective_constraint_operands = self.problem.objective_constraint_operands();
-        // println!("OBJECTIVE CONSTRAINT: {:?}", objective_constraint);
-        // println!("OBJECTIVE CONSTRAINT OPERANDS: {:?}", objective_constraint_operands);
-        if objective_constraint.is_some() && objective_constraint_operands.is_some() {
-            let objective_constraint: &Vec<Option<f64>> = &objective_constraint.as_ref().unwrap();
-            let objective_constraint_operands: &Vec<Option<String>> = &objective_constraint_operands.as_ref().unwrap();
-            for _i in 0..objective_constraint.len() {
-                let operand: &Option<String> = &objective_constraint_operands[_i];
-                let constraint: &Option<f64> = &objective_constraint[_i];
-                let obj_value: &f64 = &self.objective_fitness_values[_i];
-                // println!("OBJ VALUE: {:?}", obj_value);
-                let comparison_fn= ComparisonFunctions::new();// operand.as_ref().unwrap();
-                // println!("OPERAND: {:?}", operand);
-                let comparison_fn = comparison_fn.functions.get(operand.as_ref().unwrap()).unwrap();
-                let constraint_value = comparison_fn.compare(*obj_value, constraint.unwrap());
-                // println!("CONSTRAINT VALUE: {:?}", constraint_value);
-                constraint_values.push(constraint_value as i8 as f64);
+        let objective_constraint_operands = self.problem.try_objective_constraint_operands()?;
+        if let (Some(objective_constraint), Some(objective_constraint_operands)) = (self.problem.objective_constraint(), objective_constraint_operands) {
+            if self.objective_fitness_values.len() != objective_constraint.len() {
+                return Err(Error::LengthMismatch {
+                    what: "objective_fitness_values length does not match objective_constraint length",
+                    expected: objective_constraint.len(),
+                    found: self.objective_fitness_values.len(),
+                });
+            }
+            let comparison_functions = ComparisonFunctions::new();
+            for objective in 0..objective_constraint.len() {
+                let satisfied = match (&objective_constraint_operands[objective], objective_constraint[objective]) {
+                    (Some(operand), Some(constraint)) => {
+                        let comparison_fn = comparison_functions
+                            .functions
+                            .get(operand)
+                            .ok_or_else(|| Error::InvalidOperator(operand.clone()))?;
+                        comparison_fn.compare(self.objective_fitness_values[objective], constraint)
+                    }
+                    (None, None) => true,
+                    _ => return Err(Error::UnpairedConstraint { objective }),
+                };
+                constraint_values.push(satisfied as i8 as f64);
             }
         }
-        constraint_values
+        Ok(constraint_values)
     }
 
     pub fn calculate_constraint_violation(&mut self) -> usize {
@@ -218,17 +770,63 @@ impl<'a> Solution<'a> {
         constraint_violation
     }
 
+    /// Feasible when no constraint is violated and every subset variable holds its size in distinct elements
     pub fn is_feasible(&mut self) -> bool {
         let constraint_violation = self.constraint_violation();
-        if *constraint_violation == 0 {
-            true
+        *constraint_violation == 0
+            && self.problem.variables_in(&self.solution).all(|(genes, solution_type)| match solution_type {
+                SolutionDataTypes::Subset(subset) => subset.is_valid(&self.solution[genes]),
+                _ => true,
+            })
+    }
+
+    /// Whether both solutions activate the same variables and agree on all of their genes; inactive genes are ignored
+    pub fn is_duplicate(&self, other: &Solution) -> bool {
+        self.solution.len() == other.solution.len()
+            && (0..self.problem.variables_in(&self.solution).count())
+            .all(|index| self.problem.is_active(index, &self.solution) == self.problem.is_active(index, &other.solution))
+            && self.problem.active_genes(&self.solution) == self.problem.active_genes(&other.solution)
+    }
+
+    pub fn evaluate(&mut self) {
+        let objective_fitness_values = self.objective_values();
+        self.set_objective_values(objective_fitness_values);
+    }
+
+    /// Like `evaluate`, but reports an objective function returning the wrong number of values or NaN as an
+    /// error, leaving the solution unevaluated
+    pub fn try_evaluate(&mut self) -> Result<()> {
+        let objective_fitness_values = self.objective_values();
+        if objective_fitness_values.len() != self.problem.number_of_objectives {
+            return Err(Error::LengthMismatch {
+                what: "objective function result length does not match number_of_objectives",
+                expected: self.problem.number_of_objectives,
+                found: objective_fitness_values.len(),
+            });
+        }
+        if let Some(objective) = objective_fitness_values.iter().position(|value| value.is_nan()) {
+            return Err(Error::NanObjective { objective: self.problem.objective_name(objective) });
+        }
+        self.set_objective_values(objective_fitness_values);
+        Ok(())
+    }
+
+    // Objective values of the active genes, from the typed objective function when there is one
+    fn objective_values(&self) -> Vec<f64> {
+        let active_genes;
+        let genes: &[Gene] = if self.problem.conditions.is_empty() {
+            &self.solution
         } else {
-            false
+            active_genes = self.problem.active_genes(&self.solution);
+            &active_genes
+        };
+        match self.problem.objective_function {
+            ObjectiveFunction::Numeric(objective_function) => objective_function(&genes.iter().map(Gene::as_f64).collect()),
+            ObjectiveFunction::Typed(typed_objective_function) => typed_objective_function(genes),
         }
     }
 
-    pub fn evaluate(&mut self) {
-        let objective_fitness_values = (self.problem.objective_function)(&self.solution);
+    fn set_objective_values(&mut self, objective_fitness_values: Vec<f64>) {
         self.evaluated = true;
         self.objective_fitness_values = objective_fitness_values;
         self.feasible = self.is_feasible();
@@ -239,6 +837,37 @@ impl<'a> Solution<'a> {
 
 }
 
+// Printed with the names of the variables and objectives in place of the bare genome
+impl fmt::Debug for Solution<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        struct Named(Vec<(String, String)>);
+        impl fmt::Debug for Named {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                let mut map = f.debug_map();
+                for (name, value) in &self.0 {
+                    map.key(&format_args!("{}", name)).value(&format_args!("{}", value));
+                }
+                map.finish()
+            }
+        }
+        let variable_count = self.problem.variables_in(&self.solution).count();
+        let variables = (0..variable_count).map(|index| (self.problem.variable_name(index), self.format_variable(index)));
+        let objectives = self
+            .objective_fitness_values
+            .iter()
+            .enumerate()
+            .map(|(index, value)| (self.problem.objective_name(index), value.to_string()));
+        f.debug_struct("Solution")
+            .field("variables", &Named(variables.collect()))
+            .field("objectives", &Named(objectives.collect()))
+            .field("constraint_values", &self.constraint_values)
+            .field("evaluated", &self.evaluated)
+            .field("constraint_violation", &self.constraint_violation)
+            .field("feasible", &self.feasible)
+            .finish()
+    }
+}
+
 
 
 // Write Unit Tests
@@ -246,8 +875,9 @@ impl<'a> Solution<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::gatypes::{BitBinary, Integer, Real};
+    use crate::gatypes::{BitBinary, BitString, Categorical, Integer, IntegerDataType, Permutation, Real, RealDataType, Subset};
     use crate::benchmark_objective_functions::parabloid_5_loc;
+    use crate::conditions::{Activation, Condition};
 
     #[test]
     fn test_bit_binary() {
@@ -339,7 +969,7 @@ mod tests {
             parabloid_5_loc,
         );
 
-        let solution: Vec<f64> = problem.generate_solution();
+        let solution: Vec<Gene> = problem.generate_solution();
         println!("{:?}", solution);
         assert_eq!(solution.len(), 5);
 
@@ -450,4 +1080,295 @@ mod tests {
         assert_eq!(problem.objective_constraint().as_ref().unwrap().len(), 1);
         assert_eq!(problem.objective_constraint_operands().as_ref().unwrap().len(), 1);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_gene_layout_with_permutation() {
+        let solution_data_types = vec![
+            SolutionDataTypes::Real(Real::new(Some(0.0), Some(1.0))),
+            SolutionDataTypes::Permutation(Permutation::new(4)),
+            SolutionDataTypes::BitBinary(BitBinary::new()),
+        ];
+        let problem = Problem::new(3, 1, None, None, None, solution_data_types, |x| vec![x.iter().sum()]);
+        assert_eq!(problem.genome_length(), 6);
+        assert_eq!(problem.gene_range(1), 1..5);
+        assert_eq!(problem.gene_range(2), 5..6);
+        let ranges: Vec<Range<usize>> = problem.variables().map(|(genes, _)| genes).collect();
+        assert_eq!(ranges, vec![0..1, 1..5, 5..6]);
+        let solution = Solution::new(&problem);
+        assert_eq!(solution.solution.len(), 6);
+        let mut permutation = solution.variable(1).to_vec();
+        permutation.sort_by_key(Gene::as_usize);
+        assert_eq!(permutation, (0..4).map(Gene::Element).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_subset_feasibility() {
+        let solution_data_types = vec![SolutionDataTypes::Subset(Subset::new(5, 2))];
+        let problem = Problem::new(1, 1, None, None, None, solution_data_types, |x| vec![x.iter().sum()]);
+        let mut solution = Solution::new(&problem);
+        solution.evaluate();
+        assert!(solution.feasible);
+        solution.solution = vec![Gene::Element(3), Gene::Element(3)];
+        solution.evaluate();
+        assert!(!solution.feasible);
+    }
+
+    #[test]
+    fn test_native_values() {
+        let solution_data_types = vec![
+            SolutionDataTypes::Real(Real::new(None, None).with_data_type(RealDataType::F32)),
+            SolutionDataTypes::Integer(Integer::new(None, None).with_data_type(IntegerDataType::U8)),
+            SolutionDataTypes::BitBinary(BitBinary::new()),
+        ];
+        let problem = Problem::new(3, 1, None, None, None, solution_data_types, |x| vec![x.iter().sum()]);
+        let mut solution = Solution::new(&problem);
+        solution.solution = vec![Gene::Real(0.25), Gene::Integer(42), Gene::Bit(true)];
+        assert_eq!(solution.native_value(0), Some(NativeValue::F32(0.25)));
+        assert_eq!(solution.native_value(1), Some(NativeValue::U8(42)));
+        assert_eq!(solution.native_value(2), None);
+        // Exported in the storage type, not as the f64 the gene holds
+        solution.solution[0] = Gene::Real(0.1f32 as f64);
+        assert_eq!(solution.record()[..3], ["0.1", "42", "1"]);
+        let generated = Solution::new(&problem);
+        assert_eq!(generated.solution[0].as_f64() as f32 as f64, generated.solution[0].as_f64());
+        assert!((0..=255).contains(&generated.solution[1].as_i64()));
+    }
+
+    #[test]
+    fn test_categorical_solution() {
+        let solution_data_types = vec![
+            SolutionDataTypes::Categorical(Categorical::new(vec!["cg", "gmres", "bicgstab"])),
+            SolutionDataTypes::Real(Real::new(Some(0.0), Some(1.0))),
+        ];
+        // Objective functions receive the index of the chosen category
+        let problem = Problem::new(2, 1, None, None, None, solution_data_types, |x| vec![x[0] * 10.0 + x[1]]);
+        let mut solution = Solution::new(&problem);
+        assert!(solution.solution[0].as_usize() < 3);
+        solution.solution = vec![Gene::Category(1), Gene::Real(0.5)];
+        solution.evaluate();
+        assert_eq!(solution.category(0), Some("gmres"));
+        assert_eq!(solution.category(1), None);
+        assert_eq!(solution.objective_fitness_values, vec![10.5]);
+    }
+
+    // Number of layers (1 to 3) followed by the width of each layer
+    fn setup_layered_problem() -> Problem {
+        let mut solution_data_types = vec![SolutionDataTypes::Integer(Integer::new(Some(1), Some(4)))];
+        solution_data_types.extend((0..3).map(|_| SolutionDataTypes::Real(Real::new(Some(1.0), Some(64.0)))));
+        // Total width of the layers the objective sees
+        Problem::new(4, 1, None, None, None, solution_data_types, |x| vec![x[1..].iter().sum()])
+            .with_condition(Condition::new(2, 0, Activation::AtLeast(2.0)))
+            .with_condition(Condition::new(3, 0, Activation::AtLeast(3.0)))
+    }
+
+    #[test]
+    fn test_conditional_variables() {
+        let problem = setup_layered_problem();
+        let genome = vec![Gene::Integer(2), Gene::Real(8.0), Gene::Real(16.0), Gene::Real(32.0)];
+        assert!(problem.is_active(1, &genome) && problem.is_active(2, &genome));
+        assert!(!problem.is_active(3, &genome));
+        assert_eq!(problem.active_genes(&genome), vec![Gene::Integer(2), Gene::Real(8.0), Gene::Real(16.0)]);
+        let mut solution = Solution::new(&problem);
+        solution.solution = genome;
+        solution.evaluate();
+        // The inactive third layer is invisible to the objective
+        assert_eq!(solution.objective_fitness_values, vec![24.0]);
+        let mut other = solution.clone();
+        other.solution[3] = Gene::Real(4.0);
+        assert!(solution.is_duplicate(&other));
+        other.solution[2] = Gene::Real(4.0);
+        assert!(!solution.is_duplicate(&other));
+    }
+
+    #[test]
+    fn test_nested_conditions() {
+        let problem = setup_layered_problem().with_condition(Condition::new(1, 3, Activation::Between(0.0, 10.0)));
+        // Variable 1 depends on variable 3, which is inactive with a single layer
+        assert!(!problem.is_active(1, &[1.0, 8.0, 16.0, 5.0].map(Gene::Real)));
+        assert!(problem.is_active(1, &[3.0, 8.0, 16.0, 5.0].map(Gene::Real)));
+        assert!(!problem.is_active(1, &[3.0, 8.0, 16.0, 50.0].map(Gene::Real)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Conditions must not form a cycle")]
+    fn test_cyclic_conditions() {
+        setup_layered_problem().with_condition(Condition::new(0, 3, Activation::Equals(1.0)));
+    }
+
+    #[test]
+    #[should_panic(expected = "A variable cannot control its own activation")]
+    fn test_self_controlled_condition_literal() {
+        setup_layered_problem().with_condition(Condition { variable: 1, controller: 1, activation: Activation::Equals(1.0) });
+    }
+
+    #[test]
+    fn test_conditions_require_fixed_length() {
+        assert!(matches!(setup_layered_problem().try_with_variable_length(1, 3), Err(Error::InvalidConfiguration(_))));
+        let variable_length = Problem { conditions: Vec::new(), ..setup_layered_problem() }.with_variable_length(1, 3);
+        let condition = Condition::new(1, 0, Activation::AtLeast(1.0));
+        assert!(matches!(variable_length.try_with_condition(condition), Err(Error::InvalidConfiguration(_))));
+    }
+
+    #[test]
+    fn test_variable_length_generation() {
+        let problem = Problem::new(2, 1, None, None, None, vec![
+            SolutionDataTypes::Real(Real::new(Some(0.0), Some(1.0))),
+            SolutionDataTypes::Integer(Integer::new(Some(0), Some(5))),
+        ], |x| vec![x.len() as f64]).with_variable_length(2, 4);
+        for _ in 0..20 {
+            let solution = Solution::new(&problem);
+            assert!(solution.solution.len().is_multiple_of(2));
+            assert!((2..=4).contains(&problem.repetitions(&solution.solution)));
+            assert!(matches!(problem.variable_type(3), SolutionDataTypes::Integer(_)));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Variable length requires 0 < min_length <= max_length")]
+    fn test_invalid_variable_length() {
+        Problem::new(1, 1, None, None, None, vec![SolutionDataTypes::Real(Real::new(Some(0.0), Some(1.0)))], |x| vec![x[0]])
+            .with_variable_length(3, 2);
+    }
+
+    #[test]
+    fn test_typed_genes() {
+        let solution_data_types = vec![
+            SolutionDataTypes::Integer(Integer::new(None, None)),
+            SolutionDataTypes::BitBinary(BitBinary::new()),
+            SolutionDataTypes::Permutation(Permutation::new(3)),
+            SolutionDataTypes::BitString(BitString::new(40)),
+            SolutionDataTypes::Real(Real::new(Some(0.0), Some(1.0))),
+        ];
+        let problem = Problem::new_typed(5, 1, None, None, None, solution_data_types, |genes| vec![(genes[0].as_i64() - (1 << 60)) as f64]);
+        let mut solution = Solution::new(&problem);
+        // Integers beyond 2^53 are held and handed to the typed objective exactly
+        let wide = (1 << 60) + 1;
+        solution.solution[0] = Gene::Integer(wide);
+        solution.solution[1] = Gene::Bit(true);
+        solution.evaluate();
+        assert_eq!(solution.objective_fitness_values, vec![1.0]);
+        assert_eq!(solution.integer(0), Some(wide));
+        assert_eq!(solution.integer(4), None);
+        assert_eq!(solution.bit(1), Some(true));
+        let mut elements = solution.elements(2).unwrap();
+        elements.sort();
+        assert_eq!(elements, vec![0, 1, 2]);
+        assert_eq!(solution.bits(3).unwrap().len(), 40);
+        assert!(solution.real(4).is_some_and(|value| (0.0..=1.0).contains(&value)));
+        assert_eq!(solution.values()[1], 1.0);
+    }
+
+    #[test]
+    fn test_variable_metadata() {
+        let solution_data_types = vec![
+            SolutionDataTypes::Real(Real::new(Some(1.0), Some(5.0))),
+            SolutionDataTypes::Categorical(Categorical::new(vec!["aluminium", "titanium"])),
+            SolutionDataTypes::Integer(Integer::new(Some(2), Some(10))),
+        ];
+        let problem = Problem::new(3, 2, None, None, None, solution_data_types, |x| vec![x[0] * x[2], x[0]])
+            .with_variable_metadata(vec![
+                Metadata::new("thickness").with_unit("mm"),
+                "material".into(),
+                Metadata::new("rib_spacing").with_description("Number of bays between ribs"),
+            ])
+            .with_objective_metadata(vec!["mass".into(), "cost".into()]);
+        let mut solution = Solution::new(&problem);
+        solution.solution = vec![Gene::Real(2.5), Gene::Category(1), Gene::Integer(4)];
+        assert_eq!(solution.get("thickness"), Some(Gene::Real(2.5)));
+        assert_eq!(solution.get("rib_spacing").map(|gene| gene.as_i64()), Some(4));
+        assert_eq!(solution.get("span"), None);
+        assert_eq!(solution.objective("mass"), None);
+        solution.evaluate();
+        assert_eq!(solution.objective("mass"), Some(10.0));
+        assert_eq!(solution.header(), vec!["thickness", "material", "rib_spacing", "mass", "cost"]);
+        assert_eq!(solution.record(), vec!["2.5", "titanium", "4", "10", "2.5"]);
+        let printed = format!("{:?}", solution);
+        assert!(printed.contains("thickness: 2.5, material: titanium, rib_spacing: 4"));
+        assert!(printed.contains("mass: 10, cost: 2.5"));
+        assert_eq!(problem.variable_metadata[0].label(), "thickness [mm]");
+        // Unnamed problems fall back to positional names
+        let unnamed = Problem::new(1, 1, None, None, None, vec![SolutionDataTypes::Real(Real::new(None, None))], |x| vec![x[0]]);
+        assert_eq!((unnamed.variable_name(0), unnamed.objective_name(0)), ("x0".to_string(), "f0".to_string()));
+        // Repetitions of a variable-length template are numbered
+        let wind_farm = Problem::new(2, 1, None, None, None, vec![
+            SolutionDataTypes::Real(Real::new(Some(0.0), Some(1.0))),
+            SolutionDataTypes::Real(Real::new(Some(0.0), Some(1.0))),
+        ], |x| vec![x.len() as f64])
+            .with_variable_length(1, 4)
+            .with_variable_metadata(vec!["turbine_x".into(), "turbine_y".into()]);
+        let mut layout = Solution::new(&wind_farm);
+        layout.solution = [0.1, 0.2, 0.3, 0.4].map(Gene::Real).to_vec();
+        assert_eq!(layout.get("turbine_y[1]"), Some(Gene::Real(0.4)));
+        assert_eq!(layout.header()[..4], ["turbine_x[0]", "turbine_y[0]", "turbine_x[1]", "turbine_y[1]"]);
+    }
+
+    #[test]
+    fn test_fallible_construction() {
+        let real = || vec![SolutionDataTypes::Real(Real::new(Some(0.0), Some(1.0)))];
+        let error = |result: Result<Problem>| result.unwrap_err().to_string();
+        assert_eq!(
+            error(Problem::try_new(2, 1, None, None, None, real(), |x| vec![x[0]])),
+            "solution_length does not match solution_data_types length (expected 1, found 2)"
+        );
+        assert_eq!(
+            error(Problem::try_new(1, 2, None, None, Some(vec![-1]), real(), |x| vec![x[0]])),
+            "direction length does not match number_of_objectives (expected 2, found 1)"
+        );
+        let operands = |operand: &str| Some(vec![Some(operand.to_string())]);
+        assert_eq!(
+            error(Problem::try_new(1, 1, Some(vec![Some(1.0)]), operands("=<"), None, real(), |x| vec![x[0]])),
+            "Invalid operand: =<, expected one of <, >, <=, >=, ==, !="
+        );
+        assert!(matches!(
+            Problem::try_new(1, 1, Some(vec![Some(1.0)]), None, None, real(), |x| vec![x[0]]),
+            Err(Error::UnpairedConstraint { objective: 0 })
+        ));
+        assert!(Problem::try_new(1, 1, Some(vec![Some(1.0)]), operands("<="), None, real(), |x| vec![x[0]]).is_ok());
+        let problem = Problem::new(1, 1, None, None, None, real(), |x| vec![x[0]]);
+        assert!(matches!(problem.try_with_variable_length(2, 1), Err(Error::InvalidConfiguration(_))));
+        let problem = Problem::new(1, 1, None, None, None, real(), |x| vec![x[0]]);
+        assert!(matches!(problem.try_with_objective_metadata(vec![]), Err(Error::LengthMismatch { expected: 1, found: 0, .. })));
+        assert_eq!(Integer::try_new(Some(5), Some(1)).unwrap_err().to_string(), "Lower bound must be less than upper bound (lower 5, upper 1)");
+        assert!(matches!(Real::try_new(Some(1.0), Some(1.0)), Err(Error::EqualBounds(_))));
+        assert!(matches!(Real::try_new(Some(f64::NAN), None), Err(Error::NanBound)));
+    }
+
+    #[test]
+    fn test_fallible_evaluation() {
+        let problem = Problem::new(
+            1,
+            2,
+            Some(vec![Some(0.5), None]),
+            Some(vec![Some("<".to_string()), None]),
+            None,
+            vec![SolutionDataTypes::Real(Real::new(Some(-1.0), Some(1.0)))],
+            |x| vec![x[0], x[0].sqrt()],
+        )
+        .with_objective_metadata(vec!["drag".into(), "noise".into()]);
+        let mut solution = Solution::new(&problem);
+        solution.solution = vec![Gene::Real(-0.25)];
+        assert_eq!(solution.try_evaluate().unwrap_err().to_string(), "Objective noise evaluated to NaN");
+        assert!(!solution.evaluated);
+        solution.solution = vec![Gene::Real(0.25)];
+        solution.try_evaluate().unwrap();
+        assert_eq!(solution.try_evaluate_constraints().unwrap(), vec![1.0, 1.0]);
+        solution.objective_fitness_values.clear();
+        assert!(matches!(solution.try_evaluate_constraints(), Err(Error::LengthMismatch { expected: 2, found: 0, .. })));
+        let seed_error: Error = crate::seeds::SeedError::UnknownColumn("span".to_string()).into();
+        assert_eq!(seed_error.to_string(), "column \"span\" is neither a variable nor an objective");
+    }
+
+    #[test]
+    #[should_panic(expected = "Variable names must be unique")]
+    fn test_duplicate_variable_names() {
+        let solution_data_types = vec![SolutionDataTypes::BitBinary(BitBinary::new()), SolutionDataTypes::BitBinary(BitBinary::new())];
+        Problem::new(2, 1, None, None, None, solution_data_types, |x| vec![x[0]]).with_variable_metadata(vec!["flag".into(), "flag".into()]);
+    }
+
+    #[test]
+    #[should_panic(expected = "objective_metadata length does not match number_of_objectives")]
+    fn test_mismatched_objective_metadata() {
+        let solution_data_types = vec![SolutionDataTypes::BitBinary(BitBinary::new())];
+        Problem::new(1, 1, None, None, None, solution_data_types, |x| vec![x[0]]).with_objective_metadata(vec!["a".into(), "b".into()]);
+    }
+}
@@ -1,87 +1,536 @@
 use crate::core::{Problem, Solution};
+use crate::error::{Error, Result};
+use crate::math_utils::{magnitude, multiply, normalize, subtract};
 
 
 
-#[derive(Debug)]
+/// Dominance relation selected by name, e.g. on an algorithm's configuration.
+/// Use `to_dominance` to obtain the comparator it describes.
+#[derive(Debug, Clone)]
 pub enum DominanceEnum  {
     ParetoDominance,
-    EpsilonDominance, 
-    AttributeDominance
+    EpsilonDominance(Vec<f64>), // epsilons per objective
+    AttributeDominance(usize), // index of the objective to compare
+    WeightedSum(Vec<f64>),
+    Tchebycheff { weights: Vec<f64>, ideal_point: Option<Vec<f64>> },
+    AchievementScalarizing { weights: Vec<f64>, reference_point: Vec<f64>, augmentation: Option<f64> },
+    PenaltyBoundaryIntersection { weights: Vec<f64>, ideal_point: Option<Vec<f64>>, penalty: Option<f64> },
+}
+
+impl DominanceEnum {
+    /// Builds the comparator described by this value
+    pub fn to_dominance(&self) -> Box<dyn Dominance> {
+        self.try_to_dominance().unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Checks the parameters that depend on the problem: the objective index of AttributeDominance and
+    /// the number of weights of the scalarizing relations
+    pub fn validate(&self, problem: &Problem) -> Result<()> {
+        match self {
+            DominanceEnum::AttributeDominance(objective_index) => AttributeDominance::new(*objective_index).validate(problem),
+            DominanceEnum::WeightedSum(weights)
+            | DominanceEnum::Tchebycheff { weights, .. }
+            | DominanceEnum::AchievementScalarizing { weights, .. }
+            | DominanceEnum::PenaltyBoundaryIntersection { weights, .. } => check_weights_length(weights, problem),
+            _ => Ok(()),
+        }
+    }
+
+    /// Like `to_dominance`, but reports invalid parameters as an error
+    pub fn try_to_dominance(&self) -> Result<Box<dyn Dominance>> {
+        Ok(match self {
+            DominanceEnum::ParetoDominance => Box::new(ParetoDominance),
+            DominanceEnum::EpsilonDominance(epsilons) => Box::new(EpsilonDominance::try_new(epsilons.clone())?),
+            DominanceEnum::AttributeDominance(objective_index) => Box::new(AttributeDominance::new(*objective_index)),
+            DominanceEnum::WeightedSum(weights) => Box::new(WeightedSumDominance::try_new(weights.clone())?),
+            DominanceEnum::Tchebycheff { weights, ideal_point } => {
+                Box::new(TchebycheffDominance::try_new(weights.clone(), ideal_point.clone())?)
+            }
+            DominanceEnum::AchievementScalarizing { weights, reference_point, augmentation } => {
+                Box::new(AchievementScalarizingDominance::try_new(weights.clone(), reference_point.clone(), *augmentation)?)
+            }
+            DominanceEnum::PenaltyBoundaryIntersection { weights, ideal_point, penalty } => {
+                Box::new(PenaltyBoundaryIntersectionDominance::try_new(weights.clone(), ideal_point.clone(), *penalty)?)
+            }
+        })
+    }
+}
+
+/// Outcome of comparing solution_1 against solution_2
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DominanceResult {
+    Dominates,
+    DominatedBy,
+    NonDominated,
+    Equal,
+}
+
+impl DominanceResult {
+    /// The same comparison seen from solution_2
+    pub fn reverse(self) -> Self {
+        match self {
+            DominanceResult::Dominates => DominanceResult::DominatedBy,
+            DominanceResult::DominatedBy => DominanceResult::Dominates,
+            other => other,
+        }
+    }
+}
+
+// -1 when solution_1 dominates, 1 when solution_2 dominates, 0 otherwise
+impl From<DominanceResult> for i32 {
+    fn from(result: DominanceResult) -> i32 {
+        match result {
+            DominanceResult::Dominates => -1,
+            DominanceResult::DominatedBy => 1,
+            DominanceResult::NonDominated | DominanceResult::Equal => 0,
+        }
+    }
+}
+
+/// Embedding of a dominance relation into plain Pareto dominance: solution a dominates solution b exactly
+/// when a.tier < b.tier, or the tiers are equal and a.objectives Pareto-dominate b.objectives (minimization)
+#[derive(Debug, Clone, PartialEq)]
+pub struct SortingKey {
+    pub tier: usize,
+    pub objectives: Vec<f64>,
+}
+
+pub trait Dominance: std::fmt::Debug + Send + Sync {
+    fn compare(&self, solution_1: &Solution, solution_2: &Solution) -> DominanceResult;
+
+    /// Integer form of `compare`: -1 if solution_1 dominates, 1 if solution_2 dominates, 0 otherwise
+    fn compare_solutions(&self, solution_1: &Solution, solution_2: &Solution) -> i32 {
+        self.compare(solution_1, solution_2).into()
+    }
+
+    /// Key used by the fast non-dominated sorting backends. Relations without a Pareto embedding
+    /// return `None` and are sorted by pairwise comparison instead.
+    fn sorting_key(&self, _solution: &Solution) -> Option<SortingKey> {
+        None
+    }
+}
+
+impl Dominance for Box<dyn Dominance> {
+    fn compare(&self, solution_1: &Solution, solution_2: &Solution) -> DominanceResult {
+        self.as_ref().compare(solution_1, solution_2)
+    }
+
+    fn sorting_key(&self, solution: &Solution) -> Option<SortingKey> {
+        self.as_ref().sorting_key(solution)
+    }
+}
+
+fn has_constraints(problem: &Problem) -> bool {
+    problem
+        .objective_constraint
+        .as_ref()
+        .is_some_and(|constraints| !constraints.is_empty())
+}
+
+// Tier matching compare_constraint_violation: fewer violations sorts first when the problem is constrained
+fn constraint_tier(solution: &Solution) -> usize {
+    if has_constraints(solution.problem) {
+        solution.constraint_violation
+    } else {
+        0
+    }
+}
+
+// Feasibility decides first: fewer violated constraints wins.
+fn compare_constraint_violation(solution_1: &Solution, solution_2: &Solution) -> Option<DominanceResult> {
+    if has_constraints(solution_1.problem) && solution_1.constraint_violation != solution_2.constraint_violation {
+        return Some(if solution_1.constraint_violation < solution_2.constraint_violation {
+            DominanceResult::Dominates
+        } else {
+            DominanceResult::DominatedBy
+        });
+    }
+    None
 }
 
-pub trait Dominance { 
-    fn compare_solutions(&self, solution_1: &Solution, solution_2: &Solution) -> i32;
+// Pareto comparison of two vectors in minimization form
+pub(crate) fn compare_pareto(values_1: &[f64], values_2: &[f64]) -> DominanceResult {
+    let mut is_solution_1_better = false;
+    let mut is_solution_2_better = false;
+    for (value_1, value_2) in values_1.iter().zip(values_2.iter()) {
+        if value_1 < value_2 {
+            is_solution_1_better = true;
+        } else if value_1 > value_2 {
+            is_solution_2_better = true;
+        }
+        if is_solution_1_better && is_solution_2_better {
+            return DominanceResult::NonDominated;
+        }
+    }
+    match (is_solution_1_better, is_solution_2_better) {
+        (true, false) => DominanceResult::Dominates,
+        (false, true) => DominanceResult::DominatedBy,
+        _ => DominanceResult::Equal,
+    }
 }
+
+fn compare_scalars(value_1: f64, value_2: f64) -> DominanceResult {
+    if value_1 < value_2 {
+        DominanceResult::Dominates
+    } else if value_1 > value_2 {
+        DominanceResult::DominatedBy
+    } else {
+        DominanceResult::Equal
+    }
+}
+
 #[derive(Debug)]
 pub struct ParetoDominance ;
 
-// impl<'a> ParetoDominance<'a> { 
-//     pub fn new(solution_1: &'a Solution, solution_2: &'a Solution) -> Self {
-//         ParetoDominance { solution_1, solution_2 }
-//         }     
-// }
-
 impl Dominance for ParetoDominance {
+    fn compare(&self, solution_1: &Solution, solution_2: &Solution) -> DominanceResult {
+        if let Some(result) = compare_constraint_violation(solution_1, solution_2) {
+            return result;
+        }
+        let problem: &Problem = solution_1.problem;
+        let number_of_objectives = *problem.number_of_objectives();
+        let objectives_1 = problem.oriented_objectives(&solution_1.objective_fitness_values[..number_of_objectives]);
+        let objectives_2 = problem.oriented_objectives(&solution_2.objective_fitness_values[..number_of_objectives]);
+        compare_pareto(&objectives_1, &objectives_2)
+    }
 
-    fn compare_solutions(&self, solution_1: &Solution, solution_2: &Solution) -> i32 {
-            
-        let problem: &Problem = &solution_1.problem;
-        let range_iter: std::ops::Range<usize> = (0..*problem.number_of_objectives()).into_iter(); 
-     
-        if let Some(ref constraints) = &problem.objective_constraint { 
-            if constraints.len() > 0  && (&solution_1.constraint_violation != &solution_2.constraint_violation) {
-                match (&solution_1.constraint_violation, &solution_2.constraint_violation) { 
-                    (0, _) => -1, 
-                    (_, 0) => 1, 
-                    (v1, v2) if v1 < v2 => -1, 
-                    (v1, v2) if v1 > v2 => 1, 
-                    _ => 0
-                }; 
-            }
+    fn sorting_key(&self, solution: &Solution) -> Option<SortingKey> {
+        let problem: &Problem = solution.problem;
+        let number_of_objectives = *problem.number_of_objectives();
+        Some(SortingKey {
+            tier: constraint_tier(solution),
+            objectives: problem.oriented_objectives(&solution.objective_fitness_values[..number_of_objectives]),
+        })
+    }
+}
+
+/// Epsilon-box dominance: objectives are mapped onto boxes of size `epsilons`, boxes are compared with
+/// Pareto dominance and solutions sharing a box are ranked by their distance to the box corner
+#[derive(Debug, Clone)]
+pub struct EpsilonDominance {
+    pub epsilons: Vec<f64>,
+}
+
+impl EpsilonDominance {
+    pub fn new(epsilons: Vec<f64>) -> Self {
+        Self::try_new(epsilons).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Like `new`, but reports missing or non-positive epsilons as an error
+    pub fn try_new(epsilons: Vec<f64>) -> Result<Self> {
+        if epsilons.is_empty() {
+            return Err(Error::InvalidConfiguration("EpsilonDominance needs at least one epsilon"));
         }
-        let mut is_solution_1_better = false;
-        let mut is_solution_2_better = false;
-        println!("SOLUTION 1 OBJECTIVE VALUES FFFFF: {:?}",solution_1.objective_fitness_values);  
-        println!("SOLUTION 2 OBJECTIVE VALUES FFFFF: {:?}",solution_2.objective_fitness_values);
-        for _i in range_iter { 
-            println!("INDEX {:?}", _i); 
-            let mut obj_1: f64 = solution_1.objective_fitness_values[_i]; 
-            let mut obj_2 = solution_2.objective_fitness_values[_i]; 
-            println!("OBJ 1 {:?}", obj_1);
-            println!("OBJ 2 {:?}", obj_2);
-
-            if let Some(direction) = &problem.direction() {
-                println!("DIRECTION {:?}", direction[_i]); 
-                if direction[_i] == -1 {
-                    obj_1 = -obj_1;
-                    obj_2 = -obj_2;
-                }
-
-                if obj_1 < obj_2 {
-                    println!("SOLUTION 1 IS BETTER");
-                    is_solution_1_better = true;
-                    if is_solution_2_better {
-                        return 0;
-                    }
-                } else if obj_1 > obj_2 {
-                    println!("SOLUTION 2 IS BETTER");
-                    is_solution_2_better = true;
-                    if is_solution_1_better {
-                        return 0;
-                    }     
-                }
-            }
+        if epsilons.iter().any(|&epsilon| epsilon <= 0.0 || epsilon.is_nan()) {
+            return Err(Error::InvalidConfiguration("Epsilons must be greater than zero"));
         }
+        Ok(Self { epsilons })
+    }
 
-        if is_solution_1_better == is_solution_2_better {
-            return 0;
-        } else if is_solution_1_better {
-            return -1;
-        } else {
-            return -1;
+    /// Box index of each (minimization form) objective
+    pub fn boxes(&self, objectives: &[f64]) -> Vec<f64> {
+        objectives
+            .iter()
+            .enumerate()
+            .map(|(i, value)| (value / self.epsilon(i)).floor())
+            .collect()
+    }
+
+    // The last epsilon is reused when fewer epsilons than objectives are given
+    fn epsilon(&self, index: usize) -> f64 {
+        self.epsilons[index.min(self.epsilons.len() - 1)]
+    }
+
+    fn corner_distance(&self, objectives: &[f64], boxes: &[f64]) -> f64 {
+        objectives
+            .iter()
+            .zip(boxes.iter())
+            .enumerate()
+            .map(|(i, (value, index))| (value - index * self.epsilon(i)).powi(2))
+            .sum::<f64>()
+            .sqrt()
+    }
+}
+
+impl Dominance for EpsilonDominance {
+    fn compare(&self, solution_1: &Solution, solution_2: &Solution) -> DominanceResult {
+        if let Some(result) = compare_constraint_violation(solution_1, solution_2) {
+            return result;
         }
-    
-    
+        let problem: &Problem = solution_1.problem;
+        let objectives_1 = problem.oriented_objectives(&solution_1.objective_fitness_values);
+        let objectives_2 = problem.oriented_objectives(&solution_2.objective_fitness_values);
+        let boxes_1 = self.boxes(&objectives_1);
+        let boxes_2 = self.boxes(&objectives_2);
+        match compare_pareto(&boxes_1, &boxes_2) {
+            DominanceResult::Equal => compare_scalars(
+                self.corner_distance(&objectives_1, &boxes_1),
+                self.corner_distance(&objectives_2, &boxes_2),
+            ),
+            result => result,
+        }
+    }
+}
+
+/// Compares a single objective, honoring its direction
+#[derive(Debug, Clone)]
+pub struct AttributeDominance {
+    pub objective_index: usize,
+}
+
+impl AttributeDominance {
+    /// Comparing solutions of a problem without the objective at `objective_index` panics; `try_new`
+    /// checks the index against the problem up front
+    pub fn new(objective_index: usize) -> Self {
+        Self { objective_index }
+    }
+
+    /// Like `new`, but reports an objective index beyond the problem's objectives as an error
+    pub fn try_new(objective_index: usize, problem: &Problem) -> Result<Self> {
+        let dominance = Self { objective_index };
+        dominance.validate(problem)?;
+        Ok(dominance)
+    }
+
+    /// Reports an objective index beyond the problem's objectives as an error
+    pub fn validate(&self, problem: &Problem) -> Result<()> {
+        if self.objective_index >= problem.number_of_objectives {
+            return Err(Error::InvalidConfiguration("AttributeDominance objective index exceeds number_of_objectives"));
+        }
+        Ok(())
+    }
+
+    fn objective(&self, solution: &Solution) -> f64 {
+        solution.problem.oriented_objectives(&solution.objective_fitness_values)[self.objective_index]
+    }
+}
+
+impl Dominance for AttributeDominance {
+    fn compare(&self, solution_1: &Solution, solution_2: &Solution) -> DominanceResult {
+        if let Some(result) = compare_constraint_violation(solution_1, solution_2) {
+            return result;
+        }
+        compare_scalars(self.objective(solution_1), self.objective(solution_2))
+    }
+
+    fn sorting_key(&self, solution: &Solution) -> Option<SortingKey> {
+        Some(SortingKey { tier: constraint_tier(solution), objectives: vec![self.objective(solution)] })
+    }
+}
+
+/// Reduces an objective vector (already in minimization form) to a single value where smaller is better.
+pub trait Scalarizing {
+    fn scalarize(&self, objectives: &[f64]) -> f64;
+}
+
+fn compare_scalarized(scalarizing: &dyn Scalarizing, solution_1: &Solution, solution_2: &Solution) -> DominanceResult {
+    if let Some(result) = compare_constraint_violation(solution_1, solution_2) {
+        return result;
+    }
+    let problem: &Problem = solution_1.problem;
+    compare_scalars(
+        scalarizing.scalarize(&problem.oriented_objectives(&solution_1.objective_fitness_values)),
+        scalarizing.scalarize(&problem.oriented_objectives(&solution_2.objective_fitness_values)),
+    )
+}
+
+fn scalarized_sorting_key(scalarizing: &dyn Scalarizing, solution: &Solution) -> Option<SortingKey> {
+    let objectives = solution.problem.oriented_objectives(&solution.objective_fitness_values);
+    Some(SortingKey { tier: constraint_tier(solution), objectives: vec![scalarizing.scalarize(&objectives)] })
+}
+
+fn check_weights_length(weights: &[f64], problem: &Problem) -> Result<()> {
+    if weights.len() != problem.number_of_objectives {
+        return Err(Error::LengthMismatch {
+            what: "Weights length does not match number_of_objectives",
+            expected: problem.number_of_objectives,
+            found: weights.len(),
+        });
+    }
+    Ok(())
+}
+
+fn check_reference_length(weights: &[f64], reference: &[f64]) -> Result<()> {
+    if weights.len() != reference.len() {
+        return Err(Error::LengthMismatch {
+            what: "Reference point length does not match weights length",
+            expected: weights.len(),
+            found: reference.len(),
+        });
+    }
+    Ok(())
+}
+
+/// Weighted sum: sum_i w_i * f_i
+#[derive(Debug, Clone)]
+pub struct WeightedSumDominance {
+    pub weights: Vec<f64>,
+}
+
+impl WeightedSumDominance {
+    pub fn new(weights: Vec<f64>) -> Self {
+        Self::try_new(weights).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Like `new`, but reports missing or negative weights as an error
+    pub fn try_new(weights: Vec<f64>) -> Result<Self> {
+        if weights.is_empty() {
+            return Err(Error::InvalidConfiguration("WeightedSumDominance needs at least one weight"));
+        }
+        if weights.iter().any(|&weight| weight < 0.0 || weight.is_nan()) {
+            return Err(Error::InvalidConfiguration("Weights must not be negative"));
+        }
+        Ok(Self { weights })
+    }
+}
+
+impl Scalarizing for WeightedSumDominance {
+    fn scalarize(&self, objectives: &[f64]) -> f64 {
+        objectives.iter().zip(self.weights.iter()).map(|(f, w)| w * f).sum()
+    }
+}
+
+impl Dominance for WeightedSumDominance {
+    fn compare(&self, solution_1: &Solution, solution_2: &Solution) -> DominanceResult {
+        compare_scalarized(self, solution_1, solution_2)
+    }
+
+    fn sorting_key(&self, solution: &Solution) -> Option<SortingKey> {
+        scalarized_sorting_key(self, solution)
+    }
+}
+
+/// Weighted Tchebycheff: max_i w_i * |f_i - z_i| where z is the ideal point (defaults to the origin)
+#[derive(Debug, Clone)]
+pub struct TchebycheffDominance {
+    pub weights: Vec<f64>,
+    pub ideal_point: Vec<f64>,
+}
+
+impl TchebycheffDominance {
+    pub fn new(weights: Vec<f64>, ideal_point: Option<Vec<f64>>) -> Self {
+        Self::try_new(weights, ideal_point).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Like `new`, but reports an ideal point of the wrong length as an error
+    pub fn try_new(weights: Vec<f64>, ideal_point: Option<Vec<f64>>) -> Result<Self> {
+        let ideal_point = ideal_point.unwrap_or_else(|| vec![0.0; weights.len()]);
+        check_reference_length(&weights, &ideal_point)?;
+        Ok(Self { weights, ideal_point })
+    }
+}
+
+impl Scalarizing for TchebycheffDominance {
+    fn scalarize(&self, objectives: &[f64]) -> f64 {
+        objectives
+            .iter()
+            .zip(self.weights.iter().zip(self.ideal_point.iter()))
+            .map(|(f, (w, z))| w * (f - z).abs())
+            .fold(f64::NEG_INFINITY, f64::max)
+    }
+}
+
+impl Dominance for TchebycheffDominance {
+    fn compare(&self, solution_1: &Solution, solution_2: &Solution) -> DominanceResult {
+        compare_scalarized(self, solution_1, solution_2)
+    }
+
+    fn sorting_key(&self, solution: &Solution) -> Option<SortingKey> {
+        scalarized_sorting_key(self, solution)
+    }
+}
+
+/// Augmented achievement scalarizing function:
+/// max_i (f_i - z_i) / w_i + rho * sum_i (f_i - z_i) / w_i where z is the reference (aspiration) point
+#[derive(Debug, Clone)]
+pub struct AchievementScalarizingDominance {
+    pub weights: Vec<f64>,
+    pub reference_point: Vec<f64>,
+    pub augmentation: f64,
+}
+
+impl AchievementScalarizingDominance {
+    pub fn new(weights: Vec<f64>, reference_point: Vec<f64>, augmentation: Option<f64>) -> Self {
+        Self::try_new(weights, reference_point, augmentation).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Like `new`, but reports a reference point of the wrong length as an error
+    pub fn try_new(weights: Vec<f64>, reference_point: Vec<f64>, augmentation: Option<f64>) -> Result<Self> {
+        check_reference_length(&weights, &reference_point)?;
+        Ok(Self {
+            weights,
+            reference_point,
+            augmentation: augmentation.unwrap_or(1e-6),
+        })
+    }
+}
+
+impl Scalarizing for AchievementScalarizingDominance {
+    fn scalarize(&self, objectives: &[f64]) -> f64 {
+        // Zero weights are replaced by a tiny value so the corresponding axis still counts
+        let terms: Vec<f64> = objectives
+            .iter()
+            .zip(self.weights.iter().zip(self.reference_point.iter()))
+            .map(|(f, (w, z))| (f - z) / w.max(1e-10))
+            .collect();
+        let max_term = terms.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        max_term + self.augmentation * terms.iter().sum::<f64>()
+    }
+}
+
+impl Dominance for AchievementScalarizingDominance {
+    fn compare(&self, solution_1: &Solution, solution_2: &Solution) -> DominanceResult {
+        compare_scalarized(self, solution_1, solution_2)
+    }
+
+    fn sorting_key(&self, solution: &Solution) -> Option<SortingKey> {
+        scalarized_sorting_key(self, solution)
+    }
+}
+
+/// Penalty-based boundary intersection: d1 + theta * d2, where d1 is the distance from the ideal point
+/// along the weight direction and d2 the perpendicular distance to that direction
+#[derive(Debug, Clone)]
+pub struct PenaltyBoundaryIntersectionDominance {
+    pub weights: Vec<f64>,
+    pub ideal_point: Vec<f64>,
+    pub penalty: f64,
+}
+
+impl PenaltyBoundaryIntersectionDominance {
+    pub fn new(weights: Vec<f64>, ideal_point: Option<Vec<f64>>, penalty: Option<f64>) -> Self {
+        Self::try_new(weights, ideal_point, penalty).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Like `new`, but reports an ideal point of the wrong length as an error
+    pub fn try_new(weights: Vec<f64>, ideal_point: Option<Vec<f64>>, penalty: Option<f64>) -> Result<Self> {
+        let ideal_point = ideal_point.unwrap_or_else(|| vec![0.0; weights.len()]);
+        check_reference_length(&weights, &ideal_point)?;
+        Ok(Self {
+            weights,
+            ideal_point,
+            penalty: penalty.unwrap_or(5.0),
+        })
+    }
+}
+
+impl Scalarizing for PenaltyBoundaryIntersectionDominance {
+    fn scalarize(&self, objectives: &[f64]) -> f64 {
+        let direction = normalize(&self.weights);
+        let shifted = subtract(objectives, &self.ideal_point);
+        let d1 = shifted.iter().zip(direction.iter()).map(|(f, w)| f * w).sum::<f64>();
+        let d2 = magnitude(&subtract(&shifted, &multiply(d1, &direction)));
+        d1 + self.penalty * d2
+    }
+}
+
+impl Dominance for PenaltyBoundaryIntersectionDominance {
+    fn compare(&self, solution_1: &Solution, solution_2: &Solution) -> DominanceResult {
+        compare_scalarized(self, solution_1, solution_2)
+    }
+
+    fn sorting_key(&self, solution: &Solution) -> Option<SortingKey> {
+        scalarized_sorting_key(self, solution)
     }
 }
 
@@ -90,9 +539,10 @@ impl Dominance for ParetoDominance {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::{Problem, Solution};
-    use crate::gatypes::{SolutionDataTypes, Real, BitBinary, Integer};
+    use crate::core::{ObjectiveFunction, Problem, Solution};
+    use crate::gatypes::{SolutionDataTypes, Real, BitBinary, Integer, Gene};
     use crate::benchmark_objective_functions::{parabloid_5, parabloid_hyper_5};
+    use crate::test_utils::{evaluated_solution, setup_problem};
     // Create Problem
    
     #[test]
@@ -110,10 +560,11 @@ mod tests {
                                     SolutionDataTypes::Real(Real::new(Some(0.), Some(100.))),
                                     SolutionDataTypes::Integer(Integer::new(Some(0), Some(100))),
                                     SolutionDataTypes::Real(Real::new(Some(0.), Some(100.))),],
-            objective_function: parabloid_5
+            objective_function: ObjectiveFunction::Numeric(parabloid_5),
+            ..Default::default()
         };
-        let solution_vector1:Vec<f64> = vec![1.0, 2.0, 3.0, 4.0, 5.0];
-        let solution_vector2:Vec<f64> = vec![12.0, 10.0, -3.0, 4.0, 5.0];
+        let solution_vector1: Vec<Gene> = [1.0, 2.0, 3.0, 4.0, 5.0].map(Gene::Real).to_vec();
+        let solution_vector2: Vec<Gene> = [12.0, 10.0, -3.0, 4.0, 5.0].map(Gene::Real).to_vec();
 
         let mut solution_1 = Solution {problem: &problem, solution: solution_vector1, 
                                                 objective_fitness_values: Vec::with_capacity(*problem.number_of_objectives()), 
@@ -150,10 +601,11 @@ mod tests {
                                         SolutionDataTypes::Real(Real::new(Some(0.), Some(100.))),
                                         SolutionDataTypes::Integer(Integer::new(Some(0), Some(100))),
                                         SolutionDataTypes::Real(Real::new(Some(0.), Some(100.))),],
-            objective_function: parabloid_5
+            objective_function: ObjectiveFunction::Numeric(parabloid_5),
+            ..Default::default()
         };
-        let solution_vector1:Vec<f64> = vec![1.0, 2.0, -3.0, 4.0, 5.0];
-        let solution_vector2:Vec<f64> = vec![12.0, 10.0, -3.0, 4.0, 5.0];
+        let solution_vector1: Vec<Gene> = [1.0, 2.0, -3.0, 4.0, 5.0].map(Gene::Real).to_vec();
+        let solution_vector2: Vec<Gene> = [12.0, 10.0, -3.0, 4.0, 5.0].map(Gene::Real).to_vec();
 
         let mut solution_1 = Solution {problem: &problem, solution: solution_vector1, 
                                                 objective_fitness_values: Vec::with_capacity(*problem.number_of_objectives()), 
@@ -189,10 +641,11 @@ mod tests {
                                     SolutionDataTypes::Real(Real::new(Some(0.), Some(100.))),
                                     SolutionDataTypes::Integer(Integer::new(Some(0), Some(100))),
                                     SolutionDataTypes::Real(Real::new(Some(0.), Some(100.))),],
-            objective_function: parabloid_hyper_5
+            objective_function: ObjectiveFunction::Numeric(parabloid_hyper_5),
+            ..Default::default()
         };
-        let solution_vector1:Vec<f64> = vec![1.0, 2.0, -3.0, 4.0, 5.0];
-        let solution_vector2:Vec<f64> = vec![12.0, 10.0, -3.0, 4.0, 5.0];
+        let solution_vector1: Vec<Gene> = [1.0, 2.0, -3.0, 4.0, 5.0].map(Gene::Real).to_vec();
+        let solution_vector2: Vec<Gene> = [12.0, 10.0, -3.0, 4.0, 5.0].map(Gene::Real).to_vec();
 
         let mut solution_1 = Solution {problem: &problem, solution: solution_vector1, 
                                                 objective_fitness_values: Vec::with_capacity(*problem.number_of_objectives()), 
@@ -231,10 +684,11 @@ mod tests {
                                     SolutionDataTypes::Real(Real::new(Some(0.), Some(100.))),
                                     SolutionDataTypes::Integer(Integer::new(Some(0), Some(100))),
                                     SolutionDataTypes::Real(Real::new(Some(0.), Some(100.))),],
-            objective_function: parabloid_hyper_5
+            objective_function: ObjectiveFunction::Numeric(parabloid_hyper_5),
+            ..Default::default()
         };
-        let solution_vector1:Vec<f64> = vec![1.0, 2.0, -3.0, 4.0, 5.0];
-        let solution_vector2:Vec<f64> = vec![12.0, 10.0, -3.0, 4.0, 5.0];
+        let solution_vector1: Vec<Gene> = [1.0, 2.0, -3.0, 4.0, 5.0].map(Gene::Real).to_vec();
+        let solution_vector2: Vec<Gene> = [12.0, 10.0, -3.0, 4.0, 5.0].map(Gene::Real).to_vec();
 
         let mut solution_1 = Solution {problem: &problem, solution: solution_vector1, 
                                                 objective_fitness_values: Vec::with_capacity(*problem.number_of_objectives()), 
@@ -255,4 +709,226 @@ mod tests {
         // assert_eq!(pareto_dominance.compare_solutions(&solution_1, &solution_2), -1);
     }
 
+
+    #[test]
+    fn test_weighted_sum_dominance() {
+        let problem = setup_problem(vec![-1, -1]);
+        let solution_1 = evaluated_solution(&problem, vec![1.0, 4.0]);
+        let solution_2 = evaluated_solution(&problem, vec![2.0, 2.0]);
+
+        let balanced = WeightedSumDominance::new(vec![0.5, 0.5]);
+        assert_eq!(balanced.compare_solutions(&solution_1, &solution_2), 1);
+        assert_eq!(balanced.compare_solutions(&solution_2, &solution_1), -1);
+
+        let first_only = WeightedSumDominance::new(vec![1.0, 0.0]);
+        assert_eq!(first_only.compare_solutions(&solution_1, &solution_2), -1);
+        assert_eq!(first_only.compare_solutions(&solution_1, &solution_1), 0);
+    }
+
+    #[test]
+    fn test_weighted_sum_dominance_rejects_invalid_weights() {
+        assert_eq!(WeightedSumDominance::try_new(Vec::new()).unwrap_err().to_string(), "WeightedSumDominance needs at least one weight");
+        assert!(WeightedSumDominance::try_new(vec![1.0, -0.5]).is_err());
+        assert!(WeightedSumDominance::try_new(vec![f64::NAN]).is_err());
+    }
+
+    #[test]
+    fn test_dominance_enum_validates_weights_length() {
+        let problem = setup_problem(vec![-1, -1]);
+        let invalid = [
+            DominanceEnum::WeightedSum(vec![1.0]),
+            DominanceEnum::Tchebycheff { weights: vec![1.0; 3], ideal_point: None },
+            DominanceEnum::AchievementScalarizing { weights: vec![1.0], reference_point: vec![0.0], augmentation: None },
+            DominanceEnum::PenaltyBoundaryIntersection { weights: vec![1.0; 3], ideal_point: None, penalty: None },
+        ];
+        for dominance_enum in invalid {
+            assert!(matches!(dominance_enum.validate(&problem), Err(Error::LengthMismatch { expected: 2, .. })), "{:?}", dominance_enum);
+        }
+        assert!(DominanceEnum::WeightedSum(vec![1.0, 0.0]).validate(&problem).is_ok());
+    }
+
+    #[test]
+    fn test_weighted_sum_dominance_respects_direction() {
+        // Maximizing the first objective turns the preference around
+        let problem = setup_problem(vec![1, -1]);
+        let solution_1 = evaluated_solution(&problem, vec![1.0, 4.0]);
+        let solution_2 = evaluated_solution(&problem, vec![2.0, 4.0]);
+        let dominance = WeightedSumDominance::new(vec![1.0, 1.0]);
+        assert_eq!(dominance.compare_solutions(&solution_1, &solution_2), 1);
+    }
+
+    #[test]
+    fn test_tchebycheff_dominance() {
+        let problem = setup_problem(vec![-1, -1]);
+        let solution_1 = evaluated_solution(&problem, vec![1.0, 4.0]);
+        let solution_2 = evaluated_solution(&problem, vec![2.0, 2.0]);
+
+        let dominance = TchebycheffDominance::new(vec![1.0, 1.0], None);
+        assert_eq!(dominance.scalarize(&[1.0, 4.0]), 4.0);
+        assert_eq!(dominance.compare_solutions(&solution_1, &solution_2), 1);
+
+        let shifted = TchebycheffDominance::new(vec![1.0, 1.0], Some(vec![1.0, 4.0]));
+        assert_eq!(shifted.compare_solutions(&solution_1, &solution_2), -1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Reference point length does not match weights length")]
+    fn test_tchebycheff_dominance_mismatched_ideal_point() {
+        TchebycheffDominance::new(vec![1.0, 1.0], Some(vec![0.0]));
+    }
+
+    #[test]
+    fn test_try_to_dominance() {
+        let asf = DominanceEnum::AchievementScalarizing { weights: vec![1.0, 1.0], reference_point: vec![0.0], augmentation: None };
+        assert!(matches!(asf.try_to_dominance(), Err(Error::LengthMismatch { expected: 2, found: 1, .. })));
+        let pbi = DominanceEnum::PenaltyBoundaryIntersection { weights: vec![1.0], ideal_point: Some(vec![0.0, 0.0]), penalty: None };
+        assert!(pbi.try_to_dominance().is_err());
+        assert!(DominanceEnum::EpsilonDominance(vec![0.1, -0.1]).try_to_dominance().is_err());
+        assert!(DominanceEnum::Tchebycheff { weights: vec![1.0, 1.0], ideal_point: None }.try_to_dominance().is_ok());
+    }
+
+    #[test]
+    fn test_achievement_scalarizing_dominance() {
+        let problem = setup_problem(vec![-1, -1]);
+        let solution_1 = evaluated_solution(&problem, vec![1.0, 4.0]);
+        let solution_2 = evaluated_solution(&problem, vec![2.0, 2.0]);
+
+        let dominance = AchievementScalarizingDominance::new(vec![1.0, 1.0], vec![0.0, 0.0], Some(0.0));
+        assert_eq!(dominance.scalarize(&[1.0, 4.0]), 4.0);
+        assert_eq!(dominance.compare_solutions(&solution_1, &solution_2), 1);
+
+        // A small weight makes deviations on that objective expensive
+        let dominance = AchievementScalarizingDominance::new(vec![0.1, 1.0], vec![0.0, 0.0], None);
+        assert_eq!(dominance.compare_solutions(&solution_1, &solution_2), -1);
+    }
+
+    #[test]
+    fn test_penalty_boundary_intersection_dominance() {
+        let problem = setup_problem(vec![-1, -1]);
+        let on_direction = evaluated_solution(&problem, vec![1.0, 1.0]);
+        let off_direction = evaluated_solution(&problem, vec![2.0, 0.0]);
+
+        let dominance = PenaltyBoundaryIntersectionDominance::new(vec![1.0, 1.0], None, None);
+        assert!((dominance.scalarize(&[1.0, 1.0]) - 2.0_f64.sqrt()).abs() < 1e-12);
+        assert!((dominance.scalarize(&[2.0, 0.0]) - 6.0 * 2.0_f64.sqrt()).abs() < 1e-12);
+        assert_eq!(dominance.compare_solutions(&on_direction, &off_direction), -1);
+    }
+
+    #[test]
+    fn test_scalarizing_dominance_prefers_fewer_violations() {
+        let mut problem = setup_problem(vec![-1, -1]);
+        problem.objective_constraint = Some(vec![Some(1.0), None]);
+        problem.objective_constraint_operands = Some(vec![Some("<".to_string()), None]);
+        let mut solution_1 = evaluated_solution(&problem, vec![1.0, 1.0]);
+        let solution_2 = evaluated_solution(&problem, vec![5.0, 5.0]);
+        solution_1.constraint_violation = 1;
+
+        let dominance = WeightedSumDominance::new(vec![1.0, 1.0]);
+        assert_eq!(dominance.compare_solutions(&solution_1, &solution_2), 1);
+    }
+
+
+    #[test]
+    fn test_pareto_dominance_results() {
+        let problem = setup_problem(vec![-1, -1]);
+        let better = evaluated_solution(&problem, vec![1.0, 1.0]);
+        let worse = evaluated_solution(&problem, vec![2.0, 1.0]);
+        let trade_off = evaluated_solution(&problem, vec![0.0, 3.0]);
+
+        assert_eq!(ParetoDominance.compare(&better, &worse), DominanceResult::Dominates);
+        assert_eq!(ParetoDominance.compare(&worse, &better), DominanceResult::DominatedBy);
+        assert_eq!(ParetoDominance.compare(&better, &trade_off), DominanceResult::NonDominated);
+        assert_eq!(ParetoDominance.compare(&better, &better.clone()), DominanceResult::Equal);
+        assert_eq!(ParetoDominance.compare_solutions(&better, &worse), -1);
+        assert_eq!(ParetoDominance.compare_solutions(&worse, &better), 1);
+        assert_eq!(ParetoDominance.compare_solutions(&better, &trade_off), 0);
+    }
+
+    #[test]
+    fn test_pareto_dominance_maximization() {
+        let problem = setup_problem(vec![1, 1]);
+        let larger = evaluated_solution(&problem, vec![2.0, 2.0]);
+        let smaller = evaluated_solution(&problem, vec![1.0, 2.0]);
+        assert_eq!(ParetoDominance.compare(&larger, &smaller), DominanceResult::Dominates);
+    }
+
+    #[test]
+    fn test_pareto_dominance_constraint_violation() {
+        let mut problem = setup_problem(vec![-1, -1]);
+        problem.objective_constraint = Some(vec![Some(1.0), None]);
+        problem.objective_constraint_operands = Some(vec![Some("<".to_string()), None]);
+        let mut infeasible = evaluated_solution(&problem, vec![0.0, 0.0]);
+        let feasible = evaluated_solution(&problem, vec![5.0, 5.0]);
+        infeasible.constraint_violation = 1;
+        assert_eq!(ParetoDominance.compare(&feasible, &infeasible), DominanceResult::Dominates);
+        assert_eq!(ParetoDominance.compare(&infeasible, &feasible), DominanceResult::DominatedBy);
+    }
+
+    #[test]
+    fn test_epsilon_dominance() {
+        let problem = setup_problem(vec![-1, -1]);
+        let dominance = EpsilonDominance::new(vec![1.0, 1.0]);
+        let corner = evaluated_solution(&problem, vec![1.1, 1.1]);
+        let same_box = evaluated_solution(&problem, vec![1.5, 1.2]);
+        let next_box = evaluated_solution(&problem, vec![2.1, 1.0]);
+        let trade_off = evaluated_solution(&problem, vec![0.5, 3.5]);
+
+        assert_eq!(dominance.boxes(&[1.5, 1.2]), vec![1.0, 1.0]);
+        assert_eq!(dominance.compare(&corner, &same_box), DominanceResult::Dominates);
+        assert_eq!(dominance.compare(&next_box, &corner), DominanceResult::DominatedBy);
+        assert_eq!(dominance.compare(&corner, &trade_off), DominanceResult::NonDominated);
+    }
+
+    #[test]
+    fn test_attribute_dominance() {
+        let problem = setup_problem(vec![-1, 1]);
+        let solution_1 = evaluated_solution(&problem, vec![1.0, 1.0]);
+        let solution_2 = evaluated_solution(&problem, vec![2.0, 5.0]);
+        assert_eq!(AttributeDominance::new(0).compare(&solution_1, &solution_2), DominanceResult::Dominates);
+        assert_eq!(AttributeDominance::new(1).compare(&solution_1, &solution_2), DominanceResult::DominatedBy);
+        assert!(AttributeDominance::new(2).validate(&problem).is_err());
+        assert!(DominanceEnum::AttributeDominance(2).validate(&problem).is_err());
+        assert!(DominanceEnum::AttributeDominance(1).validate(&problem).is_ok());
+    }
+
+    #[test]
+    fn test_attribute_dominance_out_of_range() {
+        let problem = setup_problem(vec![-1, -1]);
+        assert_eq!(
+            AttributeDominance::try_new(2, &problem).unwrap_err().to_string(),
+            "AttributeDominance objective index exceeds number_of_objectives"
+        );
+        assert_eq!(AttributeDominance::try_new(1, &problem).unwrap().objective_index, 1);
+    }
+
+    #[test]
+    fn test_epsilon_dominance_rejects_invalid_epsilons() {
+        assert_eq!(EpsilonDominance::try_new(Vec::new()).unwrap_err().to_string(), "EpsilonDominance needs at least one epsilon");
+        assert!(EpsilonDominance::try_new(vec![0.1, 0.0]).is_err());
+        assert!(EpsilonDominance::try_new(vec![f64::NAN]).is_err());
+    }
+
+    #[test]
+    fn test_dominance_enum_to_dominance() {
+        let problem = setup_problem(vec![-1, -1]);
+        let solution_1 = evaluated_solution(&problem, vec![1.0, 4.0]);
+        let solution_2 = evaluated_solution(&problem, vec![2.0, 2.0]);
+
+        let expected = [
+            (DominanceEnum::ParetoDominance, DominanceResult::NonDominated),
+            (DominanceEnum::EpsilonDominance(vec![0.5]), DominanceResult::NonDominated),
+            (DominanceEnum::AttributeDominance(1), DominanceResult::DominatedBy),
+            (DominanceEnum::WeightedSum(vec![1.0, 0.0]), DominanceResult::Dominates),
+            (DominanceEnum::Tchebycheff { weights: vec![1.0, 1.0], ideal_point: None }, DominanceResult::DominatedBy),
+            (DominanceEnum::AchievementScalarizing { weights: vec![1.0, 1.0], reference_point: vec![0.0, 0.0], augmentation: None },
+                DominanceResult::DominatedBy),
+            (DominanceEnum::PenaltyBoundaryIntersection { weights: vec![1.0, 0.0], ideal_point: None, penalty: None },
+                DominanceResult::DominatedBy),
+        ];
+        for (dominance_enum, result) in expected {
+            let dominance = dominance_enum.to_dominance();
+            assert_eq!(dominance.compare(&solution_1, &solution_2), result, "{:?}", dominance_enum);
+            assert_eq!(dominance.compare(&solution_2, &solution_1), result.reverse(), "{:?}", dominance_enum);
+        }
+    }
 }
\ No newline at end of file
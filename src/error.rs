@@ -0,0 +1,66 @@
+// Errors of the fallible `try_*` constructors, builders and evaluation, for callers that must not panic on
+// invalid configurations. The panicking counterparts panic with the same messages.
+use std::fmt;
+use crate::seeds::SeedError;
+
+#[derive(Debug)]
+pub enum Error {
+    /// A parameter does not have the length required by another one
+    LengthMismatch { what: &'static str, expected: usize, found: usize },
+    /// An objective constraint operand other than <, >, <=, >=, == and !=
+    InvalidOperator(String),
+    /// An objective has a constraint value without an operand or an operand without a constraint value
+    UnpairedConstraint { objective: usize },
+    /// Lower bound above the upper bound
+    InvertedBounds { lower: String, upper: String },
+    EqualBounds(String),
+    NanBound,
+    /// The objective function returned NaN for the named objective
+    NanObjective { objective: String },
+    /// Two variables or two objectives share a name
+    DuplicateName { kind: &'static str, name: String },
+    /// Any other invalid setting, described by the message
+    InvalidConfiguration(&'static str),
+    Seed(SeedError),
+}
+
+/// Result of the fallible APIs of the crate
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::LengthMismatch { what, expected, found } => write!(f, "{} (expected {}, found {})", what, expected, found),
+            Error::InvalidOperator(operand) => {
+                write!(f, "Invalid operand: {}, expected one of <, >, <=, >=, ==, !=", operand)
+            }
+            Error::UnpairedConstraint { objective } => {
+                write!(f, "Objective {} needs both a constraint value and an operand, or neither", objective)
+            }
+            Error::InvertedBounds { lower, upper } => {
+                write!(f, "Lower bound must be less than upper bound (lower {}, upper {})", lower, upper)
+            }
+            Error::EqualBounds(bound) => write!(f, "Lower bound must not be equal to upper bound (both {})", bound),
+            Error::NanBound => write!(f, "Bounds must not be NaN"),
+            Error::NanObjective { objective } => write!(f, "Objective {} evaluated to NaN", objective),
+            Error::DuplicateName { kind, name } => write!(f, "{} names must be unique, \"{}\" is used twice", kind, name),
+            Error::InvalidConfiguration(message) => write!(f, "{}", message),
+            Error::Seed(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Seed(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<SeedError> for Error {
+    fn from(error: SeedError) -> Self {
+        Error::Seed(error)
+    }
+}
@@ -0,0 +1,406 @@
+// Genetic-programming expression trees. A tree is stored in prefix order, one node per gene, in a fixed
+// number of genes; the genes after the last node of the tree hold `Node::Unused` padding.
+use rand::Rng;
+use rand::seq::SliceRandom;
+use std::fmt;
+use crate::gatypes::Gene;
+
+/// Functions available to the inner nodes of a tree. Division, logarithm, square root and exponential are
+/// protected so that every tree evaluates to a number.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Function {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Negate,
+    Sin,
+    Cos,
+    Exp,
+    Log,
+    Sqrt,
+    Abs,
+    Min,
+    Max,
+    /// The second argument when the first is positive, the third otherwise
+    IfPositive,
+}
+
+impl Function {
+    pub fn arity(&self) -> usize {
+        match self {
+            Function::Negate | Function::Sin | Function::Cos | Function::Exp | Function::Log | Function::Sqrt | Function::Abs => 1,
+            Function::IfPositive => 3,
+            _ => 2,
+        }
+    }
+
+    pub fn apply(&self, arguments: &[f64]) -> f64 {
+        match self {
+            Function::Add => arguments[0] + arguments[1],
+            Function::Subtract => arguments[0] - arguments[1],
+            Function::Multiply => arguments[0] * arguments[1],
+            Function::Divide if arguments[1].abs() < 1e-9 => 1.0,
+            Function::Divide => arguments[0] / arguments[1],
+            Function::Negate => -arguments[0],
+            Function::Sin => arguments[0].sin(),
+            Function::Cos => arguments[0].cos(),
+            Function::Exp => arguments[0].min(700.0).exp(),
+            Function::Log if arguments[0].abs() < 1e-9 => 0.0,
+            Function::Log => arguments[0].abs().ln(),
+            Function::Sqrt => arguments[0].abs().sqrt(),
+            Function::Abs => arguments[0].abs(),
+            Function::Min => arguments[0].min(arguments[1]),
+            Function::Max => arguments[0].max(arguments[1]),
+            Function::IfPositive => if arguments[0] > 0.0 { arguments[1] } else { arguments[2] },
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Function::Add => "+",
+            Function::Subtract => "-",
+            Function::Multiply => "*",
+            Function::Divide => "/",
+            Function::Negate => "neg",
+            Function::Sin => "sin",
+            Function::Cos => "cos",
+            Function::Exp => "exp",
+            Function::Log => "log",
+            Function::Sqrt => "sqrt",
+            Function::Abs => "abs",
+            Function::Min => "min",
+            Function::Max => "max",
+            Function::IfPositive => "if_positive",
+        }
+    }
+}
+
+/// Leaves available to a tree
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Terminal {
+    /// Input at the given index of the values the tree is evaluated on
+    Variable(usize),
+    Constant(f64),
+    /// Ephemeral random constant, drawn uniformly between the bounds each time the terminal is placed
+    RandomConstant(f64, f64),
+}
+
+/// Node of a tree as held by a gene
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Node {
+    Function(Function),
+    Variable(usize),
+    Constant(f64),
+    /// Padding after the last node of the tree
+    Unused,
+}
+
+impl Node {
+    pub fn arity(&self) -> usize {
+        match self {
+            Node::Function(function) => function.arity(),
+            _ => 0,
+        }
+    }
+}
+
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Node::Function(function) => write!(f, "{}", function.symbol()),
+            Node::Variable(index) => write!(f, "x{}", index),
+            Node::Constant(value) => write!(f, "{}", value),
+            Node::Unused => write!(f, "_"),
+        }
+    }
+}
+
+/// Expression tree variable built from a function and a terminal set. Trees deeper than `max_depth` (the
+/// root is at depth 0) or larger than `max_size` nodes are never produced; `max_size` is also the number of
+/// genes the variable takes.
+#[derive(Debug)]
+pub struct ExpressionTree {
+    pub functions: Vec<Function>,
+    pub terminals: Vec<Terminal>,
+    pub max_depth: usize,
+    pub min_initial_depth: usize,
+    pub max_initial_depth: usize,
+    pub max_size: usize,
+}
+
+impl ExpressionTree {
+    /// Largest `max_size` chosen by default, as a full tree of a high `max_depth` would take millions of genes
+    pub const DEFAULT_MAX_SIZE: usize = 512;
+
+    pub fn new(functions: Vec<Function>, terminals: Vec<Terminal>, max_depth: usize) -> Self {
+        if terminals.is_empty() {
+            panic!("An expression tree needs at least one terminal");
+        }
+        let arity = functions.iter().map(Function::arity).max().unwrap_or(0);
+        // Nodes of a full tree of the widest function
+        let (mut full_size, mut level) = (0usize, 1usize);
+        for _ in 0..=max_depth {
+            full_size = full_size.saturating_add(level);
+            level = level.saturating_mul(arity.max(1));
+        }
+        Self {
+            functions,
+            terminals,
+            max_depth,
+            min_initial_depth: max_depth.min(2),
+            max_initial_depth: max_depth.min(6),
+            max_size: full_size.min(Self::DEFAULT_MAX_SIZE),
+        }
+    }
+
+    /// Range of depths of the initial trees
+    pub fn with_initial_depth(mut self, min_depth: usize, max_depth: usize) -> Self {
+        if min_depth > max_depth || max_depth > self.max_depth {
+            panic!("Initial depths require min_depth <= max_depth <= the maximum depth of the tree");
+        }
+        self.min_initial_depth = min_depth;
+        self.max_initial_depth = max_depth;
+        self
+    }
+
+    pub fn with_max_size(mut self, max_size: usize) -> Self {
+        if max_size == 0 {
+            panic!("The maximum size of an expression tree must be greater than zero");
+        }
+        self.max_size = max_size;
+        self
+    }
+
+    /// Random tree by ramped half-and-half: a depth drawn from the initial depths, grown either full or
+    /// with terminals allowed at every level with equal probability
+    pub fn generate_value(&self) -> Vec<Gene> {
+        let mut rng = rand::thread_rng();
+        let full = rng.gen::<bool>();
+        let mut depth = rng.gen_range(self.min_initial_depth..=self.max_initial_depth);
+        loop {
+            let nodes = self.random_tree(depth, full, &mut rng);
+            // A single terminal always fits
+            if self.fits(&nodes) || depth == 0 {
+                return self.to_genes(&nodes);
+            }
+            depth -= 1;
+        }
+    }
+
+    /// Random tree of at most `depth`; every branch reaches `depth` when `full`
+    pub fn random_tree(&self, depth: usize, full: bool, rng: &mut impl Rng) -> Vec<Node> {
+        let mut nodes = Vec::new();
+        // Depths of the child slots still to fill, the next one on top
+        let mut pending = vec![0];
+        while let Some(level) = pending.pop() {
+            let terminal_share = self.terminals.len() as f64 / (self.terminals.len() + self.functions.len()) as f64;
+            let node = if level >= depth || self.functions.is_empty() || (!full && rng.gen::<f64>() < terminal_share) {
+                self.random_terminal(rng)
+            } else {
+                Node::Function(*self.functions.choose(rng).unwrap())
+            };
+            pending.extend(std::iter::repeat_n(level + 1, node.arity()));
+            nodes.push(node);
+        }
+        nodes
+    }
+
+    pub fn random_terminal(&self, rng: &mut impl Rng) -> Node {
+        match *self.terminals.choose(rng).unwrap() {
+            Terminal::Variable(index) => Node::Variable(index),
+            Terminal::Constant(value) => Node::Constant(value),
+            Terminal::RandomConstant(lower, upper) => Node::Constant(rng.gen_range(lower..=upper)),
+        }
+    }
+
+    /// Random node taking `arity` arguments, None when the function set has no such function
+    pub fn random_node_of_arity(&self, arity: usize, rng: &mut impl Rng) -> Option<Node> {
+        if arity == 0 {
+            return Some(self.random_terminal(rng));
+        }
+        let functions: Vec<&Function> = self.functions.iter().filter(|function| function.arity() == arity).collect();
+        functions.choose(rng).map(|&&function| Node::Function(function))
+    }
+
+    /// Whether the tree respects the depth and size limits
+    pub fn fits(&self, nodes: &[Node]) -> bool {
+        nodes.len() <= self.max_size && Self::depths(nodes).into_iter().max().unwrap_or(0) <= self.max_depth
+    }
+
+    /// Genes of the variable holding the tree, padded to `max_size`
+    pub fn to_genes(&self, nodes: &[Node]) -> Vec<Gene> {
+        nodes.iter().copied().chain(std::iter::repeat(Node::Unused)).take(self.max_size).map(Gene::Node).collect()
+    }
+
+    /// Nodes of the tree held by the genes, without the padding
+    pub fn nodes(genes: &[Gene]) -> Vec<Node> {
+        let nodes: Vec<Node> = genes
+            .iter()
+            .map(|gene| match gene {
+                Gene::Node(node) => *node,
+                _ => Node::Unused,
+            })
+            .collect();
+        let end = Self::subtree_end(&nodes, 0);
+        nodes[..end].to_vec()
+    }
+
+    /// End (exclusive) of the subtree rooted at `start`
+    pub fn subtree_end(nodes: &[Node], start: usize) -> usize {
+        let mut open = 1;
+        let mut end = start;
+        while open > 0 && end < nodes.len() {
+            open = open + nodes[end].arity() - 1;
+            end += 1;
+        }
+        end
+    }
+
+    /// Depth of every node, the root being at depth 0
+    pub fn depths(nodes: &[Node]) -> Vec<usize> {
+        let mut depths = Vec::with_capacity(nodes.len());
+        let mut pending = vec![0];
+        for node in nodes {
+            let level = pending.pop().unwrap_or(0);
+            pending.extend(std::iter::repeat_n(level + 1, node.arity()));
+            depths.push(level);
+        }
+        depths
+    }
+
+    /// Number of nodes of the tree held by the genes, e.g. as a parsimony objective
+    pub fn size(genes: &[Gene]) -> usize {
+        Self::nodes(genes).len()
+    }
+
+    pub fn depth(genes: &[Gene]) -> usize {
+        Self::depths(&Self::nodes(genes)).into_iter().max().unwrap_or(0)
+    }
+
+    /// Value of the tree held by the genes for the given inputs
+    pub fn evaluate(genes: &[Gene], inputs: &[f64]) -> f64 {
+        let mut stack: Vec<f64> = Vec::new();
+        // In reverse prefix order the arguments of a function are on the stack, the first one on top
+        for node in Self::nodes(genes).iter().rev() {
+            let value = match node {
+                Node::Function(function) => {
+                    let arguments: Vec<f64> = (0..function.arity()).map(|_| stack.pop().unwrap_or(0.0)).collect();
+                    function.apply(&arguments)
+                }
+                Node::Variable(index) => inputs[*index],
+                Node::Constant(value) => *value,
+                Node::Unused => 0.0,
+            };
+            stack.push(value);
+        }
+        stack.pop().unwrap_or(0.0)
+    }
+
+    /// Readable form of the tree held by the genes, e.g. `(x0 * sin(x1))`
+    pub fn format(genes: &[Gene]) -> String {
+        let nodes = Self::nodes(genes);
+        let mut stack: Vec<String> = Vec::new();
+        for node in nodes.iter().rev() {
+            let text = match node {
+                Node::Function(function) => {
+                    let arguments: Vec<String> = (0..function.arity()).map(|_| stack.pop().unwrap_or_default()).collect();
+                    match function {
+                        Function::Add | Function::Subtract | Function::Multiply | Function::Divide => {
+                            format!("({} {} {})", arguments[0], function.symbol(), arguments[1])
+                        }
+                        _ => format!("{}({})", function.symbol(), arguments.join(", ")),
+                    }
+                }
+                terminal => terminal.to_string(),
+            };
+            stack.push(text);
+        }
+        stack.pop().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn genes(nodes: &[Node]) -> Vec<Gene> {
+        nodes.iter().copied().map(Gene::Node).collect()
+    }
+
+    #[test]
+    fn test_evaluate_and_format() {
+        // (x0 * sin(x1)) + 2
+        let tree = genes(&[
+            Node::Function(Function::Add),
+            Node::Function(Function::Multiply),
+            Node::Variable(0),
+            Node::Function(Function::Sin),
+            Node::Variable(1),
+            Node::Constant(2.0),
+            Node::Unused,
+            Node::Unused,
+        ]);
+        let value = ExpressionTree::evaluate(&tree, &[3.0, 0.5]);
+        assert!((value - (3.0 * 0.5f64.sin() + 2.0)).abs() < 1e-12);
+        assert_eq!(ExpressionTree::format(&tree), "((x0 * sin(x1)) + 2)");
+        assert_eq!(ExpressionTree::size(&tree), 6);
+        assert_eq!(ExpressionTree::depth(&tree), 3);
+        let nodes = ExpressionTree::nodes(&tree);
+        assert_eq!(ExpressionTree::subtree_end(&nodes, 1), 5);
+        assert_eq!(ExpressionTree::depths(&nodes), vec![0, 1, 2, 2, 3, 1]);
+    }
+
+    #[test]
+    fn test_protected_functions() {
+        assert_eq!(Function::Divide.apply(&[1.0, 0.0]), 1.0);
+        assert_eq!(Function::Log.apply(&[0.0]), 0.0);
+        assert_eq!(Function::Sqrt.apply(&[-4.0]), 2.0);
+        assert!(Function::Exp.apply(&[1e6]).is_finite());
+        assert_eq!(Function::IfPositive.apply(&[-1.0, 5.0, 7.0]), 7.0);
+    }
+
+    #[test]
+    fn test_ramped_half_and_half() {
+        let tree = ExpressionTree::new(
+            vec![Function::Add, Function::Multiply, Function::Negate],
+            vec![Terminal::Variable(0), Terminal::RandomConstant(-1.0, 1.0)],
+            5,
+        )
+        .with_initial_depth(1, 4);
+        assert_eq!(tree.max_size, 63);
+        let mut depths = std::collections::HashSet::new();
+        for _ in 0..200 {
+            let genes = tree.generate_value();
+            assert_eq!(genes.len(), 63);
+            let depth = ExpressionTree::depth(&genes);
+            assert!(depth <= 4);
+            depths.insert(depth);
+            assert!(ExpressionTree::nodes(&genes).iter().all(|node| match node {
+                Node::Constant(value) => (-1.0..=1.0).contains(value),
+                _ => true,
+            }));
+        }
+        assert!(depths.len() >= 3);
+        // Full trees of the widest function do not fit a small size limit and come out shallower
+        let small = ExpressionTree::new(vec![Function::Add], vec![Terminal::Variable(0)], 6).with_initial_depth(6, 6).with_max_size(20);
+        for _ in 0..20 {
+            assert!(ExpressionTree::size(&small.generate_value()) <= 20);
+        }
+    }
+
+    #[test]
+    fn test_limits() {
+        let tree = ExpressionTree::new(vec![Function::Add], vec![Terminal::Variable(0)], 1);
+        let deep = [Node::Function(Function::Add), Node::Function(Function::Add), Node::Variable(0), Node::Variable(0), Node::Variable(0)];
+        assert!(!tree.fits(&deep));
+        assert!(tree.fits(&deep[1..4]));
+        assert_eq!(ExpressionTree::new(vec![Function::Add], vec![Terminal::Variable(0)], 20).max_size, ExpressionTree::DEFAULT_MAX_SIZE);
+    }
+
+    #[test]
+    #[should_panic(expected = "An expression tree needs at least one terminal")]
+    fn test_tree_without_terminals() {
+        ExpressionTree::new(vec![Function::Add], Vec::new(), 3);
+    }
+}
@@ -1,5 +1,11 @@
 // import random crate
 use rand::Rng;
+use rand::seq::SliceRandom;
+use rand::seq::index::sample;
+use rand_distr::{Distribution, StandardNormal};
+use std::fmt;
+use crate::expression_tree::{ExpressionTree, Node};
+use crate::error::{Error, Result};
 // import the powf function from the f64 module
 
 
@@ -8,7 +14,135 @@ use rand::Rng;
 pub enum SolutionDataTypes {
     Real(Real),
     Integer(Integer),
-    BitBinary(BitBinary)
+    BitBinary(BitBinary),
+    Permutation(Permutation),
+    Subset(Subset),
+    BitString(BitString),
+    Categorical(Categorical),
+    ExpressionTree(ExpressionTree),
+}
+
+impl SolutionDataTypes {
+    /// Gene converted to the representation of the variable, rounded and saturated to what it can hold
+    pub fn representable(&self, gene: Gene) -> Gene {
+        match self {
+            SolutionDataTypes::Real(real) => Gene::Real(real.representable(gene.as_f64())),
+            SolutionDataTypes::Integer(integer) => Gene::Integer(integer.representable_gene(gene)),
+            SolutionDataTypes::BitBinary(_) => Gene::Bit(gene.as_bool()),
+            SolutionDataTypes::Permutation(_) | SolutionDataTypes::Subset(_) => Gene::Element(gene.as_usize()),
+            SolutionDataTypes::BitString(_) => Gene::Word(gene.as_u32()),
+            SolutionDataTypes::Categorical(_) => Gene::Category(gene.as_usize()),
+            SolutionDataTypes::ExpressionTree(_) => gene,
+        }
+    }
+
+    /// Number of genes the variable takes in the genome
+    pub fn gene_count(&self) -> usize {
+        match self {
+            SolutionDataTypes::Permutation(permutation) => permutation.size,
+            SolutionDataTypes::Subset(subset) => subset.size,
+            SolutionDataTypes::BitString(bit_string) => bit_string.words(),
+            SolutionDataTypes::ExpressionTree(tree) => tree.max_size,
+            _ => 1,
+        }
+    }
+}
+
+/// A single gene of a genome, held in the representation of its variable so that integers beyond 2^53,
+/// bits and element indices are stored exactly
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Gene {
+    Real(f64),
+    Integer(i64),
+    Bit(bool),
+    /// Element of a permutation or subset
+    Element(usize),
+    /// Word of 32 packed bits of a bit string
+    Word(u32),
+    /// Index of the chosen label of a categorical variable
+    Category(usize),
+    /// Node of an expression tree
+    Node(Node),
+}
+
+impl Gene {
+    /// Numeric value of the gene, as seen by real-coded operators and objective functions over f64
+    pub fn as_f64(&self) -> f64 {
+        match *self {
+            Gene::Real(value) => value,
+            Gene::Integer(value) => value as f64,
+            Gene::Bit(value) => value as u8 as f64,
+            Gene::Element(value) | Gene::Category(value) => value as f64,
+            Gene::Word(value) => value as f64,
+            Gene::Node(Node::Constant(value)) => value,
+            Gene::Node(_) => f64::NAN,
+        }
+    }
+
+    /// Integer value of the gene; real values are rounded and saturated
+    pub fn as_i64(&self) -> i64 {
+        match *self {
+            Gene::Real(value) => value.round() as i64,
+            Gene::Integer(value) => value,
+            Gene::Bit(value) => value as i64,
+            Gene::Element(value) | Gene::Category(value) => value as i64,
+            Gene::Word(value) => value as i64,
+            Gene::Node(_) => self.as_f64().round() as i64,
+        }
+    }
+
+    pub fn as_bool(&self) -> bool {
+        match *self {
+            Gene::Bit(value) => value,
+            gene => gene.as_f64() != 0.0,
+        }
+    }
+
+    /// Index value of the gene; negative values saturate to zero
+    pub fn as_usize(&self) -> usize {
+        match *self {
+            Gene::Element(value) | Gene::Category(value) => value,
+            gene => gene.as_i64().max(0) as usize,
+        }
+    }
+
+    pub fn as_u32(&self) -> u32 {
+        match *self {
+            Gene::Word(value) => value,
+            gene => gene.as_i64().clamp(0, u32::MAX as i64) as u32,
+        }
+    }
+}
+
+impl From<f64> for Gene {
+    fn from(value: f64) -> Self {
+        Gene::Real(value)
+    }
+}
+
+impl From<i64> for Gene {
+    fn from(value: i64) -> Self {
+        Gene::Integer(value)
+    }
+}
+
+impl From<bool> for Gene {
+    fn from(value: bool) -> Self {
+        Gene::Bit(value)
+    }
+}
+
+impl fmt::Display for Gene {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Gene::Real(value) => write!(f, "{}", value),
+            Gene::Integer(value) => write!(f, "{}", value),
+            Gene::Bit(value) => write!(f, "{}", *value as u8),
+            Gene::Element(value) | Gene::Category(value) => write!(f, "{}", value),
+            Gene::Word(value) => write!(f, "{:#010x}", value),
+            Gene::Node(node) => write!(f, "{}", node),
+        }
+    }
 }
 
 pub trait SolutionType {
@@ -16,6 +150,7 @@ pub trait SolutionType {
     
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum RealDataType { 
     F64, 
     F32, 
@@ -25,6 +160,38 @@ pub enum RealDataType {
 
 }
 
+impl RealDataType {
+    /// Largest finite magnitude of the type; F128 and F256 have no native type and are kept at f64 precision
+    pub fn max_value(&self) -> f64 {
+        match self {
+            RealDataType::F16 => 65504.0,
+            RealDataType::F32 => f32::MAX as f64,
+            _ => f64::MAX,
+        }
+    }
+
+    /// Nearest value representable in the type, saturated to its finite range
+    pub fn round(&self, value: f64) -> f64 {
+        let saturated = value.clamp(-self.max_value(), self.max_value());
+        match self {
+            RealDataType::F32 => saturated as f32 as f64,
+            RealDataType::F16 => round_to_half(saturated),
+            _ => saturated,
+        }
+    }
+}
+
+// Rounds half to even to the 11 significant bits of IEEE half precision, including its subnormal spacing of 2^-24
+fn round_to_half(value: f64) -> f64 {
+    if value == 0.0 || !value.is_finite() {
+        return value;
+    }
+    let exponent = value.abs().log2().floor().max(-14.0);
+    let step = 2f64.powf(exponent - 10.0);
+    ((value / step).round_ties_even() * step).clamp(-65504.0, 65504.0)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum IntegerDataType { 
     I64, 
     I32, 
@@ -37,6 +204,55 @@ pub enum IntegerDataType {
 
 }
 
+impl IntegerDataType {
+    /// Smallest and largest value of the type; U64 is capped at i64::MAX since bounds are stored as i64
+    pub fn range(&self) -> (i64, i64) {
+        match self {
+            IntegerDataType::I64 => (i64::MIN, i64::MAX),
+            IntegerDataType::I32 => (i32::MIN as i64, i32::MAX as i64),
+            IntegerDataType::I16 => (i16::MIN as i64, i16::MAX as i64),
+            IntegerDataType::I8 => (i8::MIN as i64, i8::MAX as i64),
+            IntegerDataType::U64 => (0, i64::MAX),
+            IntegerDataType::U32 => (0, u32::MAX as i64),
+            IntegerDataType::U16 => (0, u16::MAX as i64),
+            IntegerDataType::U8 => (0, u8::MAX as i64),
+        }
+    }
+}
+
+/// A variable value in the storage type of its variable, for serialization and export
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NativeValue {
+    F32(f32),
+    F64(f64),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+}
+
+impl fmt::Display for NativeValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NativeValue::F32(value) => write!(f, "{}", value),
+            NativeValue::F64(value) => write!(f, "{}", value),
+            NativeValue::I8(value) => write!(f, "{}", value),
+            NativeValue::I16(value) => write!(f, "{}", value),
+            NativeValue::I32(value) => write!(f, "{}", value),
+            NativeValue::I64(value) => write!(f, "{}", value),
+            NativeValue::U8(value) => write!(f, "{}", value),
+            NativeValue::U16(value) => write!(f, "{}", value),
+            NativeValue::U32(value) => write!(f, "{}", value),
+            NativeValue::U64(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum BinaryDataType { 
     Bool, 
     U8,
@@ -49,6 +265,23 @@ pub enum BinaryDataType {
     U256
 }
 
+impl BinaryDataType {
+    /// Number of bits of the type
+    pub fn width(&self) -> usize {
+        match self {
+            BinaryDataType::Bool => 1,
+            BinaryDataType::U2 => 2,
+            BinaryDataType::U4 => 4,
+            BinaryDataType::U8 => 8,
+            BinaryDataType::U16 => 16,
+            BinaryDataType::U32 => 32,
+            BinaryDataType::U64 => 64,
+            BinaryDataType::U128 => 128,
+            BinaryDataType::U256 => 256,
+        }
+    }
+}
+
 
 #[derive(Debug)]
 pub struct BitBinary { 
@@ -68,66 +301,496 @@ impl BitBinary {
 }
 
 
+/// Distribution of the initial values of a Real or Integer variable. Without one, bounded variables are
+/// sampled uniformly, half-bounded ones at the bound plus or minus a half-normal step, and unbounded ones
+/// from a standard normal.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InitialDistribution {
+    Uniform { lower: f64, upper: f64 },
+    Normal { mean: f64, standard_deviation: f64 },
+    /// Uniform in the logarithm, for positive scale-like parameters spanning orders of magnitude
+    LogUniform { lower: f64, upper: f64 },
+}
+
+impl InitialDistribution {
+    fn validate(&self) -> Result<()> {
+        match *self {
+            InitialDistribution::Uniform { lower, upper } if lower >= upper => {
+                Err(Error::InvalidConfiguration("Uniform distribution lower must be less than upper"))
+            }
+            InitialDistribution::Normal { standard_deviation, .. } if standard_deviation <= 0.0 => {
+                Err(Error::InvalidConfiguration("Normal distribution standard_deviation must be greater than zero"))
+            }
+            InitialDistribution::LogUniform { lower, upper } if lower <= 0.0 || lower >= upper => {
+                Err(Error::InvalidConfiguration("LogUniform distribution requires 0 < lower < upper"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn sample(&self, rng: &mut impl Rng) -> f64 {
+        match *self {
+            InitialDistribution::Uniform { lower, upper } => rng.gen_range(lower..upper),
+            InitialDistribution::Normal { mean, standard_deviation } => {
+                mean + standard_deviation * rng.sample::<f64, _>(StandardNormal)
+            }
+            InitialDistribution::LogUniform { lower, upper } => rng.gen_range(lower.ln()..upper.ln()).exp(),
+        }
+    }
+}
+
+// Initial value between optional limits when no distribution is given
+fn sample_between(lower: Option<f64>, upper: Option<f64>, rng: &mut impl Rng) -> f64 {
+    let step: f64 = StandardNormal.sample(rng);
+    match (lower, upper) {
+        (Some(lower), Some(upper)) => rng.gen_range(lower..upper),
+        (Some(lower), None) => lower + step.abs(),
+        (None, Some(upper)) => upper - step.abs(),
+        (None, None) => step,
+    }
+}
+
 // Create an Integer object where the lower and upper bounds are optional parameters
 #[derive(Debug)]
 pub struct Integer {
     pub lower_bound: Option<i64>,
-    pub upper_bound: Option<i64>
+    pub upper_bound: Option<i64>,
+    pub data_type: IntegerDataType,
+    pub initial_distribution: Option<InitialDistribution>
 }
 
 // create a method on Integer that randomly genrates a number between the lower and upper bounds
 impl Integer {
     // When creating a new Integer object, the lower and upper bounds are optional parameters check if lower < upper with a panic
     pub fn new(lower_bound: Option<i64>, upper_bound: Option<i64>) -> Self {
-        if lower_bound.is_some() && upper_bound.is_some() {
-            if lower_bound.unwrap() > upper_bound.unwrap() {
-                panic!("Lower bound must be less than upper bound");
-            } else if lower_bound.unwrap() == upper_bound.unwrap() {
-                panic!("Lower bound must not be equal to upper bound");
-            } 
-        }
-        Self {
+        Self::try_new(lower_bound, upper_bound).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Like `new`, but reports inverted or equal bounds as an error
+    pub fn try_new(lower_bound: Option<i64>, upper_bound: Option<i64>) -> Result<Self> {
+        if let (Some(lower), Some(upper)) = (lower_bound, upper_bound) {
+            if lower > upper {
+                return Err(Error::InvertedBounds { lower: lower.to_string(), upper: upper.to_string() });
+            } else if lower == upper {
+                return Err(Error::EqualBounds(lower.to_string()));
+            }
+        }
+        Ok(Self {
             lower_bound,
-            upper_bound
+            upper_bound,
+            data_type: IntegerDataType::I64,
+            initial_distribution: None
+        })
+    }
+
+    pub fn with_initial_distribution(self, distribution: InitialDistribution) -> Self {
+        self.try_with_initial_distribution(distribution).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Like `with_initial_distribution`, but reports an invalid distribution as an error
+    pub fn try_with_initial_distribution(mut self, distribution: InitialDistribution) -> Result<Self> {
+        distribution.validate()?;
+        self.initial_distribution = Some(distribution);
+        Ok(self)
+    }
+
+    /// Lower bound that limits sampling and step sizes: the explicit one, or the type's own range except
+    /// for I64, whose range is treated as unbounded
+    pub fn lower_limit(&self) -> Option<i64> {
+        self.lower_bound.or(match self.data_type {
+            IntegerDataType::I64 => None,
+            data_type => Some(data_type.range().0),
+        })
+    }
+
+    /// Upper bound that limits sampling and step sizes, with I64 and U64 treated as unbounded
+    pub fn upper_limit(&self) -> Option<i64> {
+        self.upper_bound.or(match self.data_type {
+            IntegerDataType::I64 | IntegerDataType::U64 => None,
+            data_type => Some(data_type.range().1),
+        })
+    }
+
+    /// Stores the values as `data_type`, whose range bounds the values where no explicit bound is given
+    pub fn with_data_type(self, data_type: IntegerDataType) -> Self {
+        self.try_with_data_type(data_type).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Like `with_data_type`, but reports bounds outside the range of the data type as an error
+    pub fn try_with_data_type(mut self, data_type: IntegerDataType) -> Result<Self> {
+        let (minimum, maximum) = data_type.range();
+        if self.lower_bound.is_some_and(|bound| bound < minimum) || self.upper_bound.is_some_and(|bound| bound > maximum) {
+            return Err(Error::InvalidConfiguration("Bounds exceed the range of the data type"));
+        }
+        self.data_type = data_type;
+        Ok(self)
+    }
+
+    /// Explicit lower bound, or the smallest value of the data type
+    pub fn lower(&self) -> i64 {
+        self.lower_bound.unwrap_or(self.data_type.range().0)
+    }
+
+    /// Explicit upper bound, or the largest value of the data type
+    pub fn upper(&self) -> i64 {
+        self.upper_bound.unwrap_or(self.data_type.range().1)
+    }
+
+    /// Value rounded and saturated to what the variable can hold
+    pub fn representable(&self, value: f64) -> f64 {
+        value.round().clamp(self.lower() as f64, self.upper() as f64)
+    }
+
+    /// Integer held by a gene saturated to the bounds, exactly for integer genes
+    pub fn representable_gene(&self, gene: Gene) -> i64 {
+        match gene {
+            Gene::Real(value) => self.representable(value) as i64,
+            gene => gene.as_i64().clamp(self.lower(), self.upper()),
         }
     }
+
+    pub fn native(&self, value: i64) -> NativeValue {
+        match self.data_type {
+            IntegerDataType::I64 => NativeValue::I64(value),
+            IntegerDataType::I32 => NativeValue::I32(value as i32),
+            IntegerDataType::I16 => NativeValue::I16(value as i16),
+            IntegerDataType::I8 => NativeValue::I8(value as i8),
+            IntegerDataType::U64 => NativeValue::U64(value as u64),
+            IntegerDataType::U32 => NativeValue::U32(value as u32),
+            IntegerDataType::U16 => NativeValue::U16(value as u16),
+            IntegerDataType::U8 => NativeValue::U8(value as u8),
+        }
+    }
+
     // Create Generate Value Method
     pub fn generate_value(&self) -> Option<i64> {
         let mut rng = rand::thread_rng();
-        Some(rng.gen_range(self.lower_bound.unwrap_or(i64::MIN)..self.upper_bound.unwrap_or(i64::MAX)))
+        match (self.initial_distribution, self.lower_limit(), self.upper_limit()) {
+            (None, Some(lower), Some(upper)) => Some(rng.gen_range(lower..upper)),
+            (Some(distribution), _, _) => Some(self.representable(distribution.sample(&mut rng)) as i64),
+            (None, lower, upper) => Some(self.representable(sample_between(lower.map(|l| l as f64), upper.map(|u| u as f64), &mut rng)) as i64),
+        }
     }
 }
 
 #[derive(Debug)]
 pub struct Real { 
     pub lower_bound: Option<f64>,
-    pub upper_bound: Option<f64>
+    pub upper_bound: Option<f64>,
+    pub data_type: RealDataType,
+    pub step: Option<f64>, // values restricted to lower_bound + k * step when set
+    pub initial_distribution: Option<InitialDistribution>
 }
 
 // create a method on Real that randomly genrates a number between the lower and upper bounds
 impl Real {
     // When creating a new Real object, the lower and upper bounds are optional parameters check if lower < upper with a panic
     pub fn new(lower_bound: Option<f64>, upper_bound: Option<f64>) -> Self {
-        if lower_bound.is_some() && upper_bound.is_some() {
-            if lower_bound.unwrap() > upper_bound.unwrap() {
-                panic!("Lower bound must be less than upper bound");
-            } else if lower_bound.unwrap() == upper_bound.unwrap() {
-                panic!("Lower bound must not be equal to upper bound");
+        Self::try_new(lower_bound, upper_bound).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Like `new`, but reports NaN, inverted or equal bounds as an error
+    pub fn try_new(lower_bound: Option<f64>, upper_bound: Option<f64>) -> Result<Self> {
+        if lower_bound.is_some_and(f64::is_nan) || upper_bound.is_some_and(f64::is_nan) {
+            return Err(Error::NanBound);
+        }
+        if let (Some(lower), Some(upper)) = (lower_bound, upper_bound) {
+            if lower > upper {
+                return Err(Error::InvertedBounds { lower: lower.to_string(), upper: upper.to_string() });
+            } else if lower == upper {
+                return Err(Error::EqualBounds(lower.to_string()));
             }
         }
-        Self {
+        Ok(Self {
             lower_bound,
-            upper_bound
+            upper_bound,
+            data_type: RealDataType::F64,
+            step: None,
+            initial_distribution: None
+        })
+    }
+
+    pub fn with_initial_distribution(self, distribution: InitialDistribution) -> Self {
+        self.try_with_initial_distribution(distribution).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Like `with_initial_distribution`, but reports an invalid distribution as an error
+    pub fn try_with_initial_distribution(mut self, distribution: InitialDistribution) -> Result<Self> {
+        distribution.validate()?;
+        self.initial_distribution = Some(distribution);
+        Ok(self)
+    }
+
+    /// Lower bound that limits sampling and step sizes: the explicit one, or the F16 range; the wider types
+    /// are treated as unbounded
+    pub fn lower_limit(&self) -> Option<f64> {
+        self.lower_bound.or(match self.data_type {
+            RealDataType::F16 => Some(-self.data_type.max_value()),
+            _ => None,
+        })
+    }
+
+    /// Upper bound that limits sampling and step sizes, see `lower_limit`
+    pub fn upper_limit(&self) -> Option<f64> {
+        self.upper_bound.or(match self.data_type {
+            RealDataType::F16 => Some(self.data_type.max_value()),
+            _ => None,
+        })
+    }
+
+    /// Restricts the values to the grid lower_bound + k * step inside the bounds
+    pub fn with_step(self, step: f64) -> Self {
+        self.try_with_step(step).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Like `with_step`, but reports a non-positive step or a missing lower bound as an error
+    pub fn try_with_step(mut self, step: f64) -> Result<Self> {
+        if step <= 0.0 || step.is_nan() {
+            return Err(Error::InvalidConfiguration("Step must be greater than zero"));
+        }
+        if self.lower_bound.is_none() {
+            return Err(Error::InvalidConfiguration("A step requires a lower bound"));
+        }
+        self.step = Some(step);
+        Ok(self)
+    }
+
+    // Largest k such that lower_bound + k * step stays within the upper limit
+    fn last_step(&self, step: f64) -> f64 {
+        ((self.upper() - self.lower()) / step + 1e-9).floor()
+    }
+
+    /// Stores the values as `data_type`, whose finite range bounds the values where no explicit bound is given
+    pub fn with_data_type(self, data_type: RealDataType) -> Self {
+        self.try_with_data_type(data_type).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Like `with_data_type`, but reports bounds outside the range of the data type as an error
+    pub fn try_with_data_type(mut self, data_type: RealDataType) -> Result<Self> {
+        let maximum = data_type.max_value();
+        if self.lower_bound.is_some_and(|bound| bound < -maximum) || self.upper_bound.is_some_and(|bound| bound > maximum) {
+            return Err(Error::InvalidConfiguration("Bounds exceed the range of the data type"));
+        }
+        self.data_type = data_type;
+        Ok(self)
+    }
+
+    /// Explicit lower bound, or the lowest finite value of the data type
+    pub fn lower(&self) -> f64 {
+        self.lower_bound.unwrap_or(-self.data_type.max_value())
+    }
+
+    /// Explicit upper bound, or the largest finite value of the data type
+    pub fn upper(&self) -> f64 {
+        self.upper_bound.unwrap_or(self.data_type.max_value())
+    }
+
+    /// Value saturated to the bounds, snapped to the nearest grid point when there is a step, and rounded to
+    /// the precision of the data type
+    pub fn representable(&self, value: f64) -> f64 {
+        let clamped = value.clamp(self.lower(), self.upper());
+        let snapped = match self.step {
+            Some(step) => self.lower() + ((clamped - self.lower()) / step).round().min(self.last_step(step)) * step,
+            None => clamped,
+        };
+        self.data_type.round(snapped)
+    }
+
+    pub fn native(&self, value: f64) -> NativeValue {
+        match self.data_type {
+            RealDataType::F32 | RealDataType::F16 => NativeValue::F32(value as f32),
+            _ => NativeValue::F64(value),
         }
     }
 
     pub fn generate_value(&self) -> Option<f64> {
         let mut rng = rand::thread_rng();
-        Some(rng.gen_range(self.lower_bound.unwrap_or(f64::MIN)..self.upper_bound.unwrap_or(f64::MAX)))
+        match (self.initial_distribution, self.step, self.upper_limit()) {
+            (Some(distribution), _, _) => Some(self.representable(distribution.sample(&mut rng))),
+            // Every grid point is equally likely
+            (None, Some(step), Some(_)) => Some(self.representable(self.lower() + rng.gen_range(0.0..=self.last_step(step)).round() * step)),
+            (None, _, upper) => Some(self.representable(sample_between(self.lower_limit(), upper, &mut rng))),
+        }
     }
 }
 
 
+// Ordering of the elements 0..size, stored as one gene per position
+#[derive(Debug)]
+pub struct Permutation {
+    pub size: usize
+}
+
+impl Permutation {
+    pub fn new(size: usize) -> Self {
+        Self::try_new(size).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Like `new`, but reports an empty permutation as an error
+    pub fn try_new(size: usize) -> Result<Self> {
+        if size == 0 {
+            return Err(Error::InvalidConfiguration("Permutation size must be greater than zero"));
+        }
+        Ok(Self { size })
+    }
+
+    pub fn generate_value(&self) -> Vec<Gene> {
+        let mut rng = rand::thread_rng();
+        let mut elements: Vec<Gene> = (0..self.size).map(Gene::Element).collect();
+        elements.shuffle(&mut rng);
+        elements
+    }
+}
+
+// Selection of `size` distinct elements out of 0..elements, stored as one gene per selected element
+#[derive(Debug)]
+pub struct Subset {
+    pub elements: usize,
+    pub size: usize
+}
+
+impl Subset {
+    pub fn new(elements: usize, size: usize) -> Self {
+        Self::try_new(elements, size).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Like `new`, but reports an empty subset or one larger than the number of elements as an error
+    pub fn try_new(elements: usize, size: usize) -> Result<Self> {
+        if size == 0 {
+            return Err(Error::InvalidConfiguration("Subset size must be greater than zero"));
+        }
+        if size > elements {
+            return Err(Error::InvalidConfiguration("Subset size must not exceed the number of elements"));
+        }
+        Ok(Self { elements, size })
+    }
+
+    pub fn generate_value(&self) -> Vec<Gene> {
+        let mut rng = rand::thread_rng();
+        sample(&mut rng, self.elements, self.size).into_iter().map(Gene::Element).collect()
+    }
+
+    /// Whether the genes hold exactly `size` distinct elements of 0..elements
+    pub fn is_valid(&self, genes: &[Gene]) -> bool {
+        let mut seen = vec![false; self.elements];
+        genes.len() == self.size
+            && genes.iter().all(|gene| {
+                let value = gene.as_f64();
+                let element = gene.as_usize();
+                let fresh = value >= 0.0 && value.fract() == 0.0 && element < self.elements && !seen[element];
+                if fresh {
+                    seen[element] = true;
+                }
+                fresh
+            })
+    }
+}
+
+// Fixed-width string of bits packed into 32-bit words, one word per gene; bit `i` is bit
+// `i % 32` of word `i / 32` and the unused high bits of the last word are always zero
+#[derive(Debug)]
+pub struct BitString {
+    pub bits: usize
+}
+
+impl BitString {
+    pub const WORD_BITS: usize = 32;
+
+    pub fn new(bits: usize) -> Self {
+        Self::try_new(bits).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Like `new`, but reports a string without bits as an error
+    pub fn try_new(bits: usize) -> Result<Self> {
+        if bits == 0 {
+            return Err(Error::InvalidConfiguration("BitString must have at least one bit"));
+        }
+        Ok(Self { bits })
+    }
+
+    /// Bit string as wide as the given binary data type
+    pub fn from_data_type(data_type: BinaryDataType) -> Self {
+        Self::new(data_type.width())
+    }
+
+    /// Number of words (genes) holding the bits
+    pub fn words(&self) -> usize {
+        self.bits.div_ceil(Self::WORD_BITS)
+    }
+
+    pub fn generate_value(&self) -> Vec<Gene> {
+        let mut rng = rand::thread_rng();
+        let mut words: Vec<u32> = (0..self.words()).map(|_| rng.gen::<u32>()).collect();
+        let last = words.len() - 1;
+        words[last] &= self.last_word_mask();
+        words.into_iter().map(Gene::Word).collect()
+    }
+
+    // Mask of the bits of the last word in use
+    fn last_word_mask(&self) -> u32 {
+        match self.bits % Self::WORD_BITS {
+            0 => u32::MAX,
+            used => (1 << used) - 1,
+        }
+    }
+
+    pub fn bit(genes: &[Gene], position: usize) -> bool {
+        (genes[position / Self::WORD_BITS].as_u32() >> (position % Self::WORD_BITS)) & 1 == 1
+    }
+
+    pub fn set_bit(genes: &mut [Gene], position: usize, value: bool) {
+        let word = genes[position / Self::WORD_BITS].as_u32();
+        let mask = 1 << (position % Self::WORD_BITS);
+        genes[position / Self::WORD_BITS] = Gene::Word(if value { word | mask } else { word & !mask });
+    }
+
+    pub fn to_bits(&self, genes: &[Gene]) -> Vec<bool> {
+        (0..self.bits).map(|position| Self::bit(genes, position)).collect()
+    }
+
+    pub fn from_bits(bits: &[bool]) -> Vec<Gene> {
+        let mut genes = vec![Gene::Word(0); bits.len().div_ceil(Self::WORD_BITS)];
+        for (position, &value) in bits.iter().enumerate() {
+            Self::set_bit(&mut genes, position, value);
+        }
+        genes
+    }
+}
+
+// Unordered choice among labelled categories, stored as the index of the chosen label
+#[derive(Debug)]
+pub struct Categorical {
+    pub choices: Vec<String>
+}
+
+impl Categorical {
+    pub fn new<S: Into<String>>(choices: Vec<S>) -> Self {
+        Self::try_new(choices).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Like `new`, but reports a categorical without choices as an error
+    pub fn try_new<S: Into<String>>(choices: Vec<S>) -> Result<Self> {
+        if choices.is_empty() {
+            return Err(Error::InvalidConfiguration("Categorical must have at least one choice"));
+        }
+        Ok(Self { choices: choices.into_iter().map(Into::into).collect() })
+    }
+
+    pub fn generate_value(&self) -> usize {
+        rand::thread_rng().gen_range(0..self.choices.len())
+    }
+
+    /// Label of the category a gene holds
+    pub fn label(&self, gene: Gene) -> &str {
+        &self.choices[gene.as_usize()]
+    }
+
+    /// Index of a label, to look categories up by name in objective functions
+    pub fn index_of(&self, label: &str) -> Option<usize> {
+        self.choices.iter().position(|choice| choice == label)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -180,4 +843,219 @@ mod tests {
     fn test_real_invalid_bounds() {
         Real::new(Some(20.0), Some(10.0));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_permutation_generation() {
+        let permutation = Permutation::new(10);
+        let mut value = permutation.generate_value();
+        assert_eq!(SolutionDataTypes::Permutation(Permutation::new(10)).gene_count(), 10);
+        value.sort_by_key(Gene::as_usize);
+        assert_eq!(value, (0..10).map(Gene::Element).collect::<Vec<Gene>>());
+    }
+
+    #[test]
+    fn test_subset_generation() {
+        let subset = Subset::new(10, 4);
+        for _ in 0..100 {
+            assert!(subset.is_valid(&subset.generate_value()));
+        }
+        assert_eq!(SolutionDataTypes::Subset(Subset::new(10, 4)).gene_count(), 4);
+        assert!(!subset.is_valid(&[1.0, 2.0, 2.0, 3.0].map(Gene::from)));
+        assert!(!subset.is_valid(&[1.0, 2.0, 10.0, 3.0].map(Gene::from)));
+        assert!(!subset.is_valid(&[1.0, 2.0, 3.0].map(Gene::from)));
+        assert_eq!(Subset::new(3, 3).generate_value().len(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "Subset size must not exceed the number of elements")]
+    fn test_subset_invalid_size() {
+        Subset::new(3, 4);
+    }
+
+    #[test]
+    fn test_try_constructors() {
+        let message = |error: Error| error.to_string();
+        assert_eq!(message(Permutation::try_new(0).unwrap_err()), "Permutation size must be greater than zero");
+        assert_eq!(message(Subset::try_new(3, 4).unwrap_err()), "Subset size must not exceed the number of elements");
+        assert_eq!(message(BitString::try_new(0).unwrap_err()), "BitString must have at least one bit");
+        assert_eq!(message(Categorical::try_new(Vec::<String>::new()).unwrap_err()), "Categorical must have at least one choice");
+        assert_eq!(message(Real::new(None, Some(1.0)).try_with_step(0.1).unwrap_err()), "A step requires a lower bound");
+        assert_eq!(
+            message(Integer::new(Some(-1), Some(300)).try_with_data_type(IntegerDataType::U8).unwrap_err()),
+            "Bounds exceed the range of the data type"
+        );
+        assert_eq!(
+            message(Real::new(None, None).try_with_initial_distribution(InitialDistribution::Uniform { lower: 1.0, upper: 0.0 }).unwrap_err()),
+            "Uniform distribution lower must be less than upper"
+        );
+        assert!(Real::new(Some(0.0), Some(1.0)).try_with_step(0.1).is_ok());
+    }
+
+    #[test]
+    fn test_bit_string() {
+        let bit_string = BitString::new(70);
+        assert_eq!(bit_string.words(), 3);
+        assert_eq!(SolutionDataTypes::BitString(BitString::new(70)).gene_count(), 3);
+        for _ in 0..20 {
+            let genes = bit_string.generate_value();
+            assert_eq!(genes.len(), 3);
+            assert!(genes[2].as_u32() < 64);
+            assert_eq!(BitString::from_bits(&bit_string.to_bits(&genes)), genes);
+        }
+        let mut genes = vec![Gene::Word(0); 3];
+        BitString::set_bit(&mut genes, 33, true);
+        assert_eq!(genes, vec![Gene::Word(0), Gene::Word(2), Gene::Word(0)]);
+        assert!(BitString::bit(&genes, 33) && !BitString::bit(&genes, 32));
+        BitString::set_bit(&mut genes, 33, false);
+        assert_eq!(genes, vec![Gene::Word(0); 3]);
+        assert_eq!(BitString::from_data_type(BinaryDataType::U256).words(), 8);
+        assert_eq!(BitString::from_data_type(BinaryDataType::U4).generate_value().len(), 1);
+    }
+
+    #[test]
+    fn test_real_data_types() {
+        assert_eq!(RealDataType::F32.round(0.1), 0.1f32 as f64);
+        assert_eq!(RealDataType::F16.round(0.1), 0.0999755859375);
+        assert_eq!(RealDataType::F16.round(1e6), 65504.0);
+        assert_eq!(RealDataType::F16.round(2049.0), 2048.0);
+        assert_eq!(RealDataType::F64.round(0.1), 0.1);
+        let real = Real::new(None, None).with_data_type(RealDataType::F16);
+        assert_eq!(real.lower(), -65504.0);
+        for _ in 0..100 {
+            let value = real.generate_value().unwrap();
+            assert!(value.abs() <= 65504.0);
+            assert_eq!(RealDataType::F16.round(value), value);
+            assert_eq!(real.native(value), NativeValue::F32(value as f32));
+        }
+        let real = Real::new(Some(0.0), Some(1.0)).with_data_type(RealDataType::F32);
+        assert_eq!(real.representable(2.0), 1.0);
+        assert_eq!(real.native(0.5).to_string(), "0.5");
+    }
+
+    #[test]
+    fn test_integer_data_types() {
+        let integer = Integer::new(None, None).with_data_type(IntegerDataType::U8);
+        assert_eq!((integer.lower(), integer.upper()), (0, 255));
+        for _ in 0..100 {
+            let value = integer.generate_value().unwrap();
+            assert!((0..=255).contains(&value));
+        }
+        assert_eq!(integer.representable(300.4), 255.0);
+        assert_eq!(integer.representable(-7.0), 0.0);
+        assert_eq!(integer.native(200), NativeValue::U8(200));
+        let integer = Integer::new(Some(-10), None).with_data_type(IntegerDataType::I16);
+        assert_eq!(integer.upper(), 32767);
+        assert_eq!(integer.representable(-12.6), -10.0);
+        assert_eq!(Integer::new(None, None).native(-3).to_string(), "-3");
+    }
+
+    #[test]
+    #[should_panic(expected = "Bounds exceed the range of the data type")]
+    fn test_integer_bounds_outside_data_type() {
+        Integer::new(Some(0), Some(1000)).with_data_type(IntegerDataType::U8);
+    }
+
+    #[test]
+    fn test_categorical() {
+        let categorical = Categorical::new(vec!["steel", "aluminium", "titanium"]);
+        for _ in 0..100 {
+            assert!(categorical.generate_value() < 3);
+        }
+        assert_eq!(categorical.label(Gene::Category(2)), "titanium");
+        assert_eq!(categorical.index_of("aluminium"), Some(1));
+        assert_eq!(categorical.index_of("copper"), None);
+        assert_eq!(SolutionDataTypes::Categorical(Categorical::new(vec!["a"])).gene_count(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Categorical must have at least one choice")]
+    fn test_categorical_without_choices() {
+        Categorical::new(Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_discretized_real() {
+        let real = Real::new(Some(1.0), Some(3.2)).with_step(0.5);
+        assert_eq!(real.representable(1.8), 2.0);
+        assert_eq!(real.representable(3.2), 3.0);
+        assert_eq!(real.representable(-4.0), 1.0);
+        let mut seen = [false; 5];
+        for _ in 0..200 {
+            let value = real.generate_value().unwrap();
+            let k = (value - 1.0) / 0.5;
+            assert_eq!(k, k.round());
+            seen[k as usize] = true;
+        }
+        assert_eq!(seen, [true; 5]);
+    }
+
+    #[test]
+    #[should_panic(expected = "A step requires a lower bound")]
+    fn test_step_without_lower_bound() {
+        Real::new(None, Some(1.0)).with_step(0.1);
+    }
+
+    #[test]
+    fn test_unbounded_generation() {
+        let real = Real::new(None, None);
+        let integer = Integer::new(None, None);
+        for _ in 0..100 {
+            assert!(real.generate_value().unwrap().abs() < 10.0);
+            assert!(integer.generate_value().unwrap().abs() < 10);
+        }
+        let half_bounded = Real::new(Some(5.0), None);
+        for _ in 0..100 {
+            let value = half_bounded.generate_value().unwrap();
+            assert!((5.0..15.0).contains(&value));
+        }
+        let stepped = Real::new(Some(0.0), None).with_step(0.25);
+        for _ in 0..100 {
+            let value = stepped.generate_value().unwrap();
+            assert!(value >= 0.0 && value * 4.0 == (value * 4.0).round());
+        }
+    }
+
+    #[test]
+    fn test_initial_distributions() {
+        let normal = Real::new(None, None).with_initial_distribution(InitialDistribution::Normal { mean: 1000.0, standard_deviation: 1.0 });
+        let log_uniform = Real::new(Some(0.0), None).with_initial_distribution(InitialDistribution::LogUniform { lower: 1e-6, upper: 1.0 });
+        let integer = Integer::new(Some(0), Some(100)).with_initial_distribution(InitialDistribution::Normal { mean: 200.0, standard_deviation: 1.0 });
+        let mut below_millesimal = 0;
+        for _ in 0..300 {
+            assert!((990.0..1010.0).contains(&normal.generate_value().unwrap()));
+            let value = log_uniform.generate_value().unwrap();
+            assert!((1e-6..1.0).contains(&value));
+            if value < 1e-3 {
+                below_millesimal += 1;
+            }
+            // Samples are clamped to the bounds
+            assert_eq!(integer.generate_value().unwrap(), 100);
+        }
+        // Half of the orders of magnitude lie below 1e-3
+        assert!(below_millesimal > 100);
+    }
+
+    #[test]
+    #[should_panic(expected = "LogUniform distribution requires 0 < lower < upper")]
+    fn test_invalid_log_uniform() {
+        Real::new(None, None).with_initial_distribution(InitialDistribution::LogUniform { lower: 0.0, upper: 1.0 });
+    }
+
+    #[test]
+    fn test_genes() {
+        let wide = i64::MAX - 1;
+        let integer = SolutionDataTypes::Integer(Integer::new(None, None));
+        // Integers beyond 2^53 survive being made representable
+        assert_eq!(integer.representable(Gene::Integer(wide)), Gene::Integer(wide));
+        assert_eq!(integer.representable(Gene::Real(2.6)), Gene::Integer(3));
+        let bounded = SolutionDataTypes::Integer(Integer::new(Some(0), Some(10)));
+        assert_eq!(bounded.representable(Gene::Integer(wide)), Gene::Integer(10));
+        assert_eq!(SolutionDataTypes::BitBinary(BitBinary::new()).representable(Gene::Real(1.0)), Gene::Bit(true));
+        assert_eq!(SolutionDataTypes::Real(Real::new(Some(0.0), Some(1.0))).representable(Gene::Integer(5)), Gene::Real(1.0));
+        assert_eq!(SolutionDataTypes::Categorical(Categorical::new(vec!["a", "b"])).representable(Gene::Real(1.0)), Gene::Category(1));
+        assert_eq!(Gene::Bit(true).as_f64(), 1.0);
+        assert_eq!(Gene::Real(-2.0).as_usize(), 0);
+        assert_eq!(Gene::Word(7).as_i64(), 7);
+        assert_eq!(Gene::Word(255).to_string(), "0x000000ff");
+    }
+}
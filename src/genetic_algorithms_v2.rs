@@ -1,10 +1,14 @@
 use rayon::prelude::*;
+use std::path::Path;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use crate::core::{Problem, Solution};
 use crate::genetic_operators::mutation::MutationManager;
 use crate::genetic_operators::crossover::CrossoverManager;
 use crate::genetic_operators::selectors::TournamentSelector;
 use crate::dominance::DominanceEnum;
+use crate::archive::{Archive, NondominatedArchive};
+use crate::gatypes::Gene;
+use crate::seeds::{self, SeedError};
 
 pub trait GeneticAlgorithm<'a> {
     fn initialize(&mut self);
@@ -24,7 +28,8 @@ pub struct BaseGeneticAlgorithm<'a> {
     pub dominance: DominanceEnum,
     pub mutation_manager: MutationManager<'a>,
     pub crossover_manager: CrossoverManager<'a>,
-    pub archive: Vec<Solution<'a>>,
+    pub archive: Box<dyn Archive<'a> + 'a>,
+    pub seeds: Vec<Vec<Gene>>,
 }
 
 impl<'a> BaseGeneticAlgorithm<'a> {
@@ -44,20 +49,66 @@ impl<'a> BaseGeneticAlgorithm<'a> {
             dominance: DominanceEnum::ParetoDominance,
             mutation_manager: MutationManager::new(),
             crossover_manager: CrossoverManager::new(),
-            archive: Vec::with_capacity(parent_population_size),
+            archive: Box::new(NondominatedArchive::new()),
+            seeds: Vec::new(),
         }
     }
 
+    /// Switches the dominance relation used by the algorithm and its selector
+    pub fn set_dominance(&mut self, dominance: DominanceEnum) {
+        self.try_set_dominance(dominance).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Like `set_dominance`, but reports parameters that do not fit the problem as an error
+    pub fn try_set_dominance(&mut self, dominance: DominanceEnum) -> crate::error::Result<()> {
+        dominance.validate(self.problem)?;
+        self.selector.set_dominance(dominance.try_to_dominance()?);
+        self.archive.set_dominance(dominance.try_to_dominance()?);
+        self.dominance = dominance;
+        Ok(())
+    }
+
+    /// Replaces the archive, e.g. with a bounded one; it adopts the algorithm's dominance relation
+    pub fn set_archive(&mut self, mut archive: Box<dyn Archive<'a> + 'a>) {
+        archive.set_dominance(self.dominance.to_dominance());
+        self.archive = archive;
+    }
+
+    /// Seeds the initial population with known designs, validated against the problem's variables;
+    /// `initialize` completes them with random solutions up to the parent population size
+    pub fn set_seeds(&mut self, seeds: Vec<Vec<Gene>>) -> Result<(), SeedError> {
+        if seeds.len() > self.parent_population_size {
+            return Err(SeedError::TooManySeeds { seeds: seeds.len(), population: self.parent_population_size });
+        }
+        self.seeds = seeds
+            .iter()
+            .enumerate()
+            .map(|(seed, genome)| seeds::validate_seed(self.problem, seed, genome))
+            .collect::<Result<_, _>>()?;
+        Ok(())
+    }
+
+    /// Seeds the initial population from a CSV or JSON file, see `seeds::read_seeds`
+    pub fn load_seeds(&mut self, path: impl AsRef<Path>) -> Result<(), SeedError> {
+        let seeds = seeds::read_seeds(self.problem, path)?;
+        self.set_seeds(seeds)
+    }
+
     fn archive_solution(&mut self, solution: Solution<'a>) {
         if solution.feasible && solution.evaluated {
-            self.archive.push(solution);
+            self.archive.add(solution);
         }
     }
 }
 
 impl<'a> GeneticAlgorithm<'a> for BaseGeneticAlgorithm<'a> {
     fn initialize(&mut self) {
-        self.parent_population = (0..self.parent_population_size)
+        let seeded = self.seeds.iter().map(|genes| {
+            let mut solution = Solution::new(self.problem);
+            solution.solution = genes.clone();
+            solution
+        });
+        let random: Vec<Solution<'a>> = (self.seeds.len()..self.parent_population_size)
             .into_par_iter()
             .map(|_| {
                 let mut solution = Solution::new(self.problem);
@@ -65,21 +116,27 @@ impl<'a> GeneticAlgorithm<'a> for BaseGeneticAlgorithm<'a> {
                 solution
             })
             .collect();
+        self.parent_population = seeded.chain(random).collect();
         println!("Initialized parent population {:?}", self.parent_population);
 
     }
 
   
     fn evaluate_all(&mut self) {
-        let new_evaluations: usize = self.parent_population
+        let new_evaluations: Vec<usize> = self.parent_population
             .par_iter_mut()
-            .filter(|solution| !solution.evaluated)
-            .map(|solution| {
+            .enumerate()
+            .filter(|(_, solution)| !solution.evaluated)
+            .map(|(i, solution)| {
                 solution.evaluate();
-                1
+                i
             })
-            .sum();
-        self.nfe.fetch_add(new_evaluations, Ordering::SeqCst);
+            .collect();
+        self.nfe.fetch_add(new_evaluations.len(), Ordering::SeqCst);
+        for i in new_evaluations {
+            let solution = self.parent_population[i].clone();
+            self.archive_solution(solution);
+        }
     }
 
     fn add_solution(&mut self, solution: Solution<'a>) {
@@ -89,14 +146,12 @@ impl<'a> GeneticAlgorithm<'a> for BaseGeneticAlgorithm<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::{Problem, Solution};
-    use crate::genetic_operators::mutation::MutationManager;
-    use crate::genetic_operators::crossover::CrossoverManager;
-    use crate::genetic_operators::selectors::TournamentSelector;
+    use crate::core::{ObjectiveFunction, Problem};
     use crate::dominance::DominanceEnum;
-    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::atomic::Ordering;
     use crate::gatypes::SolutionDataTypes;
     use crate::gatypes::{BitBinary, Integer, Real};
+    use crate::expression_tree::{ExpressionTree, Function, Terminal};
     fn setup_problem() -> Problem {
         Problem {
             solution_length: 5,
@@ -111,7 +166,8 @@ mod tests {
                 SolutionDataTypes::Real(Real::new(Some(10.0), Some(1000.0))),
                 SolutionDataTypes::Real(Real::new(Some(10.0), Some(1000.0))),
             ],
-            objective_function: |x| vec![x.iter().sum()],
+            objective_function: ObjectiveFunction::Numeric(|x| vec![x.iter().sum()]),
+            ..Default::default()
         }
     }
     
@@ -136,4 +192,109 @@ mod tests {
         // println!("{:?}", ga.parent_population.len());
         // assert_eq!(ga.parent_population.len(),  num);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_set_dominance_reaches_selector() {
+        let problem = setup_problem();
+        let mut ga = BaseGeneticAlgorithm::new(&problem, 3, 3);
+        ga.set_dominance(DominanceEnum::WeightedSum(vec![1.0]));
+        assert!(matches!(ga.dominance, DominanceEnum::WeightedSum(_)));
+        assert!(format!("{:?}", ga.selector.dominance()).contains("WeightedSumDominance"));
+        assert!(ga.try_set_dominance(DominanceEnum::AttributeDominance(1)).is_err());
+        assert!(ga.try_set_dominance(DominanceEnum::EpsilonDominance(Vec::new())).is_err());
+        assert!(matches!(ga.dominance, DominanceEnum::WeightedSum(_)));
+    }
+
+    #[test]
+    fn test_evaluate_all_archives_nondominated_solutions() {
+        let problem = setup_problem();
+        let mut ga = BaseGeneticAlgorithm::new(&problem, 20, 20);
+        ga.initialize();
+        ga.evaluate_all();
+        // A single objective leaves one best solution in the archive
+        assert_eq!(ga.nfe.load(Ordering::SeqCst), 20);
+        assert_eq!(ga.archive.len(), 1);
+    }
+
+    #[test]
+    fn test_symbolic_regression_trade_off() {
+        // Fit x^2 + x on [-1, 1], minimising both the error and the size of the expression
+        let problem = Problem {
+            solution_length: 1,
+            number_of_objectives: 2,
+            objective_constraint: None,
+            objective_constraint_operands: None,
+            direction: Some(vec![-1, -1]),
+            solution_data_types: vec![SolutionDataTypes::ExpressionTree(
+                ExpressionTree::new(
+                    vec![Function::Add, Function::Subtract, Function::Multiply, Function::Divide],
+                    vec![Terminal::Variable(0), Terminal::RandomConstant(-1.0, 1.0)],
+                    5,
+                )
+                .with_initial_depth(0, 4),
+            )],
+            objective_function: ObjectiveFunction::Typed(|genes| {
+                let error: f64 = (0..=20)
+                    .map(|i| {
+                        let x = i as f64 / 10.0 - 1.0;
+                        (ExpressionTree::evaluate(genes, &[x]) - (x * x + x)).powi(2)
+                    })
+                    .sum();
+                vec![error / 21.0, ExpressionTree::size(genes) as f64]
+            }),
+            ..Default::default()
+        };
+        let mut ga = BaseGeneticAlgorithm::new(&problem, 50, 50);
+        ga.initialize();
+        ga.evaluate_all();
+        let mut front: Vec<(f64, f64)> = ga
+            .archive
+            .solutions()
+            .iter()
+            .map(|solution| (solution.objective_fitness_values[1], solution.objective_fitness_values[0]))
+            .collect();
+        front.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        // Larger expressions only stay in the archive when they are more accurate
+        assert!(!front.is_empty());
+        assert!(front.windows(2).all(|pair| pair[0].0 < pair[1].0 && pair[0].1 > pair[1].1));
+        assert!(ga.parent_population.iter().all(|solution| solution.expression(0).is_some()));
+    }
+
+    #[test]
+    fn test_seeded_initialization() {
+        let problem = setup_problem();
+        let mut ga = BaseGeneticAlgorithm::new(&problem, 4, 4);
+        let seed = vec![Gene::Bit(true), Gene::Integer(500), Gene::Real(20.0), Gene::Real(30.0), Gene::Real(40.0)];
+        ga.set_seeds(vec![seed.clone(), seed.clone()]).unwrap();
+        ga.initialize();
+        assert_eq!(ga.parent_population.len(), 4);
+        assert_eq!(ga.parent_population[0].solution, seed);
+        assert_eq!(ga.parent_population[1].solution, seed);
+        // Invalid seeds are reported and leave the previous seeds in place
+        let mut out_of_bounds = seed.clone();
+        out_of_bounds[2] = Gene::Real(5.0);
+        assert_eq!(ga.set_seeds(vec![seed.clone(), out_of_bounds]).unwrap_err().to_string(), "seed 1: x2 = 5 is outside [10, 1000]");
+        assert!(matches!(ga.set_seeds(vec![seed; 5]), Err(SeedError::TooManySeeds { seeds: 5, population: 4 })));
+        assert_eq!(ga.seeds.len(), 2);
+        let path = std::env::temp_dir().join(format!("rustypus_ga_seeds_{}.json", std::process::id()));
+        std::fs::write(&path, "[[0, 12, 10.5, 11, 12]]").unwrap();
+        let loaded = ga.load_seeds(&path);
+        std::fs::remove_file(&path).unwrap();
+        loaded.unwrap();
+        ga.initialize();
+        assert_eq!(ga.parent_population[0].solution[..2], [Gene::Bit(false), Gene::Integer(12)]);
+    }
+
+    #[test]
+    fn test_set_archive() {
+        let problem = setup_problem();
+        let mut ga = BaseGeneticAlgorithm::new(&problem, 20, 20);
+        ga.set_dominance(DominanceEnum::WeightedSum(vec![1.0]));
+        ga.set_archive(Box::new(crate::archive::CrowdingDistanceArchive::new(5)));
+        assert!(format!("{:?}", ga.archive.dominance()).contains("WeightedSumDominance"));
+        ga.initialize();
+        ga.evaluate_all();
+        assert_eq!(ga.archive.diversity().capacity, Some(5));
+        assert_eq!(ga.archive.len(), 1);
+    }
+}
@@ -1,7 +1,10 @@
 use crate::core::Solution;
-use crate::gatypes::{SolutionDataTypes, Real, Integer, BitBinary};
+use crate::expression_tree::ExpressionTree;
+use crate::gatypes::{BitString, Gene, SolutionDataTypes};
 use crate::math_utils::clip;
 use rand::Rng;
+use rand::rngs::ThreadRng;
+use rand::seq::SliceRandom;
 use std::collections::HashMap;
 
 /// Trait for crossover operations
@@ -66,16 +69,17 @@ impl<'a> Crossover<'a> for SimulatedBinaryCrossover {
         let mut child2 = parent2.clone();
         // println!("Parent1: {:?}", parent1.solution);
         // println!("Parent2: {:?}", parent2.solution);
-        for (i, solution_type) in parent1.problem.solution_data_types.iter().enumerate() {
+        for (genes, solution_type) in parent1.problem.shared_variables(&parent1.solution, &parent2.solution) {
+            let i = genes.start;
             let random_number: f64 = rand::thread_rng().gen();
             match solution_type {
                 SolutionDataTypes::Real(real) => {
                     if random_number < self.probability {
-                        let lower_bound = real.lower_bound.unwrap_or(f64::MIN);
-                        let upper_bound = real.upper_bound.unwrap_or(f64::MAX);
-                        let (c1, c2) = self.sbx_crossover(child1.solution[i], child2.solution[i], lower_bound, upper_bound);
-                        child1.solution[i] = c1;
-                        child2.solution[i] = c2;
+                        let lower_bound = real.lower();
+                        let upper_bound = real.upper();
+                        let (c1, c2) = self.sbx_crossover(child1.solution[i].as_f64(), child2.solution[i].as_f64(), lower_bound, upper_bound);
+                        child1.solution[i] = Gene::Real(real.representable(c1));
+                        child2.solution[i] = Gene::Real(real.representable(c2));
                         }
                     }
             _ => {}
@@ -112,19 +116,16 @@ impl<'a> Crossover<'a> for DifferentialEvolutionCrossover {
         let mut child1 = parent1.clone();
         let mut child2 = parent2.clone();
 
-        for (i, solution_type) in parent1.problem.solution_data_types.iter().enumerate() {
+        for (genes, solution_type) in parent1.problem.shared_variables(&parent1.solution, &parent2.solution) {
+            let i = genes.start;
             if let SolutionDataTypes::Real(real) = solution_type {
                 if rand::thread_rng().gen::<f64>() < self.probability {
-                    let lower = real.lower_bound.unwrap_or(f64::MIN);
-                    let upper = real.upper_bound.unwrap_or(f64::MAX);
-                    let mut c1 = parent1.solution[i] + self.scaling_factor * (parent2.solution[i] - parent1.solution[i]);
-                    let mut c2 = parent2.solution[i] + self.scaling_factor * (parent1.solution[i] - parent2.solution[i]);
+                    let (x1, x2) = (parent1.solution[i].as_f64(), parent2.solution[i].as_f64());
+                    let c1 = x1 + self.scaling_factor * (x2 - x1);
+                    let c2 = x2 + self.scaling_factor * (x1 - x2);
 
-                    c1 = clip(c1, lower, upper);
-                    c2 = clip(c2, lower, upper);
-
-                    child1.solution[i] = c1;
-                    child2.solution[i] = c2;
+                    child1.solution[i] = Gene::Real(real.representable(c1));
+                    child2.solution[i] = Gene::Real(real.representable(c2));
                 }
             }
         }
@@ -153,41 +154,34 @@ impl<'a> Crossover<'a> for ParentCentricCrossover {
         // println!("Parent1: {:?}", parent1);
         // println!("Parent2: {:?}", parent2);
 
-        for (i, solution_type) in parent1.problem.solution_data_types.iter().enumerate() {
+        for (genes, solution_type) in parent1.problem.shared_variables(&parent1.solution, &parent2.solution) {
+            let i = genes.start;
             match solution_type {
                 SolutionDataTypes::Real(real) => {
-                    let lower = real.lower_bound.unwrap_or(f64::MIN);
-                    let upper = real.upper_bound.unwrap_or(f64::MAX);
+                    let (x1, x2) = (parent1.solution[i].as_f64(), parent2.solution[i].as_f64());
                     let mut sum = 0.0;
                     for _ in 0..self.nparents {
-                        sum += parent1.solution[i] + parent2.solution[i];
+                        sum += x1 + x2;
                     }
                     let avg = sum / self.nparents as f64;
-                    let mut c1 = parent1.solution[i] + self.eta * (avg - parent1.solution[i]);
-                    let mut c2 = parent2.solution[i] + self.eta * (avg - parent2.solution[i]);
-
-                    c1 = clip(c1, lower, upper);
-                    c2 = clip(c2, lower, upper);
+                    let c1 = x1 + self.eta * (avg - x1);
+                    let c2 = x2 + self.eta * (avg - x2);
 
-                    child1.solution[i] = c1;
-                    child2.solution[i] = c2;
+                    child1.solution[i] = Gene::Real(real.representable(c1));
+                    child2.solution[i] = Gene::Real(real.representable(c2));
                 }
                 SolutionDataTypes::Integer(integer) => {
-                    let lower = integer.lower_bound.unwrap_or(i64::MIN) as f64;
-                    let upper = integer.upper_bound.unwrap_or(i64::MAX) as f64;
+                    let (x1, x2) = (parent1.solution[i].as_f64(), parent2.solution[i].as_f64());
                     let mut sum = 0.0;
                     for _ in 0..self.nparents {
-                        sum += parent1.solution[i] + parent2.solution[i];
+                        sum += x1 + x2;
                     }
                     let avg = sum / self.nparents as f64;
-                    let mut c1 = parent1.solution[i] + self.eta * (avg - parent1.solution[i]);
-                    let mut c2 = parent2.solution[i] + self.eta * (avg - parent2.solution[i]);
-
-                    c1 = clip(c1.round(), lower, upper);
-                    c2 = clip(c2.round(), lower, upper);
+                    let c1 = x1 + self.eta * (avg - x1);
+                    let c2 = x2 + self.eta * (avg - x2);
 
-                    child1.solution[i] = c1;
-                    child2.solution[i] = c2;
+                    child1.solution[i] = Gene::Integer(integer.representable(c1) as i64);
+                    child2.solution[i] = Gene::Integer(integer.representable(c2) as i64);
                 }
                 _ => {}
             }
@@ -220,23 +214,20 @@ impl<'a> Crossover<'a> for UnimodalDistributionCrossover {
         let mut child2 = parent2.clone();
         // println!("Parent1: {:?}", parent1);
         // println!("Parent2: {:?}", parent2);
-        for (i, solution_type) in parent1.problem.solution_data_types.iter().enumerate() {
+        for (genes, solution_type) in parent1.problem.shared_variables(&parent1.solution, &parent2.solution) {
+            let i = genes.start;
             if let SolutionDataTypes::Real(real) = solution_type {
-                let lower = real.lower_bound.unwrap_or(f64::MIN);
-                let upper = real.upper_bound.unwrap_or(f64::MAX);
+                let (x1, x2) = (parent1.solution[i].as_f64(), parent2.solution[i].as_f64());
                 let mut sum = 0.0;
                 for j in 0..self.nparents {
-                    sum += parent1.solution[i] + parent2.solution[i];
+                    sum += x1 + x2;
                 }
                 let avg = sum / self.nparents as f64;
-                let mut c1 = parent1.solution[i] + self.eta * (avg - parent1.solution[i]);
-                let mut c2 = parent2.solution[i] + self.eta * (avg - parent2.solution[i]);
+                let c1 = x1 + self.eta * (avg - x1);
+                let c2 = x2 + self.eta * (avg - x2);
 
-                c1 = clip(c1, lower, upper);
-                c2 = clip(c2, lower, upper);
-
-                child1.solution[i] = c1;
-                child2.solution[i] = c2;
+                child1.solution[i] = Gene::Real(real.representable(c1));
+                child2.solution[i] = Gene::Real(real.representable(c2));
             }
         }
 
@@ -262,18 +253,15 @@ impl<'a> Crossover<'a> for BlendCrossover {
         let mut child2 = parent2.clone();
         // println!("Parent1: {:?}", parent1);
         // println!("Parent2: {:?}", parent2);
-        for (i, solution_type) in parent1.problem.solution_data_types.iter().enumerate() {
+        for (genes, solution_type) in parent1.problem.shared_variables(&parent1.solution, &parent2.solution) {
+            let i = genes.start;
             if let SolutionDataTypes::Real(real) = solution_type {
-                let lower = real.lower_bound.unwrap_or(f64::MIN);
-                let upper = real.upper_bound.unwrap_or(f64::MAX);
-                let mut c1 = 0.5 * (1.0 + self.alpha) * parent1.solution[i] + 0.5 * (1.0 - self.alpha) * parent2.solution[i];
-                let mut c2 = 0.5 * (1.0 + self.alpha) * parent2.solution[i] + 0.5 * (1.0 - self.alpha) * parent1.solution[i];
-
-                c1 = clip(c1, lower, upper);
-                c2 = clip(c2, lower, upper);
+                let (x1, x2) = (parent1.solution[i].as_f64(), parent2.solution[i].as_f64());
+                let c1 = 0.5 * (1.0 + self.alpha) * x1 + 0.5 * (1.0 - self.alpha) * x2;
+                let c2 = 0.5 * (1.0 + self.alpha) * x2 + 0.5 * (1.0 - self.alpha) * x1;
 
-                child1.solution[i] = c1;
-                child2.solution[i] = c2;
+                child1.solution[i] = Gene::Real(real.representable(c1));
+                child2.solution[i] = Gene::Real(real.representable(c2));
             }
         }
 
@@ -287,7 +275,7 @@ impl<'a> Crossover<'a> for BlendCrossover {
     }
 }
 
-// Uniform Crossover for integer types
+// Uniform Crossover for integer types; categories are swapped as a whole with probability 1/2
 pub struct UniformCrossover {
     pub probability: f64,
 }
@@ -299,29 +287,35 @@ impl<'a> Crossover<'a> for UniformCrossover {
         // println!("Parent1: {:?}", parent1);
         // println!("Parent2: {:?}", parent2);
 
-        for (i, solution_type) in parent1.problem.solution_data_types.iter().enumerate() {
+        for (genes, solution_type) in parent1.problem.shared_variables(&parent1.solution, &parent2.solution) {
+            let i = genes.start;
             match solution_type {
                 SolutionDataTypes::Integer(integer) => {
-                    let lower = integer.lower_bound.unwrap_or(i64::MIN);
-                    let upper = integer.upper_bound.unwrap_or(i64::MAX);
-                    for j in 0..32 {
+                    let lower = integer.lower();
+                    let upper = integer.upper();
+                    let (x1, x2) = (parent1.solution[i].as_i64(), parent2.solution[i].as_i64());
+                    for j in 0..64 {
                         if rand::thread_rng().gen::<f64>() < self.probability {
                             let mask = 1 << j;
-                            let c1 = (parent1.solution[i] as i64 & mask) | (parent2.solution[i] as i64 & !mask);
-                            let c2 = (parent2.solution[i] as i64 & mask) | (parent1.solution[i] as i64 & !mask);
-                            child1.solution[i] = c1.clamp(lower, upper) as f64;
-                            child2.solution[i] = c2.clamp(lower, upper) as f64;
+                            let c1 = (x1 & mask) | (x2 & !mask);
+                            let c2 = (x2 & mask) | (x1 & !mask);
+                            child1.solution[i] = Gene::Integer(c1.clamp(lower, upper));
+                            child2.solution[i] = Gene::Integer(c2.clamp(lower, upper));
                         }
                     }
                 }
                 SolutionDataTypes::BitBinary(_) => {
                     if rand::thread_rng().gen::<f64>() < self.probability {
-                        let c1 = (parent1.solution[i] as i64) ^ (1); // Flip the bit for child1
-                        let c2 = (parent2.solution[i] as i64) ^ (1); // Flip the bit for child2
-                        child1.solution[i] = c1 as f64;
-                        child2.solution[i] = c2 as f64;
+                        child1.solution[i] = Gene::Bit(!parent1.solution[i].as_bool()); // Flip the bit for child1
+                        child2.solution[i] = Gene::Bit(!parent2.solution[i].as_bool()); // Flip the bit for child2
                     }
                 }
+                SolutionDataTypes::Categorical(_)
+                    if rand::thread_rng().gen::<f64>() < self.probability && rand::thread_rng().gen::<bool>() =>
+                {
+                    child1.solution[i] = parent2.solution[i];
+                    child2.solution[i] = parent1.solution[i];
+                }
                 _ => {}
             }
         }
@@ -347,14 +341,15 @@ impl<'a> Crossover<'a> for ArithmeticCrossover {
         let mut child2 = parent2.clone();
         // println!("Parent1: {:?}", parent1);
         // println!("Parent2: {:?}", parent2);
-        for (i, solution_type) in parent1.problem.solution_data_types.iter().enumerate() {
+        for (genes, solution_type) in parent1.problem.shared_variables(&parent1.solution, &parent2.solution) {
+            let i = genes.start;
             if let SolutionDataTypes::Integer(integer) = solution_type {
-                let lower = integer.lower_bound.unwrap_or(i64::MIN);
-                let upper = integer.upper_bound.unwrap_or(i64::MAX);
-                let c1 = (parent1.solution[i] + parent2.solution[i]) / 2.;
-                let c2 = (parent1.solution[i] + parent2.solution[i]) / 2.;
-                child1.solution[i] = clip(c1, lower as f64, upper as f64) as f64;
-                child2.solution[i] = clip(c2, lower as f64, upper as f64) as f64;
+                let lower = integer.lower();
+                let upper = integer.upper();
+                // The midpoint in 128 bits neither overflows nor loses precision
+                let midpoint = ((parent1.solution[i].as_i64() as i128 + parent2.solution[i].as_i64() as i128) / 2) as i64;
+                child1.solution[i] = Gene::Integer(midpoint.clamp(lower, upper));
+                child2.solution[i] = Gene::Integer(midpoint.clamp(lower, upper));
             }
         }
 
@@ -370,11 +365,471 @@ impl<'a> Crossover<'a> for ArithmeticCrossover {
 
 
 
+// Applies a recombination of element indices to every variable of the parents accepted by `applies`
+fn recombine_elements<'a>(
+    parent1: &'a Solution<'a>,
+    parent2: &'a Solution<'a>,
+    probability: f64,
+    applies: fn(&SolutionDataTypes) -> bool,
+    recombine: impl Fn(&[usize], &[usize], &mut ThreadRng) -> (Vec<usize>, Vec<usize>),
+) -> (Solution<'a>, Solution<'a>) {
+    let mut child1 = parent1.clone();
+    let mut child2 = parent2.clone();
+    let mut rng = rand::thread_rng();
+    for (genes, solution_type) in parent1.problem.shared_variables(&parent1.solution, &parent2.solution) {
+        if applies(solution_type) && genes.len() > 1 && rng.gen::<f64>() < probability {
+            let elements1: Vec<usize> = parent1.solution[genes.clone()].iter().map(Gene::as_usize).collect();
+            let elements2: Vec<usize> = parent2.solution[genes.clone()].iter().map(Gene::as_usize).collect();
+            let (c1, c2) = recombine(&elements1, &elements2, &mut rng);
+            for (k, gene) in genes.enumerate() {
+                child1.solution[gene] = Gene::Element(c1[k]);
+                child2.solution[gene] = Gene::Element(c2[k]);
+            }
+        }
+    }
+
+    child1.evaluated = false;
+    child1.feasible = false;
+    child2.evaluated = false;
+    child2.feasible = false;
+    (child1, child2)
+}
+
+fn is_permutation(solution_type: &SolutionDataTypes) -> bool {
+    matches!(solution_type, SolutionDataTypes::Permutation(_))
+}
+
+fn is_subset(solution_type: &SolutionDataTypes) -> bool {
+    matches!(solution_type, SolutionDataTypes::Subset(_))
+}
+
+// Two cut points start < end, delimiting the segment start..=end
+fn cut_points(length: usize, rng: &mut ThreadRng) -> (usize, usize) {
+    let start = rng.gen_range(0..length - 1);
+    (start, rng.gen_range(start + 1..length))
+}
+
+// Position of every element in a permutation
+fn positions(permutation: &[usize]) -> Vec<usize> {
+    let mut positions = vec![0; permutation.len()];
+    for (position, &element) in permutation.iter().enumerate() {
+        positions[element] = position;
+    }
+    positions
+}
+
+// PMX child: the segment of `segment_parent`, the other positions from `other` with conflicts resolved
+// through the mapping defined by the segment
+fn partially_mapped(segment_parent: &[usize], other: &[usize], start: usize, end: usize) -> Vec<usize> {
+    let mut child = segment_parent.to_vec();
+    let mut in_segment = vec![false; segment_parent.len()];
+    segment_parent[start..=end].iter().for_each(|&element| in_segment[element] = true);
+    let segment_positions = positions(segment_parent);
+    for position in (0..start).chain(end + 1..segment_parent.len()) {
+        let mut element = other[position];
+        while in_segment[element] {
+            element = other[segment_positions[element]];
+        }
+        child[position] = element;
+    }
+    child
+}
+
+// OX child: the segment of `segment_parent`, the remaining elements in the order of `other`, both read
+// and written from just after the segment with wrap-around
+fn ordered(segment_parent: &[usize], other: &[usize], start: usize, end: usize) -> Vec<usize> {
+    let length = segment_parent.len();
+    let mut child = segment_parent.to_vec();
+    let mut in_segment = vec![false; length];
+    segment_parent[start..=end].iter().for_each(|&element| in_segment[element] = true);
+    let mut fill = (end + 1..end + 1 + length - (end - start + 1)).map(|position| position % length);
+    for offset in 1..=length {
+        let element = other[(end + offset) % length];
+        if !in_segment[element] {
+            child[fill.next().unwrap()] = element;
+        }
+    }
+    child
+}
+
+// CX children: the positions split into cycles, taken alternately from either parent
+fn cycle(parent1: &[usize], parent2: &[usize]) -> (Vec<usize>, Vec<usize>) {
+    let mut child1 = parent1.to_vec();
+    let mut child2 = parent2.to_vec();
+    let positions1 = positions(parent1);
+    let mut visited = vec![false; parent1.len()];
+    let mut cycle_number = 0;
+    for start in 0..parent1.len() {
+        if visited[start] {
+            continue;
+        }
+        let mut position = start;
+        while !visited[position] {
+            visited[position] = true;
+            if cycle_number % 2 == 1 {
+                child1[position] = parent2[position];
+                child2[position] = parent1[position];
+            }
+            position = positions1[parent2[position]];
+        }
+        cycle_number += 1;
+    }
+    (child1, child2)
+}
+
+// ERX child: starting from `first`, repeatedly moves to the neighbour (in either parent, cyclically) that
+// has the fewest remaining neighbours, or to a random unvisited element at a dead end
+fn edge_recombination(parent1: &[usize], parent2: &[usize], first: usize, rng: &mut ThreadRng) -> Vec<usize> {
+    let length = parent1.len();
+    let mut neighbours: Vec<Vec<usize>> = vec![Vec::with_capacity(4); length];
+    for parent in [parent1, parent2] {
+        for (position, &element) in parent.iter().enumerate() {
+            for neighbour in [parent[(position + length - 1) % length], parent[(position + 1) % length]] {
+                if neighbour != element && !neighbours[element].contains(&neighbour) {
+                    neighbours[element].push(neighbour);
+                }
+            }
+        }
+    }
+    let mut visited = vec![false; length];
+    let mut child = Vec::with_capacity(length);
+    let mut current = first;
+    loop {
+        child.push(current);
+        visited[current] = true;
+        neighbours.iter_mut().for_each(|list| list.retain(|&element| element != current));
+        if child.len() == length {
+            break;
+        }
+        let candidates = &neighbours[current];
+        current = match candidates.iter().map(|&element| neighbours[element].len()).min() {
+            Some(fewest) => {
+                let best: Vec<usize> = candidates.iter().copied().filter(|&element| neighbours[element].len() == fewest).collect();
+                best[rng.gen_range(0..best.len())]
+            }
+            None => {
+                let unvisited: Vec<usize> = (0..length).filter(|&element| !visited[element]).collect();
+                unvisited[rng.gen_range(0..unvisited.len())]
+            }
+        };
+    }
+    child
+}
+
+// SSX children: every element held by only one parent is paired with one held only by the other, and each
+// pair is exchanged with probability 1/2, so both children keep their size and hold no duplicates
+fn subset_exchange(parent1: &[usize], parent2: &[usize], rng: &mut ThreadRng) -> (Vec<usize>, Vec<usize>) {
+    let mut child1 = parent1.to_vec();
+    let mut child2 = parent2.to_vec();
+    let only1: Vec<usize> = (0..parent1.len()).filter(|&j| !parent2.contains(&parent1[j])).collect();
+    let mut only2: Vec<usize> = (0..parent2.len()).filter(|&k| !parent1.contains(&parent2[k])).collect();
+    only2.shuffle(rng);
+    for (&j, &k) in only1.iter().zip(only2.iter()) {
+        if rng.gen::<bool>() {
+            std::mem::swap(&mut child1[j], &mut child2[k]);
+        }
+    }
+    (child1, child2)
+}
+
+/// Partially Mapped Crossover (PMX) for permutations: swaps a random segment and repairs the rest
+/// through the mapping between the two segments
+pub struct PartiallyMappedCrossover {
+    pub probability: f64,
+}
+
+impl PartiallyMappedCrossover {
+    pub fn new(probability: Option<f64>) -> Self {
+        Self { probability: probability.unwrap_or(1.0) }
+    }
+}
+
+impl<'a> Crossover<'a> for PartiallyMappedCrossover {
+    fn crossover(&self, parent1: &'a Solution<'a>, parent2: &'a Solution<'a>) -> (Solution<'a>, Solution<'a>) {
+        recombine_elements(parent1, parent2, self.probability, is_permutation, |p1, p2, rng| {
+            let (start, end) = cut_points(p1.len(), rng);
+            (partially_mapped(p1, p2, start, end), partially_mapped(p2, p1, start, end))
+        })
+    }
+}
+
+/// Order Crossover (OX) for permutations: keeps a random segment of one parent and fills the rest in the
+/// relative order of the other
+pub struct OrderCrossover {
+    pub probability: f64,
+}
+
+impl OrderCrossover {
+    pub fn new(probability: Option<f64>) -> Self {
+        Self { probability: probability.unwrap_or(1.0) }
+    }
+}
+
+impl<'a> Crossover<'a> for OrderCrossover {
+    fn crossover(&self, parent1: &'a Solution<'a>, parent2: &'a Solution<'a>) -> (Solution<'a>, Solution<'a>) {
+        recombine_elements(parent1, parent2, self.probability, is_permutation, |p1, p2, rng| {
+            let (start, end) = cut_points(p1.len(), rng);
+            (ordered(p1, p2, start, end), ordered(p2, p1, start, end))
+        })
+    }
+}
+
+/// Cycle Crossover (CX) for permutations: every element keeps the absolute position it has in one of the parents
+pub struct CycleCrossover {
+    pub probability: f64,
+}
+
+impl CycleCrossover {
+    pub fn new(probability: Option<f64>) -> Self {
+        Self { probability: probability.unwrap_or(1.0) }
+    }
+}
+
+impl<'a> Crossover<'a> for CycleCrossover {
+    fn crossover(&self, parent1: &'a Solution<'a>, parent2: &'a Solution<'a>) -> (Solution<'a>, Solution<'a>) {
+        recombine_elements(parent1, parent2, self.probability, is_permutation, |p1, p2, _| cycle(p1, p2))
+    }
+}
+
+/// Edge Recombination Crossover (ERX) for permutations: builds tours from the adjacencies of both parents,
+/// suited to routing problems where neighbourhood matters more than position
+pub struct EdgeRecombinationCrossover {
+    pub probability: f64,
+}
+
+impl EdgeRecombinationCrossover {
+    pub fn new(probability: Option<f64>) -> Self {
+        Self { probability: probability.unwrap_or(1.0) }
+    }
+}
+
+impl<'a> Crossover<'a> for EdgeRecombinationCrossover {
+    fn crossover(&self, parent1: &'a Solution<'a>, parent2: &'a Solution<'a>) -> (Solution<'a>, Solution<'a>) {
+        recombine_elements(parent1, parent2, self.probability, is_permutation, |p1, p2, rng| {
+            (edge_recombination(p1, p2, p1[0], rng), edge_recombination(p1, p2, p2[0], rng))
+        })
+    }
+}
+
+/// Subset Crossover (SSX): exchanges elements that only one of the parents selected, keeping both subsets valid
+pub struct SubsetCrossover {
+    pub probability: f64,
+}
+
+impl SubsetCrossover {
+    pub fn new(probability: Option<f64>) -> Self {
+        Self { probability: probability.unwrap_or(1.0) }
+    }
+}
+
+impl<'a> Crossover<'a> for SubsetCrossover {
+    fn crossover(&self, parent1: &'a Solution<'a>, parent2: &'a Solution<'a>) -> (Solution<'a>, Solution<'a>) {
+        recombine_elements(parent1, parent2, self.probability, is_subset, subset_exchange)
+    }
+}
+
+// Applies a recombination of the bits of every bit string variable of the parents
+fn recombine_bit_strings<'a>(
+    parent1: &'a Solution<'a>,
+    parent2: &'a Solution<'a>,
+    probability: f64,
+    recombine: impl Fn(&mut [bool], &mut [bool], &mut ThreadRng),
+) -> (Solution<'a>, Solution<'a>) {
+    let mut child1 = parent1.clone();
+    let mut child2 = parent2.clone();
+    let mut rng = rand::thread_rng();
+    for (genes, solution_type) in parent1.problem.shared_variables(&parent1.solution, &parent2.solution) {
+        if let SolutionDataTypes::BitString(bit_string) = solution_type {
+            if rng.gen::<f64>() < probability {
+                let mut bits1 = bit_string.to_bits(&parent1.solution[genes.clone()]);
+                let mut bits2 = bit_string.to_bits(&parent2.solution[genes.clone()]);
+                recombine(&mut bits1, &mut bits2, &mut rng);
+                child1.solution[genes.clone()].copy_from_slice(&BitString::from_bits(&bits1));
+                child2.solution[genes].copy_from_slice(&BitString::from_bits(&bits2));
+            }
+        }
+    }
+
+    child1.evaluated = false;
+    child1.feasible = false;
+    child2.evaluated = false;
+    child2.feasible = false;
+    (child1, child2)
+}
+
+/// Half Uniform Crossover (HUX) for bit strings: exchanges exactly half of the bits in which the parents differ
+pub struct HalfUniformCrossover {
+    pub probability: f64,
+}
+
+impl HalfUniformCrossover {
+    pub fn new(probability: Option<f64>) -> Self {
+        Self { probability: probability.unwrap_or(1.0) }
+    }
+}
+
+impl<'a> Crossover<'a> for HalfUniformCrossover {
+    fn crossover(&self, parent1: &'a Solution<'a>, parent2: &'a Solution<'a>) -> (Solution<'a>, Solution<'a>) {
+        recombine_bit_strings(parent1, parent2, self.probability, |bits1, bits2, rng| {
+            let mut differing: Vec<usize> = (0..bits1.len()).filter(|&position| bits1[position] != bits2[position]).collect();
+            differing.shuffle(rng);
+            for &position in &differing[..differing.len() / 2] {
+                std::mem::swap(&mut bits1[position], &mut bits2[position]);
+            }
+        })
+    }
+}
+
+/// Single Point Crossover for bit strings: exchanges every bit after a random cut point
+pub struct SinglePointCrossover {
+    pub probability: f64,
+}
+
+impl SinglePointCrossover {
+    pub fn new(probability: Option<f64>) -> Self {
+        Self { probability: probability.unwrap_or(1.0) }
+    }
+}
+
+impl<'a> Crossover<'a> for SinglePointCrossover {
+    fn crossover(&self, parent1: &'a Solution<'a>, parent2: &'a Solution<'a>) -> (Solution<'a>, Solution<'a>) {
+        recombine_bit_strings(parent1, parent2, self.probability, |bits1, bits2, rng| {
+            if bits1.len() > 1 {
+                let cut = rng.gen_range(1..bits1.len());
+                bits1[cut..].swap_with_slice(&mut bits2[cut..]);
+            }
+        })
+    }
+}
+
+/// Uniform Crossover for bit strings: exchanges every bit independently with probability 1/2
+pub struct BitUniformCrossover {
+    pub probability: f64,
+}
+
+impl BitUniformCrossover {
+    pub fn new(probability: Option<f64>) -> Self {
+        Self { probability: probability.unwrap_or(1.0) }
+    }
+}
+
+impl<'a> Crossover<'a> for BitUniformCrossover {
+    fn crossover(&self, parent1: &'a Solution<'a>, parent2: &'a Solution<'a>) -> (Solution<'a>, Solution<'a>) {
+        recombine_bit_strings(parent1, parent2, self.probability, |bits1, bits2, rng| {
+            for (bit1, bit2) in bits1.iter_mut().zip(bits2.iter_mut()) {
+                if rng.gen::<bool>() {
+                    std::mem::swap(bit1, bit2);
+                }
+            }
+        })
+    }
+}
+
+/// Cut and Splice Crossover for variable-length genomes: cuts each parent at its own random template
+/// boundary and exchanges the tails, so children may differ in length from both parents while staying
+/// within the length limits of the problem
+pub struct CutAndSpliceCrossover {
+    pub probability: f64,
+}
+
+impl CutAndSpliceCrossover {
+    pub fn new(probability: Option<f64>) -> Self {
+        Self { probability: probability.unwrap_or(1.0) }
+    }
+}
+
+impl<'a> Crossover<'a> for CutAndSpliceCrossover {
+    fn crossover(&self, parent1: &'a Solution<'a>, parent2: &'a Solution<'a>) -> (Solution<'a>, Solution<'a>) {
+        let mut child1 = parent1.clone();
+        let mut child2 = parent2.clone();
+        let mut rng = rand::thread_rng();
+        let variable_length = match &parent1.problem.variable_length {
+            Some(variable_length) => variable_length,
+            None => return (child1, child2),
+        };
+        if rng.gen::<f64>() >= self.probability {
+            return (child1, child2);
+        }
+        let (min_length, max_length) = (variable_length.min_length, variable_length.max_length);
+        let repetitions1 = parent1.problem.repetitions(&parent1.solution);
+        let repetitions2 = parent1.problem.repetitions(&parent2.solution);
+        // Cut points for which both children keep between min_length and max_length repetitions
+        let cuts: Vec<(usize, usize)> = (0..=repetitions1)
+            .flat_map(|cut1| (0..=repetitions2).map(move |cut2| (cut1, cut2)))
+            .filter(|&(cut1, cut2)| {
+                let length1 = cut1 + repetitions2 - cut2;
+                let length2 = cut2 + repetitions1 - cut1;
+                (min_length..=max_length).contains(&length1) && (min_length..=max_length).contains(&length2)
+            })
+            .collect();
+        if let Some(&(cut1, cut2)) = cuts.choose(&mut rng) {
+            let unit = parent1.problem.solution_data_types.len();
+            let (head1, tail1) = parent1.solution.split_at(cut1 * unit);
+            let (head2, tail2) = parent2.solution.split_at(cut2 * unit);
+            child1.solution = [head1, tail2].concat();
+            child2.solution = [head2, tail1].concat();
+        }
+        (child1, child2)
+    }
+}
+
+/// Subtree Crossover for expression trees: exchanges a random subtree of each parent; a child that would
+/// exceed the depth or size limit of the tree keeps its parent's tree instead, which keeps bloat in check
+pub struct SubtreeCrossover {
+    pub probability: f64,
+}
+
+impl SubtreeCrossover {
+    pub fn new(probability: Option<f64>) -> Self {
+        Self { probability: probability.unwrap_or(1.0) }
+    }
+}
+
+impl<'a> Crossover<'a> for SubtreeCrossover {
+    fn crossover(&self, parent1: &'a Solution<'a>, parent2: &'a Solution<'a>) -> (Solution<'a>, Solution<'a>) {
+        let mut child1 = parent1.clone();
+        let mut child2 = parent2.clone();
+        let mut rng = rand::thread_rng();
+        for (genes, solution_type) in parent1.problem.shared_variables(&parent1.solution, &parent2.solution) {
+            if let SolutionDataTypes::ExpressionTree(tree) = solution_type {
+                if rng.gen::<f64>() < self.probability {
+                    let nodes1 = ExpressionTree::nodes(&parent1.solution[genes.clone()]);
+                    let nodes2 = ExpressionTree::nodes(&parent2.solution[genes.clone()]);
+                    let start1 = rng.gen_range(0..nodes1.len());
+                    let start2 = rng.gen_range(0..nodes2.len());
+                    let end1 = ExpressionTree::subtree_end(&nodes1, start1);
+                    let end2 = ExpressionTree::subtree_end(&nodes2, start2);
+                    let offspring1 = [&nodes1[..start1], &nodes2[start2..end2], &nodes1[end1..]].concat();
+                    let offspring2 = [&nodes2[..start2], &nodes1[start1..end1], &nodes2[end2..]].concat();
+                    if tree.fits(&offspring1) {
+                        child1.solution[genes.clone()].copy_from_slice(&tree.to_genes(&offspring1));
+                    }
+                    if tree.fits(&offspring2) {
+                        child2.solution[genes].copy_from_slice(&tree.to_genes(&offspring2));
+                    }
+                }
+            }
+        }
+
+        child1.evaluated = false;
+        child1.feasible = false;
+        child2.evaluated = false;
+        child2.feasible = false;
+        (child1, child2)
+    }
+}
+
 /// CrossoverManager to manage and apply different crossover operations
 pub struct CrossoverManager<'a> {
     default_real_crossover: Box<dyn Crossover<'a>>,
     default_integer_crossover: Box<dyn Crossover<'a>>,
     default_binary_crossover: Box<dyn Crossover<'a>>,
+    default_permutation_crossover: Box<dyn Crossover<'a>>,
+    default_subset_crossover: Box<dyn Crossover<'a>>,
+    default_bit_string_crossover: Box<dyn Crossover<'a>>,
+    default_categorical_crossover: Box<dyn Crossover<'a>>,
+    default_expression_tree_crossover: Box<dyn Crossover<'a>>,
+    default_variable_length_crossover: Box<dyn Crossover<'a>>,
     custom_crossovers: HashMap<usize, Box<dyn Crossover<'a>>>,
 }
 
@@ -385,6 +840,12 @@ impl<'a> CrossoverManager<'a> {
             default_real_crossover: Box::new(SimulatedBinaryCrossover::new(None, None)),
             default_integer_crossover: Box::new(UniformCrossover { probability: 1.0 }),
             default_binary_crossover: Box::new(UniformCrossover { probability: 1.0 }),
+            default_permutation_crossover: Box::new(PartiallyMappedCrossover::new(None)),
+            default_subset_crossover: Box::new(SubsetCrossover::new(None)),
+            default_bit_string_crossover: Box::new(HalfUniformCrossover::new(None)),
+            default_categorical_crossover: Box::new(UniformCrossover { probability: 1.0 }),
+            default_expression_tree_crossover: Box::new(SubtreeCrossover::new(None)),
+            default_variable_length_crossover: Box::new(CutAndSpliceCrossover::new(None)),
             custom_crossovers: HashMap::new(),
         }
     }
@@ -409,29 +870,78 @@ impl<'a> CrossoverManager<'a> {
         self.default_binary_crossover = crossover;
     }
 
+    /// Sets the default crossover for Permutation types
+    pub fn set_default_permutation_crossover(&mut self, crossover: Box<dyn Crossover<'a>>) {
+        self.default_permutation_crossover = crossover;
+    }
+
+    /// Sets the default crossover for Subset types
+    pub fn set_default_subset_crossover(&mut self, crossover: Box<dyn Crossover<'a>>) {
+        self.default_subset_crossover = crossover;
+    }
+
+    /// Sets the default crossover for BitString types
+    pub fn set_default_bit_string_crossover(&mut self, crossover: Box<dyn Crossover<'a>>) {
+        self.default_bit_string_crossover = crossover;
+    }
+
+    /// Sets the default crossover for Categorical types
+    pub fn set_default_categorical_crossover(&mut self, crossover: Box<dyn Crossover<'a>>) {
+        self.default_categorical_crossover = crossover;
+    }
+
+    /// Sets the default crossover for ExpressionTree types
+    pub fn set_default_expression_tree_crossover(&mut self, crossover: Box<dyn Crossover<'a>>) {
+        self.default_expression_tree_crossover = crossover;
+    }
+
+    /// Sets the crossover used for variable-length genomes, which recombines whole template repetitions
+    pub fn set_default_variable_length_crossover(&mut self, crossover: Box<dyn Crossover<'a>>) {
+        self.default_variable_length_crossover = crossover;
+    }
+
     /// Performs crossover on the given parents and returns the children
     pub fn perform_crossover(
         &self,
         parent1: &'a Solution<'a>,
         parent2: &'a Solution<'a>,
     ) -> Vec<Solution<'a>> {
+        if parent1.problem.variable_length.is_some() {
+            let (mut child1, mut child2) = self.default_variable_length_crossover.crossover(parent1, parent2);
+            for child in [&mut child1, &mut child2] {
+                child.evaluated = false;
+                child.feasible = false;
+            }
+            return vec![child1, child2];
+        }
         let mut child1 = parent1.clone();
         let mut child2 = parent2.clone();
     
-        for (i, solution_type) in parent1.problem.solution_data_types.iter().enumerate() {
+        for (i, (genes, solution_type)) in parent1.problem.shared_variables(&parent1.solution, &parent2.solution).enumerate() {
+            // Conditional variables are only recombined when both parents use them
+            if !parent1.problem.is_active(i, &parent1.solution) || !parent1.problem.is_active(i, &parent2.solution) {
+                continue;
+            }
             let crossover = self.custom_crossovers.get(&i).or_else(|| {
                 match solution_type {
                     SolutionDataTypes::Real(_) => Some(&self.default_real_crossover),
                     SolutionDataTypes::Integer(_) => Some(&self.default_integer_crossover),
                     SolutionDataTypes::BitBinary(_) => Some(&self.default_binary_crossover),
-                    _ => None, // Skip unsupported types
+                    SolutionDataTypes::Permutation(_) => Some(&self.default_permutation_crossover),
+                    SolutionDataTypes::Subset(_) => Some(&self.default_subset_crossover),
+                    SolutionDataTypes::BitString(_) => Some(&self.default_bit_string_crossover),
+                    SolutionDataTypes::Categorical(_) => Some(&self.default_categorical_crossover),
+                    SolutionDataTypes::ExpressionTree(_) => Some(&self.default_expression_tree_crossover),
                 }
             });
             
             if let Some(crossover) = crossover {
                 let (c1, c2) = crossover.crossover(parent1, parent2);
-                child1.solution[i] = c1.solution[i];
-                child2.solution[i] = c2.solution[i];
+                // Offspring values are kept representable in the storage type of the variable
+                for gene in genes {
+                    child1.solution[gene] = solution_type.representable(c1.solution[gene]);
+                    child2.solution[gene] = solution_type.representable(c2.solution[gene]);
+                }
             }
         }
     
@@ -449,8 +959,10 @@ impl<'a> CrossoverManager<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::{Problem, Solution};
-    use crate::gatypes::{SolutionDataTypes, Real};
+    use crate::core::{ObjectiveFunction, Problem, Solution};
+    use crate::conditions::{Activation, Condition};
+    use crate::gatypes::{SolutionDataTypes, Real, Integer, BitBinary, Permutation, Subset, Categorical};
+    use crate::expression_tree::{Function, Terminal};
 
     fn setup_problem() -> Problem {
         Problem {
@@ -466,15 +978,21 @@ mod tests {
                 SolutionDataTypes::Real(Real::new(Some(10.0), Some(1000.0))),
                 SolutionDataTypes::Real(Real::new(Some(10.0), Some(1000.0))),
             ],
-            objective_function: |x| vec![x.iter().sum()],
+            objective_function: ObjectiveFunction::Numeric(|x| vec![x.iter().sum()]),
+            ..Default::default()
         }
     }
+
+    // Genes of a problem of single-gene variables, each in the representation of its variable
+    fn typed_genes(problem: &Problem, values: &[f64]) -> Vec<Gene> {
+        values.iter().enumerate().map(|(i, &value)| problem.variable_type(i).representable(Gene::Real(value))).collect()
+    }
     // Create solutions
     fn setup_solutions(problem: &Problem) -> Vec<Solution> {
         vec![
             Solution {
                 problem,
-                solution: vec![1.0, 10.0, 10.0, 10.0, 10.0],
+                solution: typed_genes(problem, &[1.0, 10.0, 10.0, 10.0, 10.0]),
                 objective_fitness_values: Vec::with_capacity(*problem.number_of_objectives()),
                 constraint_values: Vec::with_capacity(*problem.number_of_objectives()),
                 constraint_violation: 0,
@@ -483,7 +1001,7 @@ mod tests {
             },
             Solution {
                 problem,
-                solution: vec![1.0, 20.0, 20.0, 20.0, 20.0],
+                solution: typed_genes(problem, &[1.0, 20.0, 20.0, 20.0, 20.0]),
                 objective_fitness_values: Vec::with_capacity(*problem.number_of_objectives()),
                 constraint_values: Vec::with_capacity(*problem.number_of_objectives()),
                 constraint_violation: 0,
@@ -492,7 +1010,7 @@ mod tests {
             },
             Solution {
                 problem,
-                solution: vec![0.0, 15.0, 15.0, 15.0, 15.0],
+                solution: typed_genes(problem, &[0.0, 15.0, 15.0, 15.0, 15.0]),
                 objective_fitness_values: Vec::with_capacity(*problem.number_of_objectives()),
                 constraint_values: Vec::with_capacity(*problem.number_of_objectives()),
                 constraint_violation: 0,
@@ -536,7 +1054,7 @@ mod tests {
         // define two solutions
         let parent1 = Solution {
             problem: &problem,
-            solution: vec![1.0, 10.0, 10.0, 10.0, 10.0],
+            solution: typed_genes(&problem, &[1.0, 10.0, 10.0, 10.0, 10.0]),
             objective_fitness_values: Vec::with_capacity(*problem.number_of_objectives()),
             constraint_values: Vec::with_capacity(*problem.number_of_objectives()),
             constraint_violation: 0,
@@ -545,7 +1063,7 @@ mod tests {
         };
         let parent2 = Solution {
             problem: &problem,
-            solution: vec![0.0, 20.0, 20.0, 20.0, 20.0],
+            solution: typed_genes(&problem, &[0.0, 20.0, 20.0, 20.0, 20.0]),
             objective_fitness_values: Vec::with_capacity(*problem.number_of_objectives()),
             constraint_values: Vec::with_capacity(*problem.number_of_objectives()),
             constraint_violation: 0,
@@ -567,7 +1085,7 @@ mod tests {
         // define two solutions
         let parent1 = Solution {
             problem: &problem,
-            solution: vec![1.0, 10.0, 20.0, 30.0, 40.0],
+            solution: typed_genes(&problem, &[1.0, 10.0, 20.0, 30.0, 40.0]),
             objective_fitness_values: Vec::with_capacity(*problem.number_of_objectives()),
             constraint_values: Vec::with_capacity(*problem.number_of_objectives()),
             constraint_violation: 0,
@@ -576,7 +1094,7 @@ mod tests {
         };
         let parent2 = Solution {
             problem: &problem,
-            solution: vec![0.0, 60.0, 70.5, 80.2, 90.3],
+            solution: typed_genes(&problem, &[0.0, 60.0, 70.5, 80.2, 90.3]),
             objective_fitness_values: Vec::with_capacity(*problem.number_of_objectives()),
             constraint_values: Vec::with_capacity(*problem.number_of_objectives()),
             constraint_violation: 0,
@@ -602,7 +1120,7 @@ mod tests {
         // define two solutions
         let parent1 = Solution {
             problem: &problem,
-            solution: vec![1.0, 10.0, 20.0, 30.0, 40.0],
+            solution: typed_genes(&problem, &[1.0, 10.0, 20.0, 30.0, 40.0]),
             objective_fitness_values: Vec::with_capacity(*problem.number_of_objectives()),
             constraint_values: Vec::with_capacity(*problem.number_of_objectives()),
             constraint_violation: 0,
@@ -611,7 +1129,7 @@ mod tests {
         };
         let parent2 = Solution {
             problem: &problem,
-            solution: vec![0.0, 60.0, 70.5, 80.2, 90.3],
+            solution: typed_genes(&problem, &[0.0, 60.0, 70.5, 80.2, 90.3]),
             objective_fitness_values: Vec::with_capacity(*problem.number_of_objectives()),
             constraint_values: Vec::with_capacity(*problem.number_of_objectives()),
             constraint_violation: 0,
@@ -636,7 +1154,7 @@ mod tests {
         // define two solutions
         let parent1 = Solution {
             problem: &problem,
-            solution: vec![1.0, 10.0, 20.0, 30.0, 40.0],
+            solution: typed_genes(&problem, &[1.0, 10.0, 20.0, 30.0, 40.0]),
             objective_fitness_values: Vec::with_capacity(*problem.number_of_objectives()),
             constraint_values: Vec::with_capacity(*problem.number_of_objectives()),
             constraint_violation: 0,
@@ -645,7 +1163,7 @@ mod tests {
         };
         let parent2 = Solution {
             problem: &problem,
-            solution: vec![0.0, 60.0, 70.5, 80.2, 90.3],
+            solution: typed_genes(&problem, &[0.0, 60.0, 70.5, 80.2, 90.3]),
             objective_fitness_values: Vec::with_capacity(*problem.number_of_objectives()),
             constraint_values: Vec::with_capacity(*problem.number_of_objectives()),
             constraint_violation: 0,
@@ -665,6 +1183,385 @@ mod tests {
         assert_ne!(children[1].solution, parent2.solution);
     }// Define a problem with both `Integer` and `BitBinary` types
 
+    fn assert_permutation(genes: &[Gene]) {
+        let mut sorted = genes.to_vec();
+        sorted.sort_by_key(Gene::as_usize);
+        assert_eq!(sorted, (0..genes.len()).map(Gene::Element).collect::<Vec<Gene>>());
+    }
+
+    fn setup_permutation_problem() -> Problem {
+        Problem {
+            solution_length: 2,
+            number_of_objectives: 1,
+            objective_constraint: None,
+            objective_constraint_operands: None,
+            direction: Some(vec![-1]),
+            solution_data_types: vec![
+                SolutionDataTypes::Real(Real::new(Some(0.0), Some(1.0))),
+                SolutionDataTypes::Permutation(Permutation::new(8)),
+            ],
+            objective_function: ObjectiveFunction::Numeric(|x| vec![x.iter().sum()]),
+            ..Default::default()
+        }
+    }
+
+    fn permutation_solution(problem: &Problem, elements: [usize; 8]) -> Solution<'_> {
+        let mut solution = vec![Gene::Real(0.5)];
+        solution.extend(elements.map(Gene::Element));
+        Solution {
+            problem,
+            solution,
+            objective_fitness_values: Vec::new(),
+            constraint_values: Vec::new(),
+            constraint_violation: 0,
+            feasible: false,
+            evaluated: false,
+        }
+    }
+
+    #[test]
+    fn test_permutation_recombination_examples() {
+        let p1 = [0, 1, 2, 3, 4, 5, 6, 7];
+        let p2 = [2, 7, 5, 1, 6, 4, 0, 3];
+        assert_eq!(partially_mapped(&p1, &p2, 3, 5), vec![2, 7, 6, 3, 4, 5, 0, 1]);
+        assert_eq!(ordered(&p1, &p2, 3, 5), vec![7, 1, 6, 3, 4, 5, 0, 2]);
+        let (c1, c2) = cycle(&p1, &[1, 2, 0, 4, 3, 6, 7, 5]);
+        assert_eq!(c1, vec![0, 1, 2, 4, 3, 5, 6, 7]);
+        assert_eq!(c2, vec![1, 2, 0, 3, 4, 6, 7, 5]);
+        // With identical parents only the parent's own edges exist, walked in either direction
+        let child = edge_recombination(&p1, &p1, 0, &mut rand::thread_rng());
+        assert!(child == p1.to_vec() || child == vec![0, 7, 6, 5, 4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn test_permutation_crossovers() {
+        let problem = setup_permutation_problem();
+        let parent1 = permutation_solution(&problem, [0, 1, 2, 3, 4, 5, 6, 7]);
+        let parent2 = permutation_solution(&problem, [3, 7, 0, 5, 1, 6, 2, 4]);
+        let crossovers: Vec<Box<dyn Crossover>> = vec![
+            Box::new(PartiallyMappedCrossover::new(None)),
+            Box::new(OrderCrossover::new(None)),
+            Box::new(CycleCrossover::new(None)),
+            Box::new(EdgeRecombinationCrossover::new(None)),
+        ];
+        for crossover in crossovers.iter() {
+            for _ in 0..50 {
+                let (child1, child2) = crossover.crossover(&parent1, &parent2);
+                assert_permutation(child1.variable(1));
+                assert_permutation(child2.variable(1));
+                assert_eq!(child1.solution[0], Gene::Real(0.5));
+            }
+        }
+    }
+
+    #[test]
+    fn test_crossover_manager_with_permutation() {
+        let problem = setup_permutation_problem();
+        let parent1 = permutation_solution(&problem, [0, 1, 2, 3, 4, 5, 6, 7]);
+        let parent2 = permutation_solution(&problem, [7, 6, 5, 4, 3, 2, 1, 0]);
+        let mut cm = CrossoverManager::new();
+        cm.set_default_permutation_crossover(Box::new(OrderCrossover::new(None)));
+        for child in cm.perform_crossover(&parent1, &parent2) {
+            assert_eq!(child.solution.len(), 9);
+            assert_permutation(child.variable(1));
+        }
+    }
+
+    #[test]
+    fn test_subset_crossover() {
+        let problem = Problem {
+            solution_length: 1,
+            number_of_objectives: 1,
+            objective_constraint: None,
+            objective_constraint_operands: None,
+            direction: Some(vec![-1]),
+            solution_data_types: vec![SolutionDataTypes::Subset(Subset::new(10, 4))],
+            objective_function: ObjectiveFunction::Numeric(|x| vec![x.iter().sum()]),
+            ..Default::default()
+        };
+        let subset = Subset::new(10, 4);
+        let solution = |elements: [usize; 4]| Solution {
+            problem: &problem,
+            solution: elements.map(Gene::Element).to_vec(),
+            objective_fitness_values: Vec::new(),
+            constraint_values: Vec::new(),
+            constraint_violation: 0,
+            feasible: false,
+            evaluated: false,
+        };
+        let parent1 = solution([0, 1, 2, 3]);
+        let parent2 = solution([2, 5, 3, 9]);
+        let mut exchanged = false;
+        for _ in 0..50 {
+            for child in CrossoverManager::new().perform_crossover(&parent1, &parent2) {
+                assert!(subset.is_valid(&child.solution));
+                // Shared elements stay in both children
+                assert!(child.solution.contains(&Gene::Element(2)) && child.solution.contains(&Gene::Element(3)));
+                exchanged |= child.solution != parent1.solution && child.solution != parent2.solution;
+            }
+        }
+        assert!(exchanged);
+    }
+
+    #[test]
+    fn test_bit_string_crossovers() {
+        let problem = Problem {
+            solution_length: 1,
+            number_of_objectives: 1,
+            objective_constraint: None,
+            objective_constraint_operands: None,
+            direction: Some(vec![-1]),
+            solution_data_types: vec![SolutionDataTypes::BitString(BitString::new(100))],
+            objective_function: ObjectiveFunction::Numeric(|x| vec![x.iter().sum()]),
+            ..Default::default()
+        };
+        let solution = |value: bool| Solution {
+            problem: &problem,
+            solution: BitString::from_bits(&[value; 100]),
+            objective_fitness_values: Vec::new(),
+            constraint_values: Vec::new(),
+            constraint_violation: 0,
+            feasible: false,
+            evaluated: false,
+        };
+        let (zeros, ones) = (solution(false), solution(true));
+        let bit_string = BitString::new(100);
+        let count = |child: &Solution| bit_string.to_bits(&child.solution).iter().filter(|&&bit| bit).count();
+        let crossovers: Vec<Box<dyn Crossover>> = vec![
+            Box::new(HalfUniformCrossover::new(None)),
+            Box::new(SinglePointCrossover::new(None)),
+            Box::new(BitUniformCrossover::new(None)),
+        ];
+        for crossover in crossovers.iter() {
+            let (child1, child2) = crossover.crossover(&zeros, &ones);
+            // Bits are exchanged, never created or lost
+            assert_eq!(count(&child1) + count(&child2), 100);
+            assert!(child1.solution[3].as_u32() < 16 && child2.solution[3].as_u32() < 16);
+        }
+        let (child1, _) = HalfUniformCrossover::new(None).crossover(&zeros, &ones);
+        assert_eq!(count(&child1), 50);
+        let (child1, _) = SinglePointCrossover::new(None).crossover(&zeros, &ones);
+        let bits = bit_string.to_bits(&child1.solution);
+        assert!(bits.windows(2).all(|pair| pair[0] <= pair[1]));
+        for child in CrossoverManager::new().perform_crossover(&zeros, &ones) {
+            assert_eq!(count(&child), 50);
+        }
+    }
+
+    #[test]
+    fn test_categorical_uniform_crossover() {
+        let problem = Problem {
+            solution_length: 3,
+            number_of_objectives: 1,
+            objective_constraint: None,
+            objective_constraint_operands: None,
+            direction: Some(vec![-1]),
+            solution_data_types: (0..3).map(|_| SolutionDataTypes::Categorical(Categorical::new(vec!["a", "b", "c", "d"]))).collect(),
+            objective_function: ObjectiveFunction::Numeric(|x| vec![x.iter().sum()]),
+            ..Default::default()
+        };
+        let solution = |genes: Vec<f64>| Solution {
+            problem: &problem,
+            solution: typed_genes(&problem, &genes),
+            objective_fitness_values: Vec::new(),
+            constraint_values: Vec::new(),
+            constraint_violation: 0,
+            feasible: false,
+            evaluated: false,
+        };
+        let parent1 = solution(vec![0.0, 0.0, 0.0]);
+        let parent2 = solution(vec![3.0, 3.0, 3.0]);
+        let mut swapped = false;
+        for _ in 0..20 {
+            let children = CrossoverManager::new().perform_crossover(&parent1, &parent2);
+            // Categories are only exchanged, never blended
+            for position in 0..3 {
+                assert!(children[0].solution[position].as_usize() + children[1].solution[position].as_usize() == 3);
+                assert!(matches!(children[0].solution[position], Gene::Category(0) | Gene::Category(3)));
+            }
+            swapped |= children[0].solution != parent1.solution;
+        }
+        assert!(swapped);
+    }
 
+    #[test]
+    fn test_subtree_crossover() {
+        let problem = Problem {
+            solution_length: 1,
+            number_of_objectives: 1,
+            objective_constraint: None,
+            objective_constraint_operands: None,
+            direction: Some(vec![-1]),
+            solution_data_types: vec![SolutionDataTypes::ExpressionTree(
+                ExpressionTree::new(
+                    vec![Function::Add, Function::Multiply, Function::Sin],
+                    vec![Terminal::Variable(0), Terminal::RandomConstant(-1.0, 1.0)],
+                    4,
+                )
+                .with_max_size(20),
+            )],
+            objective_function: ObjectiveFunction::Numeric(|x| vec![x.len() as f64]),
+            ..Default::default()
+        };
+        let tree = match &problem.solution_data_types[0] {
+            SolutionDataTypes::ExpressionTree(tree) => tree,
+            _ => unreachable!(),
+        };
+        let solution = || Solution {
+            problem: &problem,
+            solution: problem.generate_solution(),
+            objective_fitness_values: Vec::new(),
+            constraint_values: Vec::new(),
+            constraint_violation: 0,
+            feasible: false,
+            evaluated: false,
+        };
+        let mut exchanged = false;
+        for _ in 0..50 {
+            let (parent1, parent2) = (solution(), solution());
+            for child in CrossoverManager::new().perform_crossover(&parent1, &parent2) {
+                // Offspring beyond the depth or size limit are never produced
+                let nodes = ExpressionTree::nodes(&child.solution);
+                assert!(tree.fits(&nodes));
+                assert_eq!(child.solution.len(), 20);
+                exchanged |= child.solution != parent1.solution && child.solution != parent2.solution;
+            }
+        }
+        assert!(exchanged);
+        let parent = solution();
+        let (child1, child2) = SubtreeCrossover::new(Some(0.0)).crossover(&parent, &parent);
+        assert_eq!(child1.solution, parent.solution);
+        assert_eq!(child2.solution, parent.solution);
+    }
 
+    #[test]
+    fn test_crossovers_snap_to_real_grid() {
+        let problem = Problem {
+            solution_length: 2,
+            number_of_objectives: 1,
+            objective_constraint: None,
+            objective_constraint_operands: None,
+            direction: Some(vec![-1]),
+            solution_data_types: (0..2).map(|_| SolutionDataTypes::Real(Real::new(Some(0.0), Some(10.0)).with_step(0.5))).collect(),
+            objective_function: ObjectiveFunction::Numeric(|x| vec![x.iter().sum()]),
+            ..Default::default()
+        };
+        let solution = |genes: Vec<f64>| Solution {
+            problem: &problem,
+            solution: typed_genes(&problem, &genes),
+            objective_fitness_values: Vec::new(),
+            constraint_values: Vec::new(),
+            constraint_violation: 0,
+            feasible: false,
+            evaluated: false,
+        };
+        let parent1 = solution(vec![1.0, 2.5]);
+        let parent2 = solution(vec![8.5, 6.0]);
+        let crossovers: Vec<Box<dyn Crossover>> = vec![
+            Box::new(SimulatedBinaryCrossover::new(None, Some(2.0))),
+            Box::new(DifferentialEvolutionCrossover::new(Some(1.0), Some(0.3))),
+            Box::new(ParentCentricCrossover { nparents: 3, noffspring: 2, eta: 0.3, zeta: 0.1 }),
+            Box::new(UnimodalDistributionCrossover { probability: 1.0, distribution_index: 2.0, nparents: 3, zeta: 0.1, eta: 0.3 }),
+            Box::new(BlendCrossover { probability: 1.0, alpha: 0.3 }),
+        ];
+        for crossover in crossovers.iter() {
+            for _ in 0..20 {
+                let (child1, child2) = crossover.crossover(&parent1, &parent2);
+                for value in child1.solution.iter().chain(child2.solution.iter()).map(Gene::as_f64) {
+                    assert!((0.0..=10.0).contains(&value));
+                    assert_eq!(value * 2.0, (value * 2.0).round());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_crossover_skips_inactive_variables() {
+        let problem = Problem {
+            solution_length: 2,
+            number_of_objectives: 1,
+            objective_constraint: None,
+            objective_constraint_operands: None,
+            direction: Some(vec![-1]),
+            solution_data_types: vec![
+                SolutionDataTypes::Integer(Integer::new(Some(0), Some(3))),
+                SolutionDataTypes::Real(Real::new(Some(0.0), Some(100.0))),
+            ],
+            conditions: vec![Condition::new(1, 0, Activation::AtLeast(1.0))],
+            objective_function: ObjectiveFunction::Numeric(|x| vec![x.iter().sum()]),
+            ..Default::default()
+        };
+        let solution = |genes: Vec<f64>| Solution {
+            problem: &problem,
+            solution: typed_genes(&problem, &genes),
+            objective_fitness_values: Vec::new(),
+            constraint_values: Vec::new(),
+            constraint_violation: 0,
+            feasible: false,
+            evaluated: false,
+        };
+        let parent1 = solution(vec![0.0, 10.0]);
+        let parent2 = solution(vec![2.0, 90.0]);
+        let mut cm = CrossoverManager::new();
+        cm.set_default_integer_crossover(Box::new(ArithmeticCrossover { probability: 1.0 }));
+        for _ in 0..20 {
+            let children = cm.perform_crossover(&parent1, &parent2);
+            assert_eq!(children[0].solution[1], Gene::Real(10.0));
+            assert_eq!(children[1].solution[1], Gene::Real(90.0));
+        }
+    }
+
+    #[test]
+    fn test_cut_and_splice_crossover() {
+        let problem = Problem::new(2, 1, None, None, None, vec![
+            SolutionDataTypes::Real(Real::new(Some(0.0), Some(10.0))),
+            SolutionDataTypes::Integer(Integer::new(Some(0), Some(10))),
+        ], |x| vec![x.len() as f64]).with_variable_length(1, 3);
+        let solution = |genes: Vec<f64>| Solution {
+            problem: &problem,
+            solution: typed_genes(&problem, &genes),
+            objective_fitness_values: Vec::new(),
+            constraint_values: Vec::new(),
+            constraint_violation: 0,
+            feasible: false,
+            evaluated: false,
+        };
+        let parent1 = solution(vec![1.0, 1.0]);
+        let parent2 = solution(vec![2.0, 2.0, 3.0, 3.0, 4.0, 4.0]);
+        let mut lengths = std::collections::HashSet::new();
+        for _ in 0..50 {
+            for child in CrossoverManager::new().perform_crossover(&parent1, &parent2) {
+                let length = child.solution.len();
+                assert!(length.is_multiple_of(2) && (2..=6).contains(&length));
+                // Template repetitions are exchanged whole
+                assert!(child.solution.chunks(2).all(|unit| matches!(unit, [Gene::Real(real), Gene::Integer(integer)] if *real == *integer as f64)));
+                lengths.insert(length);
+            }
+        }
+        assert!(lengths.len() > 1);
+    }
+
+    #[test]
+    fn test_uniform_crossover_keeps_wide_integers() {
+        let problem = Problem::new(1, 1, None, None, None, vec![SolutionDataTypes::Integer(Integer::new(None, None))], |x| vec![x[0]]);
+        let solution = |value: i64| Solution {
+            problem: &problem,
+            solution: vec![Gene::Integer(value)],
+            objective_fitness_values: Vec::new(),
+            constraint_values: Vec::new(),
+            constraint_violation: 0,
+            feasible: false,
+            evaluated: false,
+        };
+        let (value1, value2) = ((1 << 60) + 3, i64::MAX - 12);
+        let parent1 = solution(value1);
+        let parent2 = solution(value2);
+        for _ in 0..20 {
+            let (child1, child2) = UniformCrossover { probability: 0.5 }.crossover(&parent1, &parent2);
+            let (bits1, bits2) = (child1.solution[0].as_i64(), child2.solution[0].as_i64());
+            // Every bit, including those above 2^53, comes from one of the parents
+            assert_eq!(bits1 & bits2, value1 & value2);
+            assert_eq!(bits1 | bits2, value1 | value2);
+        }
+    }
 }
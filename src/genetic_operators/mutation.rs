@@ -1,20 +1,33 @@
-use crate::core::{Solution, Problem};
-use crate::gatypes::{SolutionDataTypes, Real};
-use rand::rngs::ThreadRng;
+use crate::core::Solution;
+use crate::expression_tree::ExpressionTree;
+use crate::gatypes::{BitString, Gene, SolutionDataTypes};
 use std::collections::HashMap;
 use std::sync::Arc;
 use rand::Rng;
+use rand::seq::SliceRandom;
 
-/// Trait for mutation operations
-/// Trait for mutation operations
+/// Trait for mutation operations. `index` is the variable index; scalar mutations implement `mutate`,
+/// mutations of variables spanning several genes implement `mutate_variable`.
 pub trait Mutation<'a>: Send + Sync {
-    fn mutate(&self, parent: &'a Solution<'a>, index: usize) -> f64;
+    /// Mutated value of the single gene of the variable at `index`
+    fn mutate(&self, parent: &'a Solution<'a>, index: usize) -> Gene {
+        self.mutate_variable(parent, index)[0]
+    }
+
+    /// Mutated genes of the variable at `index`; scalar mutations leave multi-gene variables unchanged
+    fn mutate_variable(&self, parent: &'a Solution<'a>, index: usize) -> Vec<Gene> {
+        if parent.problem.variable_type(index).gene_count() == 1 {
+            vec![self.mutate(parent, index)]
+        } else {
+            parent.variable(index).to_vec()
+        }
+    }
 }
 /// MutationManager to manage and apply mutations
 pub struct MutationManager<'a> {
     default_mutations: HashMap<&'static str, Arc<dyn Mutation<'a>>>,
     custom_mutations: HashMap<usize, Arc<dyn Mutation<'a>>>,
-
+    length_mutations: Vec<Arc<dyn LengthMutation<'a>>>,
 }
 
 impl<'a> MutationManager<'a> {
@@ -24,13 +37,28 @@ impl<'a> MutationManager<'a> {
         default_mutations.insert("BitBinary", Arc::new(BitFlipMutation::default()));
         default_mutations.insert("Real", Arc::new(UniformMutation::default()));
         default_mutations.insert("Integer", Arc::new(UniformMutation::default()));
+        default_mutations.insert("Permutation", Arc::new(SwapMutation::default()));
+        default_mutations.insert("Subset", Arc::new(ReplaceMutation::default()));
+        default_mutations.insert("BitString", Arc::new(BitStringFlipMutation::default()));
+        default_mutations.insert("Categorical", Arc::new(RandomResetMutation::default()));
+        default_mutations.insert("ExpressionTree", Arc::new(SubtreeMutation::default()));
 
         Self {
             default_mutations,
             custom_mutations: HashMap::new(),
+            length_mutations: vec![
+                Arc::new(InsertGeneMutation::new(Some(0.1))),
+                Arc::new(DeleteGeneMutation::new(Some(0.1))),
+                Arc::new(DuplicateGeneMutation::new(Some(0.1))),
+            ],
         }
     }
 
+    /// Sets the mutations applied in turn to variable-length genomes after the per-variable mutations
+    pub fn set_length_mutations(&mut self, length_mutations: Vec<Arc<dyn LengthMutation<'a>>>) {
+        self.length_mutations = length_mutations;
+    }
+
     /// Sets a custom mutation for a specific index
     pub fn set_custom_mutation(&mut self, index: usize, mutation: Arc<dyn Mutation<'a>>) {
         self.custom_mutations.insert(index, mutation);
@@ -61,7 +89,11 @@ impl<'a> MutationManager<'a> {
     // }
     pub fn mutate(&self, parent: &'a Solution<'a>) -> Solution<'a> {
         let mut child = parent.clone();
-        for (i, solution_type) in parent.problem.solution_data_types.iter().enumerate() {
+        for (i, (genes, solution_type)) in parent.problem.variables_in(&parent.solution).enumerate() {
+            // Inactive conditional variables are left untouched
+            if !parent.problem.is_active(i, &parent.solution) {
+                continue;
+            }
             let mutation = self
                 .custom_mutations
                 .get(&i)
@@ -70,11 +102,23 @@ impl<'a> MutationManager<'a> {
                     SolutionDataTypes::BitBinary(_) => self.default_mutations.get("BitBinary").cloned(),
                     SolutionDataTypes::Real(_) => self.default_mutations.get("Real").cloned(),
                     SolutionDataTypes::Integer(_) => self.default_mutations.get("Integer").cloned(),
-                    _ => None,
+                    SolutionDataTypes::Permutation(_) => self.default_mutations.get("Permutation").cloned(),
+                    SolutionDataTypes::Subset(_) => self.default_mutations.get("Subset").cloned(),
+                    SolutionDataTypes::BitString(_) => self.default_mutations.get("BitString").cloned(),
+                    SolutionDataTypes::Categorical(_) => self.default_mutations.get("Categorical").cloned(),
+                    SolutionDataTypes::ExpressionTree(_) => self.default_mutations.get("ExpressionTree").cloned(),
                 });
 
             if let Some(mutation) = mutation {
-                child.solution[i] = mutation.mutate(parent, i);
+                // Mutated values are kept representable in the storage type of the variable
+                for (gene, value) in genes.zip(mutation.mutate_variable(parent, i)) {
+                    child.solution[gene] = solution_type.representable(value);
+                }
+            }
+        }
+        if parent.problem.variable_length.is_some() {
+            for length_mutation in &self.length_mutations {
+                child = length_mutation.mutate_length(&child);
             }
         }
         child.feasible = false;
@@ -93,16 +137,16 @@ pub trait SolutionTypeBounds {
 impl SolutionTypeBounds for SolutionDataTypes {
     fn get_lower_bound(&self) -> Option<f64> {
         match self {
-            SolutionDataTypes::Real(real) => real.lower_bound,
-            SolutionDataTypes::Integer(integer) => integer.lower_bound.map(|v| v as f64),
+            SolutionDataTypes::Real(real) => Some(real.lower()),
+            SolutionDataTypes::Integer(integer) => Some(integer.lower() as f64),
             _ => None,
         }
     }
 
     fn get_upper_bound(&self) -> Option<f64> {
         match self {
-            SolutionDataTypes::Real(real) => real.upper_bound,
-            SolutionDataTypes::Integer(integer) => integer.upper_bound.map(|v| v as f64),
+            SolutionDataTypes::Real(real) => Some(real.upper()),
+            SolutionDataTypes::Integer(integer) => Some(integer.upper() as f64),
             _ => None,
         }
     }
@@ -120,12 +164,13 @@ impl Default for BitFlipMutation {
 }
 
 impl<'a> Mutation<'a> for BitFlipMutation {
-    fn mutate(&self, parent: &'a Solution<'a>, index: usize) -> f64 {
+    fn mutate(&self, parent: &'a Solution<'a>, index: usize) -> Gene {
         let mut rng = rand::thread_rng();
+        let gene = parent.problem.gene_range(index).start;
         if rng.gen::<f64>() < self.probability {
-            1.0 - parent.solution[index]
+            Gene::Bit(!parent.solution[gene].as_bool())
         } else {
-            parent.solution[index]
+            parent.solution[gene]
         }
     }
 }
@@ -142,34 +187,42 @@ impl Default for UniformMutation {
 }
 
 impl<'a> Mutation<'a> for UniformMutation {
-    fn mutate(&self, parent: &'a Solution<'a>, index: usize) -> f64 {
+    fn mutate(&self, parent: &'a Solution<'a>, index: usize) -> Gene {
         let mut rng = rand::thread_rng();
-        match &parent.problem.solution_data_types[index] {
-            SolutionDataTypes::Integer(integer) => {
-                let lower_bound = integer.lower_bound.unwrap_or(i64::MIN) as f64;
-                let upper_bound = integer.upper_bound.unwrap_or(i64::MAX) as f64;
-
-                if rng.gen::<f64>() < self.probability {
-                    rng.gen_range(lower_bound..=upper_bound).round() // Ensures result is an integer
-                } else {
-                    parent.solution[index]
+        let gene = parent.problem.gene_range(index).start;
+        let x = parent.solution[gene];
+        match parent.problem.variable_type(index) {
+            SolutionDataTypes::Integer(integer) if rng.gen::<f64>() < self.probability => {
+                match (integer.lower_limit(), integer.upper_limit()) {
+                    // Drawn as an integer so that wide bounds keep every value reachable
+                    (Some(lower), Some(upper)) => Gene::Integer(rng.gen_range(lower..=upper)),
+                    (lower, upper) => {
+                        let limits = (lower.map(|l| l as f64), upper.map(|u| u as f64));
+                        Gene::Integer(integer.representable(uniform_within(x.as_f64(), limits, &mut rng)) as i64)
+                    }
                 }
             }
-            SolutionDataTypes::Real(real) => {
-                let lower_bound = real.lower_bound.unwrap_or(f64::MIN);
-                let upper_bound = real.upper_bound.unwrap_or(f64::MAX);
-
-                if rng.gen::<f64>() < self.probability {
-                    rng.gen_range(lower_bound..=upper_bound)
-                } else {
-                    parent.solution[index]
-                }
+            SolutionDataTypes::Real(real) if rng.gen::<f64>() < self.probability => {
+                Gene::Real(real.representable(uniform_within(x.as_f64(), (real.lower_limit(), real.upper_limit()), &mut rng)))
             }
-            _ => parent.solution[index], // No mutation for other types
+            _ => x, // No mutation for other types, or not selected
         }
     }
 }
 
+// Scale of a step away from `x` when a bound is missing, so the step is relative to the magnitude of the value
+fn scale_free_range(x: f64) -> f64 {
+    x.abs().max(1.0)
+}
+
+// Uniform value between the limits, or within one scale-free range of `x` when a limit is missing
+fn uniform_within(x: f64, limits: (Option<f64>, Option<f64>), rng: &mut impl Rng) -> f64 {
+    match limits {
+        (Some(lower), Some(upper)) => rng.gen_range(lower..=upper),
+        _ => x + rng.gen_range(-1.0..=1.0) * scale_free_range(x),
+    }
+}
+
 pub struct PolynomialMutation {
     pub probability: f64,
     pub distribution_index: f64,
@@ -184,57 +237,49 @@ impl PolynomialMutation {
     }
 }
 
-impl<'a> Mutation<'a> for PolynomialMutation {
-    fn mutate(&self, parent: &'a Solution<'a>, index: usize) -> f64 {
-        let mut rng = rand::thread_rng();
-        match &parent.problem.solution_data_types[index] {
-            SolutionDataTypes::Integer(integer) => {
-                let lower_bound = integer.lower_bound.unwrap_or(i64::MIN) as f64;
-                let upper_bound = integer.upper_bound.unwrap_or(i64::MAX) as f64;
-
-                if rng.gen::<f64>() < self.probability {
-                    let u = rng.gen::<f64>();
-                    let dx = upper_bound - lower_bound;
-                    let delta = if u < 0.5 {
-                        let bl = (parent.solution[index] - lower_bound) / dx;
-                        (2.0 * u + (1.0 - 2.0 * u) * (1.0 - bl).powf(self.distribution_index + 1.0))
-                            .powf(1.0 / (self.distribution_index + 1.0))
-                            - 1.0
-                    } else {
-                        let bu = (upper_bound - parent.solution[index]) / dx;
-                        (2.0 * (1.0 - u) + 2.0 * (u - 0.5) * (1.0 - bu).powf(self.distribution_index + 1.0))
-                            .powf(1.0 / (self.distribution_index + 1.0))
-                            - 1.0
-                    };
-                    (parent.solution[index] + delta * dx).round().clamp(lower_bound, upper_bound)
+impl PolynomialMutation {
+    // Polynomial perturbation of `x`; a missing limit switches to the unbounded form scaled to the magnitude of `x`
+    fn perturb(&self, x: f64, limits: (Option<f64>, Option<f64>), rng: &mut impl Rng) -> f64 {
+        let u = rng.gen::<f64>();
+        let exponent = 1.0 / (self.distribution_index + 1.0);
+        match limits {
+            (Some(lower_bound), Some(upper_bound)) => {
+                let dx = upper_bound - lower_bound;
+                let delta = if u < 0.5 {
+                    let bl = (x - lower_bound) / dx;
+                    (2.0 * u + (1.0 - 2.0 * u) * (1.0 - bl).powf(self.distribution_index + 1.0)).powf(exponent) - 1.0
                 } else {
-                    parent.solution[index]
-                }
+                    let bu = (upper_bound - x) / dx;
+                    1.0 - (2.0 * (1.0 - u) + 2.0 * (u - 0.5) * (1.0 - bu).powf(self.distribution_index + 1.0)).powf(exponent)
+                };
+                x + delta * dx
             }
-            SolutionDataTypes::Real(real) => {
-                let lower_bound = real.lower_bound.unwrap_or(f64::MIN);
-                let upper_bound = real.upper_bound.unwrap_or(f64::MAX);
-
-                if rng.gen::<f64>() < self.probability {
-                    let u = rng.gen::<f64>();
-                    let dx = upper_bound - lower_bound;
-                    let delta = if u < 0.5 {
-                        let bl = (parent.solution[index] - lower_bound) / dx;
-                        (2.0 * u + (1.0 - 2.0 * u) * (1.0 - bl).powf(self.distribution_index + 1.0))
-                            .powf(1.0 / (self.distribution_index + 1.0))
-                            - 1.0
-                    } else {
-                        let bu = (upper_bound - parent.solution[index]) / dx;
-                        (2.0 * (1.0 - u) + 2.0 * (u - 0.5) * (1.0 - bu).powf(self.distribution_index + 1.0))
-                            .powf(1.0 / (self.distribution_index + 1.0))
-                            - 1.0
-                    };
-                    (parent.solution[index] + delta * dx).clamp(lower_bound, upper_bound)
+            _ => {
+                let delta = if u < 0.5 {
+                    (2.0 * u).powf(exponent) - 1.0
                 } else {
-                    parent.solution[index]
-                }
+                    1.0 - (2.0 * (1.0 - u)).powf(exponent)
+                };
+                x + delta * scale_free_range(x)
+            }
+        }
+    }
+}
+
+impl<'a> Mutation<'a> for PolynomialMutation {
+    fn mutate(&self, parent: &'a Solution<'a>, index: usize) -> Gene {
+        let mut rng = rand::thread_rng();
+        let gene = parent.problem.gene_range(index).start;
+        let x = parent.solution[gene];
+        match parent.problem.variable_type(index) {
+            SolutionDataTypes::Integer(integer) if rng.gen::<f64>() < self.probability => {
+                let limits = (integer.lower_limit().map(|l| l as f64), integer.upper_limit().map(|u| u as f64));
+                Gene::Integer(integer.representable(self.perturb(x.as_f64(), limits, &mut rng)) as i64)
+            }
+            SolutionDataTypes::Real(real) if rng.gen::<f64>() < self.probability => {
+                Gene::Real(real.representable(self.perturb(x.as_f64(), (real.lower_limit(), real.upper_limit()), &mut rng)))
             }
-            _ => parent.solution[index], // No mutation for other types
+            _ => x, // No mutation for other types, or not selected
         }
     }
 }
@@ -255,29 +300,456 @@ impl GaussianMutation {
 }
 
 impl<'a> Mutation<'a> for GaussianMutation {
-    fn mutate(&self, parent: &'a Solution<'a>, index: usize) -> f64 {
+    fn mutate(&self, parent: &'a Solution<'a>, index: usize) -> Gene {
         let mut rng = rand::thread_rng();
-        let lower_bound = parent.problem.solution_data_types[index]
+        let gene = parent.problem.gene_range(index).start;
+        let lower_bound = parent.problem.variable_type(index)
             .get_lower_bound()
             .unwrap_or(f64::MIN);
-        let upper_bound = parent.problem.solution_data_types[index]
+        let upper_bound = parent.problem.variable_type(index)
             .get_upper_bound()
             .unwrap_or(f64::MAX);
 
         if rng.gen::<f64>() < self.probability {
-            (parent.solution[index] + rng.gen::<f64>() * self.standard_deviation)
-                .clamp(lower_bound, upper_bound)
+            let value = (parent.solution[gene].as_f64() + rng.gen::<f64>() * self.standard_deviation).clamp(lower_bound, upper_bound);
+            match parent.problem.variable_type(index) {
+                SolutionDataTypes::Integer(integer) => Gene::Integer(integer.representable(value) as i64),
+                SolutionDataTypes::Real(real) => Gene::Real(real.representable(value)),
+                _ => Gene::Real(value),
+            }
         } else {
-            parent.solution[index]
+            parent.solution[gene]
+        }
+    }
+}
+
+// Two different random positions of a sequence
+fn distinct_positions(length: usize, rng: &mut impl Rng) -> (usize, usize) {
+    let first = rng.gen_range(0..length);
+    let mut second = rng.gen_range(0..length - 1);
+    if second >= first {
+        second += 1;
+    }
+    (first, second)
+}
+
+// Copy of the permutation genes of the variable, or None when it is not a permutation or not selected
+fn permutation_genes<'a>(parent: &'a Solution<'a>, index: usize, probability: f64) -> Option<Vec<Gene>> {
+    match parent.problem.variable_type(index) {
+        SolutionDataTypes::Permutation(permutation)
+            if permutation.size > 1 && rand::thread_rng().gen::<f64>() < probability =>
+        {
+            Some(parent.variable(index).to_vec())
+        }
+        _ => None,
+    }
+}
+
+/// Swap Mutation: exchanges the elements at two random positions of a permutation
+pub struct SwapMutation {
+    pub probability: f64,
+}
+
+impl SwapMutation {
+    pub fn new(probability: Option<f64>) -> Self {
+        Self { probability: probability.unwrap_or(1.0) }
+    }
+}
+
+impl Default for SwapMutation {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl<'a> Mutation<'a> for SwapMutation {
+    fn mutate_variable(&self, parent: &'a Solution<'a>, index: usize) -> Vec<Gene> {
+        match permutation_genes(parent, index, self.probability) {
+            Some(mut genes) => {
+                let (i, j) = distinct_positions(genes.len(), &mut rand::thread_rng());
+                genes.swap(i, j);
+                genes
+            }
+            None => parent.variable(index).to_vec(),
+        }
+    }
+}
+
+/// Insertion Mutation: moves the element at a random position to another random position of a permutation
+pub struct InsertionMutation {
+    pub probability: f64,
+}
+
+impl InsertionMutation {
+    pub fn new(probability: Option<f64>) -> Self {
+        Self { probability: probability.unwrap_or(1.0) }
+    }
+}
+
+impl Default for InsertionMutation {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl<'a> Mutation<'a> for InsertionMutation {
+    fn mutate_variable(&self, parent: &'a Solution<'a>, index: usize) -> Vec<Gene> {
+        match permutation_genes(parent, index, self.probability) {
+            Some(mut genes) => {
+                let (from, to) = distinct_positions(genes.len(), &mut rand::thread_rng());
+                let element = genes.remove(from);
+                genes.insert(to, element);
+                genes
+            }
+            None => parent.variable(index).to_vec(),
+        }
+    }
+}
+
+/// Inversion Mutation: reverses the elements between two random positions of a permutation
+pub struct InversionMutation {
+    pub probability: f64,
+}
+
+impl InversionMutation {
+    pub fn new(probability: Option<f64>) -> Self {
+        Self { probability: probability.unwrap_or(1.0) }
+    }
+}
+
+impl Default for InversionMutation {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl<'a> Mutation<'a> for InversionMutation {
+    fn mutate_variable(&self, parent: &'a Solution<'a>, index: usize) -> Vec<Gene> {
+        match permutation_genes(parent, index, self.probability) {
+            Some(mut genes) => {
+                let (i, j) = distinct_positions(genes.len(), &mut rand::thread_rng());
+                genes[i.min(j)..=i.max(j)].reverse();
+                genes
+            }
+            None => parent.variable(index).to_vec(),
+        }
+    }
+}
+
+/// Scramble Mutation: shuffles the elements between two random positions of a permutation
+pub struct ScrambleMutation {
+    pub probability: f64,
+}
+
+impl ScrambleMutation {
+    pub fn new(probability: Option<f64>) -> Self {
+        Self { probability: probability.unwrap_or(1.0) }
+    }
+}
+
+impl Default for ScrambleMutation {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl<'a> Mutation<'a> for ScrambleMutation {
+    fn mutate_variable(&self, parent: &'a Solution<'a>, index: usize) -> Vec<Gene> {
+        match permutation_genes(parent, index, self.probability) {
+            Some(mut genes) => {
+                let mut rng = rand::thread_rng();
+                let (i, j) = distinct_positions(genes.len(), &mut rng);
+                genes[i.min(j)..=i.max(j)].shuffle(&mut rng);
+                genes
+            }
+            None => parent.variable(index).to_vec(),
+        }
+    }
+}
+/// Replace Mutation: swaps a random element of a subset for a random element outside it
+pub struct ReplaceMutation {
+    pub probability: f64,
+}
+
+impl ReplaceMutation {
+    pub fn new(probability: Option<f64>) -> Self {
+        Self { probability: probability.unwrap_or(1.0) }
+    }
+}
+
+impl Default for ReplaceMutation {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl<'a> Mutation<'a> for ReplaceMutation {
+    fn mutate_variable(&self, parent: &'a Solution<'a>, index: usize) -> Vec<Gene> {
+        let mut genes = parent.variable(index).to_vec();
+        let mut rng = rand::thread_rng();
+        if let SolutionDataTypes::Subset(subset) = parent.problem.variable_type(index) {
+            if subset.size < subset.elements && rng.gen::<f64>() < self.probability {
+                let outside: Vec<usize> = (0..subset.elements)
+                    .filter(|&element| !genes.iter().any(|gene| gene.as_usize() == element))
+                    .collect();
+                let position = rng.gen_range(0..genes.len());
+                genes[position] = Gene::Element(*outside.choose(&mut rng).unwrap());
+            }
+        }
+        genes
+    }
+}
+
+/// Bit Flip Mutation for bit strings: flips every bit independently, with probability 1 / bits unless given
+pub struct BitStringFlipMutation {
+    pub probability: Option<f64>,
+}
+
+impl BitStringFlipMutation {
+    pub fn new(probability: Option<f64>) -> Self {
+        Self { probability }
+    }
+}
+
+impl Default for BitStringFlipMutation {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl<'a> Mutation<'a> for BitStringFlipMutation {
+    fn mutate_variable(&self, parent: &'a Solution<'a>, index: usize) -> Vec<Gene> {
+        let mut genes = parent.variable(index).to_vec();
+        if let SolutionDataTypes::BitString(bit_string) = parent.problem.variable_type(index) {
+            let mut rng = rand::thread_rng();
+            let probability = self.probability.unwrap_or(1.0 / bit_string.bits as f64);
+            for position in 0..bit_string.bits {
+                if rng.gen::<f64>() < probability {
+                    let bit = BitString::bit(&genes, position);
+                    BitString::set_bit(&mut genes, position, !bit);
+                }
+            }
+        }
+        genes
+    }
+}
+
+/// Random Reset Mutation for categories: replaces the category with a different one drawn uniformly
+pub struct RandomResetMutation {
+    pub probability: f64,
+}
+
+impl RandomResetMutation {
+    pub fn new(probability: Option<f64>) -> Self {
+        Self { probability: probability.unwrap_or(1.0) }
+    }
+}
+
+impl Default for RandomResetMutation {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl<'a> Mutation<'a> for RandomResetMutation {
+    fn mutate(&self, parent: &'a Solution<'a>, index: usize) -> Gene {
+        let mut rng = rand::thread_rng();
+        let gene = parent.problem.gene_range(index).start;
+        match parent.problem.variable_type(index) {
+            SolutionDataTypes::Categorical(categorical)
+                if categorical.choices.len() > 1 && rng.gen::<f64>() < self.probability =>
+            {
+                // Draw among the other categories by skipping over the current one
+                let current = parent.solution[gene].as_usize();
+                let other = rng.gen_range(0..categorical.choices.len() - 1);
+                Gene::Category(if other >= current { other + 1 } else { other })
+            }
+            _ => parent.solution[gene],
+        }
+    }
+}
+
+/// Point Mutation for expression trees: replaces every node independently, with probability 1 / size unless
+/// given, by a random node of the same arity
+pub struct PointMutation {
+    pub probability: Option<f64>,
+}
+
+impl PointMutation {
+    pub fn new(probability: Option<f64>) -> Self {
+        Self { probability }
+    }
+}
+
+impl Default for PointMutation {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl<'a> Mutation<'a> for PointMutation {
+    fn mutate_variable(&self, parent: &'a Solution<'a>, index: usize) -> Vec<Gene> {
+        let genes = parent.variable(index);
+        match parent.problem.variable_type(index) {
+            SolutionDataTypes::ExpressionTree(tree) => {
+                let mut rng = rand::thread_rng();
+                let mut nodes = ExpressionTree::nodes(genes);
+                let probability = self.probability.unwrap_or(1.0 / nodes.len() as f64);
+                for node in nodes.iter_mut() {
+                    if rng.gen::<f64>() < probability {
+                        if let Some(replacement) = tree.random_node_of_arity(node.arity(), &mut rng) {
+                            *node = replacement;
+                        }
+                    }
+                }
+                tree.to_genes(&nodes)
+            }
+            _ => genes.to_vec(),
+        }
+    }
+}
+
+/// Subtree Mutation for expression trees: replaces a random subtree by a randomly grown one of at most
+/// `max_depth`; a result that would exceed the depth or size limit of the tree is discarded
+pub struct SubtreeMutation {
+    pub probability: f64,
+    pub max_depth: usize,
+}
+
+impl SubtreeMutation {
+    pub fn new(probability: Option<f64>, max_depth: Option<usize>) -> Self {
+        Self { probability: probability.unwrap_or(1.0), max_depth: max_depth.unwrap_or(4) }
+    }
+}
+
+impl Default for SubtreeMutation {
+    fn default() -> Self {
+        Self::new(None, None)
+    }
+}
+
+impl<'a> Mutation<'a> for SubtreeMutation {
+    fn mutate_variable(&self, parent: &'a Solution<'a>, index: usize) -> Vec<Gene> {
+        let genes = parent.variable(index);
+        let mut rng = rand::thread_rng();
+        match parent.problem.variable_type(index) {
+            SolutionDataTypes::ExpressionTree(tree) if rng.gen::<f64>() < self.probability => {
+                let nodes = ExpressionTree::nodes(genes);
+                let start = rng.gen_range(0..nodes.len());
+                let end = ExpressionTree::subtree_end(&nodes, start);
+                let depth = self.max_depth.min(tree.max_depth.saturating_sub(ExpressionTree::depths(&nodes)[start]));
+                let subtree = tree.random_tree(depth, false, &mut rng);
+                let mutated = [&nodes[..start], &subtree[..], &nodes[end..]].concat();
+                if tree.fits(&mutated) {
+                    tree.to_genes(&mutated)
+                } else {
+                    genes.to_vec()
+                }
+            }
+            _ => genes.to_vec(),
+        }
+    }
+}
+
+/// Mutations that change the number of template repetitions of a variable-length genome; they leave
+/// fixed-length genomes and genomes at their length limit unchanged
+pub trait LengthMutation<'a>: Send + Sync {
+    fn mutate_length(&self, parent: &Solution<'a>) -> Solution<'a>;
+}
+
+// Child of a length mutation, with the repetition count of the parent and its length limits
+fn resized_child<'a>(parent: &Solution<'a>) -> (Solution<'a>, usize, usize, usize) {
+    let mut child = parent.clone();
+    child.evaluated = false;
+    child.feasible = false;
+    let repetitions = parent.problem.repetitions(&parent.solution);
+    match &parent.problem.variable_length {
+        Some(variable_length) => (child, repetitions, variable_length.min_length, variable_length.max_length),
+        None => (child, repetitions, repetitions, repetitions),
+    }
+}
+
+/// Insert Gene Mutation: inserts a randomly generated template repetition at a random position
+pub struct InsertGeneMutation {
+    pub probability: f64,
+}
+
+impl InsertGeneMutation {
+    pub fn new(probability: Option<f64>) -> Self {
+        Self { probability: probability.unwrap_or(1.0) }
+    }
+}
+
+impl<'a> LengthMutation<'a> for InsertGeneMutation {
+    fn mutate_length(&self, parent: &Solution<'a>) -> Solution<'a> {
+        let (mut child, repetitions, _, max_length) = resized_child(parent);
+        let mut rng = rand::thread_rng();
+        if repetitions < max_length && rng.gen::<f64>() < self.probability {
+            let unit = parent.problem.solution_data_types.len();
+            let position = rng.gen_range(0..=repetitions) * unit;
+            child.solution.splice(position..position, parent.problem.generate_unit());
+        }
+        child
+    }
+}
+
+/// Delete Gene Mutation: removes a random template repetition
+pub struct DeleteGeneMutation {
+    pub probability: f64,
+}
+
+impl DeleteGeneMutation {
+    pub fn new(probability: Option<f64>) -> Self {
+        Self { probability: probability.unwrap_or(1.0) }
+    }
+}
+
+impl<'a> LengthMutation<'a> for DeleteGeneMutation {
+    fn mutate_length(&self, parent: &Solution<'a>) -> Solution<'a> {
+        let (mut child, repetitions, min_length, _) = resized_child(parent);
+        let mut rng = rand::thread_rng();
+        if repetitions > min_length && rng.gen::<f64>() < self.probability {
+            let unit = parent.problem.solution_data_types.len();
+            let position = rng.gen_range(0..repetitions) * unit;
+            child.solution.drain(position..position + unit);
         }
+        child
+    }
+}
+
+/// Duplicate Gene Mutation: copies a random template repetition right after itself
+pub struct DuplicateGeneMutation {
+    pub probability: f64,
+}
+
+impl DuplicateGeneMutation {
+    pub fn new(probability: Option<f64>) -> Self {
+        Self { probability: probability.unwrap_or(1.0) }
+    }
+}
+
+impl<'a> LengthMutation<'a> for DuplicateGeneMutation {
+    fn mutate_length(&self, parent: &Solution<'a>) -> Solution<'a> {
+        let (mut child, repetitions, _, max_length) = resized_child(parent);
+        let mut rng = rand::thread_rng();
+        if repetitions < max_length && rng.gen::<f64>() < self.probability {
+            let unit = parent.problem.solution_data_types.len();
+            let position = rng.gen_range(0..repetitions) * unit;
+            let copy = parent.solution[position..position + unit].to_vec();
+            child.solution.splice(position + unit..position + unit, copy);
+        }
+        child
     }
 }
+
 // Tests
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::{Problem, Solution};
-    use crate::gatypes::{SolutionDataTypes, Real, Integer, BitBinary};
+    use crate::core::{ObjectiveFunction, Problem, Solution};
+    use crate::conditions::{Activation, Condition};
+    use crate::gatypes::{SolutionDataTypes, Real, Integer, BitBinary, Permutation, Subset, Categorical};
+    use crate::expression_tree::{Function, Node, Terminal};
 
     fn setup_problem() -> Problem {
         Problem {
@@ -293,7 +765,8 @@ mod tests {
                 SolutionDataTypes::Real(Real::new(Some(-100.0), Some(1000.0))),
                 SolutionDataTypes::Real(Real::new(Some(-100.0), Some(1000.0))),
             ],
-            objective_function: |x| vec![x.iter().sum()],
+            objective_function: ObjectiveFunction::Numeric(|x| vec![x.iter().sum()]),
+            ..Default::default()
         }
     }
 
@@ -301,7 +774,7 @@ mod tests {
         
         Solution {
             problem,
-            solution: vec![1.0, 10.0, 10.0, 10.0, 10.0],
+            solution: vec![Gene::Bit(true), Gene::Integer(10), Gene::Real(10.0), Gene::Real(10.0), Gene::Real(10.0)],
             objective_fitness_values: Vec::with_capacity(*problem.number_of_objectives()),
             constraint_values: Vec::with_capacity(*problem.number_of_objectives()),
             constraint_violation: 0,
@@ -321,7 +794,7 @@ mod tests {
             let child = mutation_manager.mutate(&parent);
             println!(" Parent: {:?}", parent.solution);
             println!(" Child: {:?}", child.solution);
-            assert!(child.solution[0] == 0.0 || child.solution[0] == 1.0);
+            assert!(matches!(child.solution[0], Gene::Bit(_)));
             assert!(child.solution[1] != parent.solution[1]);
             assert!(child.solution[2] != parent.solution[2]);
             assert!(child.solution[3] != parent.solution[3]);
@@ -335,11 +808,11 @@ mod tests {
         let mutation = BitFlipMutation{probability: 1.0};
         let parent1 = setup_solution(&problem);
         let mut parent2 = setup_solution(&problem);
-        parent2.solution[0] = 0.0;
+        parent2.solution[0] = Gene::Bit(false);
         let child1 = mutation.mutate(&parent1, 0);
         let child2 = mutation.mutate(&parent2, 0);
-        assert!(child1 == 0.0);
-        assert!(child2 == 1.0);
+        assert_eq!(child1, Gene::Bit(false));
+        assert_eq!(child2, Gene::Bit(true));
     }
     #[test]
     fn test_polynomial_mutation_with_integer_and_real() {
@@ -349,20 +822,20 @@ mod tests {
 
 
         let mutation = PolynomialMutation::new(Some(1.0), Some(20.0));
-        let child_solution_0 = mutation.mutate(&parent, 1); // Integer mutation
-        let child_solution_1 = mutation.mutate(&parent, 2); // Real mutation
-        let child_solution_2 = mutation.mutate(&parent, 3); // Real mutation
-        let child_solution_3 = mutation.mutate(&parent, 4); // Real mutation
+        let child_solution_0 = mutation.mutate(&parent, 1).as_f64(); // Integer mutation
+        let child_solution_1 = mutation.mutate(&parent, 2).as_f64(); // Real mutation
+        let child_solution_2 = mutation.mutate(&parent, 3).as_f64(); // Real mutation
+        let child_solution_3 = mutation.mutate(&parent, 4).as_f64(); // Real mutation
 
         assert!(child_solution_0 >= -2000.0 && child_solution_0 <= 2000.0);
         assert!(child_solution_1 >= -100.0 && child_solution_1 <= 1000.0);
         assert!(child_solution_2 >= -100.0 && child_solution_2 <= 1000.0);
         assert!(child_solution_3 >= -100.0 && child_solution_3 <= 1000.0);
         // assert new solution is mutated
-        assert!(child_solution_0 != parent.solution[1]);
-        assert!(child_solution_1 != parent.solution[2]);
-        assert!(child_solution_2 != parent.solution[3]);
-        assert!(child_solution_3 != parent.solution[4]);
+        assert!(child_solution_0 != parent.solution[1].as_f64());
+        assert!(child_solution_1 != parent.solution[2].as_f64());
+        assert!(child_solution_2 != parent.solution[3].as_f64());
+        assert!(child_solution_3 != parent.solution[4].as_f64());
         
     }
 
@@ -373,20 +846,20 @@ mod tests {
         let parent = setup_solution(&problem);
 
         let mutation = UniformMutation::default();
-        let child_solution_0 = mutation.mutate(&parent, 1); // Integer mutation
-        let child_solution_1 = mutation.mutate(&parent, 2); // Real mutation
-        let child_solution_2 = mutation.mutate(&parent, 3); // Real mutation
-        let child_solution_3 = mutation.mutate(&parent, 4); // Real mutation
+        let child_solution_0 = mutation.mutate(&parent, 1).as_f64(); // Integer mutation
+        let child_solution_1 = mutation.mutate(&parent, 2).as_f64(); // Real mutation
+        let child_solution_2 = mutation.mutate(&parent, 3).as_f64(); // Real mutation
+        let child_solution_3 = mutation.mutate(&parent, 4).as_f64(); // Real mutation
 
         assert!(child_solution_0 >= -2000.0 && child_solution_0 <= 2000.0);
         assert!(child_solution_1 >= -100.0 && child_solution_1 <= 1000.0);
         assert!(child_solution_2 >= -100.0 && child_solution_2 <= 1000.0);
         assert!(child_solution_3 >= -100.0 && child_solution_3 <= 1000.0);
         // assert new solution is mutated
-        assert!(child_solution_0 != parent.solution[1]);
-        assert!(child_solution_1 != parent.solution[2]);
-        assert!(child_solution_2 != parent.solution[3]);
-        assert!(child_solution_3 != parent.solution[4]);
+        assert!(child_solution_0 != parent.solution[1].as_f64());
+        assert!(child_solution_1 != parent.solution[2].as_f64());
+        assert!(child_solution_2 != parent.solution[3].as_f64());
+        assert!(child_solution_3 != parent.solution[4].as_f64());
     
     }
 
@@ -397,21 +870,393 @@ mod tests {
         let parent = setup_solution(&problem);
 
         let mutation = GaussianMutation::new(Some(1.0), Some(0.1));
-        let child_solution_0 = mutation.mutate(&parent, 1); // Integer mutation
-        let child_solution_1 = mutation.mutate(&parent, 2); // Real mutation
-        let child_solution_2 = mutation.mutate(&parent, 3); // Real mutation
-        let child_solution_3 = mutation.mutate(&parent, 4); // Real mutation
+        let child_gene_0 = mutation.mutate(&parent, 1); // Integer mutation
+        let child_solution_1 = mutation.mutate(&parent, 2).as_f64(); // Real mutation
+        let child_solution_2 = mutation.mutate(&parent, 3).as_f64(); // Real mutation
+        let child_solution_3 = mutation.mutate(&parent, 4).as_f64(); // Real mutation
 
-        assert!(child_solution_0 >= -2000.0 && child_solution_0 <= 2000.0);
+        assert!(matches!(child_gene_0, Gene::Integer(-2000..=2000)));
         assert!(child_solution_1 >= -100.0 && child_solution_1 <= 1000.0);
         assert!(child_solution_2 >= -100.0 && child_solution_2 <= 1000.0);
         assert!(child_solution_3 >= -100.0 && child_solution_3 <= 1000.0);
-        // assert new solution is mutated
-        assert!(child_solution_0 != parent.solution[1]);
-        assert!(child_solution_1 != parent.solution[2]);
-        assert!(child_solution_2 != parent.solution[3]);
-        assert!(child_solution_3 != parent.solution[4]);
+        // assert new solution is mutated; the integer step stays below half a unit and rounds back
+        assert_eq!(child_gene_0, parent.solution[1]);
+        assert!(child_solution_1 != parent.solution[2].as_f64());
+        assert!(child_solution_2 != parent.solution[3].as_f64());
+        assert!(child_solution_3 != parent.solution[4].as_f64());
+    }
+
+    fn setup_permutation_problem() -> Problem {
+        Problem {
+            solution_length: 2,
+            number_of_objectives: 1,
+            objective_constraint: None,
+            objective_constraint_operands: None,
+            direction: Some(vec![-1]),
+            solution_data_types: vec![
+                SolutionDataTypes::Real(Real::new(Some(0.0), Some(1.0))),
+                SolutionDataTypes::Permutation(Permutation::new(8)),
+            ],
+            objective_function: ObjectiveFunction::Numeric(|x| vec![x.iter().sum()]),
+            ..Default::default()
+        }
     }
 
+    fn permutation_solution(problem: &Problem) -> Solution<'_> {
+        let mut solution = vec![Gene::Real(0.5)];
+        solution.extend((0..8).map(Gene::Element));
+        Solution {
+            problem,
+            solution,
+            objective_fitness_values: Vec::new(),
+            constraint_values: Vec::new(),
+            constraint_violation: 0,
+            feasible: false,
+            evaluated: false,
+        }
+    }
 
+    fn assert_permutation(genes: &[Gene]) {
+        let mut sorted = genes.to_vec();
+        sorted.sort_by_key(Gene::as_usize);
+        assert_eq!(sorted, (0..genes.len()).map(Gene::Element).collect::<Vec<Gene>>());
+    }
+
+    #[test]
+    fn test_permutation_mutations() {
+        let problem = setup_permutation_problem();
+        let parent = permutation_solution(&problem);
+        let mutations: Vec<Box<dyn Mutation>> = vec![
+            Box::new(SwapMutation::default()),
+            Box::new(InsertionMutation::default()),
+            Box::new(InversionMutation::default()),
+            Box::new(ScrambleMutation::default()),
+        ];
+        for mutation in mutations.iter() {
+            for _ in 0..50 {
+                let genes = mutation.mutate_variable(&parent, 1);
+                assert_eq!(genes.len(), 8);
+                assert_permutation(&genes);
+            }
+            // Non-permutation variables are left alone
+            assert_eq!(mutation.mutate_variable(&parent, 0), vec![Gene::Real(0.5)]);
+        }
+        let swapped = SwapMutation::default().mutate_variable(&parent, 1);
+        assert_eq!(swapped.iter().zip(parent.variable(1)).filter(|(a, b)| a != b).count(), 2);
+        let inverted = InversionMutation::default().mutate_variable(&parent, 1);
+        assert_ne!(inverted, parent.variable(1));
+        assert_eq!(SwapMutation::new(Some(0.0)).mutate_variable(&parent, 1), parent.variable(1));
+    }
+
+    #[test]
+    fn test_mutation_manager_with_permutation() {
+        let problem = setup_permutation_problem();
+        let parent = permutation_solution(&problem);
+        let mut mutation_manager = MutationManager::new();
+        let child = mutation_manager.mutate(&parent);
+        assert_eq!(child.solution.len(), 9);
+        assert_permutation(child.variable(1));
+        assert_ne!(child.variable(1), parent.variable(1));
+        // A scalar mutation set on a permutation variable leaves it unchanged
+        mutation_manager.set_custom_mutation(1, Arc::new(UniformMutation::default()));
+        assert_eq!(mutation_manager.mutate(&parent).variable(1), parent.variable(1));
+    }
+
+    #[test]
+    fn test_replace_mutation() {
+        let problem = Problem {
+            solution_length: 2,
+            number_of_objectives: 1,
+            objective_constraint: None,
+            objective_constraint_operands: None,
+            direction: Some(vec![-1]),
+            solution_data_types: vec![
+                SolutionDataTypes::Subset(Subset::new(6, 3)),
+                SolutionDataTypes::Subset(Subset::new(3, 3)),
+            ],
+            objective_function: ObjectiveFunction::Numeric(|x| vec![x.iter().sum()]),
+            ..Default::default()
+        };
+        let parent = Solution {
+            problem: &problem,
+            solution: [0, 1, 2, 2, 0, 1].map(Gene::Element).to_vec(),
+            objective_fitness_values: Vec::new(),
+            constraint_values: Vec::new(),
+            constraint_violation: 0,
+            feasible: false,
+            evaluated: false,
+        };
+        let subset = Subset::new(6, 3);
+        for _ in 0..50 {
+            let child = MutationManager::new().mutate(&parent);
+            assert!(subset.is_valid(child.variable(0)));
+            assert_eq!(child.variable(0).iter().filter(|gene| gene.as_usize() >= 3).count(), 1);
+            // A subset of every element has nothing to swap in
+            assert_eq!(child.variable(1), parent.variable(1));
+        }
+    }
+
+    #[test]
+    fn test_bit_string_flip_mutation() {
+        let problem = Problem {
+            solution_length: 1,
+            number_of_objectives: 1,
+            objective_constraint: None,
+            objective_constraint_operands: None,
+            direction: Some(vec![-1]),
+            solution_data_types: vec![SolutionDataTypes::BitString(BitString::new(40))],
+            objective_function: ObjectiveFunction::Numeric(|x| vec![x.iter().sum()]),
+            ..Default::default()
+        };
+        let parent = Solution {
+            problem: &problem,
+            solution: vec![Gene::Word(0); 2],
+            objective_fitness_values: Vec::new(),
+            constraint_values: Vec::new(),
+            constraint_violation: 0,
+            feasible: false,
+            evaluated: false,
+        };
+        let flipped = BitStringFlipMutation::new(Some(1.0)).mutate_variable(&parent, 0);
+        assert_eq!(flipped, vec![Gene::Word(u32::MAX), Gene::Word(255)]);
+        assert_eq!(BitStringFlipMutation::new(Some(0.0)).mutate_variable(&parent, 0), parent.solution);
+        let mut total = 0;
+        for _ in 0..200 {
+            let child = MutationManager::new().mutate(&parent);
+            total += BitString::new(40).to_bits(&child.solution).iter().filter(|&&bit| bit).count();
+        }
+        // One flipped bit per mutation on average
+        assert!(total > 100 && total < 300);
+    }
+
+    #[test]
+    fn test_expression_tree_mutations() {
+        let problem = Problem {
+            solution_length: 1,
+            number_of_objectives: 1,
+            objective_constraint: None,
+            objective_constraint_operands: None,
+            direction: Some(vec![-1]),
+            solution_data_types: vec![SolutionDataTypes::ExpressionTree(ExpressionTree::new(
+                vec![Function::Add, Function::Subtract, Function::Sin, Function::Cos],
+                vec![Terminal::Variable(0), Terminal::Variable(1)],
+                3,
+            ))],
+            objective_function: ObjectiveFunction::Numeric(|x| vec![x.len() as f64]),
+            ..Default::default()
+        };
+        let tree = match &problem.solution_data_types[0] {
+            SolutionDataTypes::ExpressionTree(tree) => tree,
+            _ => unreachable!(),
+        };
+        // sin(x0) + x1
+        let nodes = [Node::Function(Function::Add), Node::Function(Function::Sin), Node::Variable(0), Node::Variable(1)];
+        let parent = Solution {
+            problem: &problem,
+            solution: tree.to_genes(&nodes),
+            objective_fitness_values: Vec::new(),
+            constraint_values: Vec::new(),
+            constraint_violation: 0,
+            feasible: false,
+            evaluated: false,
+        };
+        // Point mutation keeps the shape of the tree
+        let mutated = ExpressionTree::nodes(&PointMutation::new(Some(1.0)).mutate_variable(&parent, 0));
+        assert_eq!(mutated.iter().map(Node::arity).collect::<Vec<usize>>(), vec![2, 1, 0, 0]);
+        assert_eq!(PointMutation::new(Some(0.0)).mutate_variable(&parent, 0), parent.solution);
+        let mut changed = false;
+        for _ in 0..50 {
+            let genes = SubtreeMutation::new(None, Some(2)).mutate_variable(&parent, 0);
+            assert!(tree.fits(&ExpressionTree::nodes(&genes)));
+            let child = MutationManager::new().mutate(&parent);
+            assert!(ExpressionTree::depth(&child.solution) <= 3);
+            assert_eq!(child.solution.len(), tree.max_size);
+            changed |= child.solution != parent.solution;
+        }
+        assert!(changed);
+    }
+
+    #[test]
+    fn test_random_reset_mutation() {
+        let problem = Problem {
+            solution_length: 2,
+            number_of_objectives: 1,
+            objective_constraint: None,
+            objective_constraint_operands: None,
+            direction: Some(vec![-1]),
+            solution_data_types: vec![
+                SolutionDataTypes::Categorical(Categorical::new(vec!["a", "b", "c"])),
+                SolutionDataTypes::Categorical(Categorical::new(vec!["only"])),
+            ],
+            objective_function: ObjectiveFunction::Numeric(|x| vec![x.iter().sum()]),
+            ..Default::default()
+        };
+        let parent = Solution {
+            problem: &problem,
+            solution: vec![Gene::Category(1), Gene::Category(0)],
+            objective_fitness_values: Vec::new(),
+            constraint_values: Vec::new(),
+            constraint_violation: 0,
+            feasible: false,
+            evaluated: false,
+        };
+        let mut seen = [false; 3];
+        for _ in 0..100 {
+            let child = MutationManager::new().mutate(&parent);
+            assert!(matches!(child.solution[0], Gene::Category(0) | Gene::Category(2)));
+            assert_eq!(child.solution[1], Gene::Category(0));
+            seen[child.solution[0].as_usize()] = true;
+        }
+        assert_eq!(seen, [true, false, true]);
+    }
+
+    #[test]
+    fn test_mutations_snap_to_real_grid() {
+        let problem = Problem {
+            solution_length: 1,
+            number_of_objectives: 1,
+            objective_constraint: None,
+            objective_constraint_operands: None,
+            direction: Some(vec![-1]),
+            solution_data_types: vec![SolutionDataTypes::Real(Real::new(Some(0.0), Some(10.0)).with_step(0.5))],
+            objective_function: ObjectiveFunction::Numeric(|x| vec![x.iter().sum()]),
+            ..Default::default()
+        };
+        let parent = Solution {
+            problem: &problem,
+            solution: vec![Gene::Real(5.0)],
+            objective_fitness_values: Vec::new(),
+            constraint_values: Vec::new(),
+            constraint_violation: 0,
+            feasible: false,
+            evaluated: false,
+        };
+        let mutations: Vec<Box<dyn Mutation>> = vec![
+            Box::new(UniformMutation::default()),
+            Box::new(PolynomialMutation::new(Some(1.0), Some(5.0))),
+            Box::new(GaussianMutation::new(Some(1.0), Some(2.0))),
+        ];
+        for mutation in mutations.iter() {
+            for _ in 0..50 {
+                let value = mutation.mutate(&parent, 0).as_f64();
+                assert!((0.0..=10.0).contains(&value));
+                assert_eq!(value * 2.0, (value * 2.0).round());
+            }
+        }
+    }
+
+    #[test]
+    fn test_mutations_of_unbounded_variables() {
+        let problem = Problem {
+            solution_length: 3,
+            number_of_objectives: 1,
+            objective_constraint: None,
+            objective_constraint_operands: None,
+            direction: Some(vec![-1]),
+            solution_data_types: vec![
+                SolutionDataTypes::Real(Real::new(None, None)),
+                SolutionDataTypes::Real(Real::new(Some(0.0), None)),
+                SolutionDataTypes::Integer(Integer::new(None, None)),
+            ],
+            objective_function: ObjectiveFunction::Numeric(|x| vec![x.iter().sum()]),
+            ..Default::default()
+        };
+        let parent = Solution {
+            problem: &problem,
+            solution: vec![Gene::Real(250.0), Gene::Real(0.5), Gene::Integer(-40)],
+            objective_fitness_values: Vec::new(),
+            constraint_values: Vec::new(),
+            constraint_violation: 0,
+            feasible: false,
+            evaluated: false,
+        };
+        let mutations: Vec<Box<dyn Mutation>> = vec![
+            Box::new(UniformMutation::default()),
+            Box::new(PolynomialMutation::new(Some(1.0), Some(20.0))),
+        ];
+        for mutation in mutations.iter() {
+            for _ in 0..100 {
+                // Steps are finite and relative to the magnitude of the value
+                let value = mutation.mutate(&parent, 0).as_f64();
+                assert!(value.is_finite() && value != 250.0);
+                assert!((0.0..=500.0).contains(&value));
+                assert!((0.0..=1.5).contains(&mutation.mutate(&parent, 1).as_f64()));
+                let value = mutation.mutate(&parent, 2);
+                assert!(matches!(value, Gene::Integer(-80..=0)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_mutation_skips_inactive_variables() {
+        let problem = Problem {
+            solution_length: 2,
+            number_of_objectives: 1,
+            objective_constraint: None,
+            objective_constraint_operands: None,
+            direction: Some(vec![-1]),
+            solution_data_types: vec![
+                SolutionDataTypes::Categorical(Categorical::new(vec!["off", "on"])),
+                SolutionDataTypes::Real(Real::new(Some(0.0), Some(1.0))),
+            ],
+            conditions: vec![Condition::new(1, 0, Activation::Equals(1.0))],
+            objective_function: ObjectiveFunction::Numeric(|x| vec![x.iter().sum()]),
+            ..Default::default()
+        };
+        let parent = Solution {
+            problem: &problem,
+            solution: vec![Gene::Category(0), Gene::Real(0.5)],
+            objective_fitness_values: Vec::new(),
+            constraint_values: Vec::new(),
+            constraint_violation: 0,
+            feasible: false,
+            evaluated: false,
+        };
+        let mut active = parent.clone();
+        active.solution[0] = Gene::Category(1);
+        let mut mutation_manager = MutationManager::new();
+        mutation_manager.set_custom_mutation(0, Arc::new(RandomResetMutation::new(Some(0.0))));
+        assert_eq!(mutation_manager.mutate(&parent).solution, vec![Gene::Category(0), Gene::Real(0.5)]);
+        assert_ne!(mutation_manager.mutate(&active).solution[1], Gene::Real(0.5));
+    }
+
+    fn setup_variable_length_problem() -> Problem {
+        let solution_data_types = vec![
+            SolutionDataTypes::Real(Real::new(Some(0.0), Some(100.0))),
+            SolutionDataTypes::Real(Real::new(Some(0.0), Some(100.0))),
+        ];
+        Problem::new(2, 1, None, None, None, solution_data_types, |x| vec![x.len() as f64]).with_variable_length(1, 3)
+    }
+
+    #[test]
+    fn test_length_mutations() {
+        let problem = setup_variable_length_problem();
+        let mut parent = Solution::new(&problem);
+        parent.solution = [1.0, 2.0, 3.0, 4.0].map(Gene::Real).to_vec();
+        let inserted = InsertGeneMutation::new(None).mutate_length(&parent);
+        assert_eq!(inserted.solution.len(), 6);
+        assert_eq!(InsertGeneMutation::new(None).mutate_length(&inserted).solution.len(), 6);
+        let deleted = DeleteGeneMutation::new(None).mutate_length(&parent);
+        assert!(deleted.solution == [1.0, 2.0].map(Gene::Real) || deleted.solution == [3.0, 4.0].map(Gene::Real));
+        assert_eq!(DeleteGeneMutation::new(None).mutate_length(&deleted).solution.len(), 2);
+        let duplicated = DuplicateGeneMutation::new(None).mutate_length(&parent);
+        assert!(duplicated.solution == [1.0, 2.0, 1.0, 2.0, 3.0, 4.0].map(Gene::Real) || duplicated.solution == [1.0, 2.0, 3.0, 4.0, 3.0, 4.0].map(Gene::Real));
+    }
+
+    #[test]
+    fn test_mutation_manager_with_variable_length() {
+        let problem = setup_variable_length_problem();
+        let parent = Solution::new(&problem);
+        let mut mutation_manager = MutationManager::new();
+        let mut lengths = std::collections::HashSet::new();
+        for _ in 0..200 {
+            let child = mutation_manager.mutate(&parent);
+            assert!(child.solution.len().is_multiple_of(2) && (2..=6).contains(&child.solution.len()));
+            assert!(child.solution.iter().all(|gene| (0.0..=100.0).contains(&gene.as_f64())));
+            lengths.insert(child.solution.len());
+        }
+        assert!(lengths.len() > 1);
+        mutation_manager.set_length_mutations(Vec::new());
+        assert_eq!(mutation_manager.mutate(&parent).solution.len(), parent.solution.len());
+    }
 }
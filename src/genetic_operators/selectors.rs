@@ -1,6 +1,6 @@
 use crate::dominance::ParetoDominance;
 use crate::core::Solution; 
-use crate::dominance::Dominance;
+use crate::dominance::{Dominance, DominanceResult};
 use rand::{Rng, SeedableRng};
 use rand::rngs::StdRng;
 
@@ -13,14 +13,15 @@ pub trait Selector {
 #[derive(Debug)]
 pub struct TournamentSelector {
     tournament_size: usize,
-    dominance: ParetoDominance,
+    dominance: Box<dyn Dominance>,
     rng: StdRng, // Random number generator with optional seed
 }
 
 impl TournamentSelector {
 
-    /// Default constructor with random seed
-    pub fn new(tournament_size: usize, dominance: ParetoDominance, seed: Option<u64>) -> Self {
+    /// Creates a selector comparing contestants with `dominance`; any relation works, including the
+    /// scalarizing ones that turn a multi-objective problem into a single-objective search
+    pub fn new(tournament_size: usize, dominance: Box<dyn Dominance>, seed: Option<u64>) -> Self {
         let rng = match seed {
             Some(seed_value) => StdRng::seed_from_u64(seed_value), // Seeded RNG
             None => StdRng::from_entropy(),                      // RNG with entropy
@@ -33,13 +34,21 @@ impl TournamentSelector {
         }
     }
 
+    /// Replaces the relation used to decide each tournament
+    pub fn set_dominance(&mut self, dominance: Box<dyn Dominance>) {
+        self.dominance = dominance;
+    }
+
+    pub fn dominance(&self) -> &dyn Dominance {
+        self.dominance.as_ref()
+    }
+
     pub fn select_one<'a>(&mut self, population: &[&'a Solution<'a>]) -> &'a Solution<'a> {
         let mut winner = population[self.rng.gen_range(0..population.len())];
 
         for _ in 0..self.tournament_size {
             let challenger = population[self.rng.gen_range(0..population.len())];
-            let flag = self.dominance.compare_solutions(challenger, winner);
-            if flag > 0 {
+            if self.dominance.compare(challenger, winner) == DominanceResult::Dominates {
                 winner = challenger;
             }
         }
@@ -62,7 +71,7 @@ impl TournamentSelector {
 
 impl Default for TournamentSelector {
     fn default() -> Self {
-        TournamentSelector::new(2, ParetoDominance, Some(1234))
+        TournamentSelector::new(2, Box::new(ParetoDominance), Some(1234))
     }
 }
 
@@ -72,8 +81,9 @@ impl Default for TournamentSelector {
 mod tests {
     use super::*;
     use std::mem;
-    use crate::core::{Solution, Problem};
-    use crate::gatypes::{SolutionDataTypes, BitBinary, Integer, Real};
+    use crate::dominance::WeightedSumDominance;
+    use crate::core::{ObjectiveFunction, Solution, Problem};
+    use crate::gatypes::{SolutionDataTypes, BitBinary, Integer, Real, Gene};
     use crate::benchmark_objective_functions::{parabloid_5_loc, parabloid_hyper_5};
 
     fn setup_problem(objective_function: fn(&Vec<f64>) -> Vec<f64>, direction: Vec<i8>) -> Problem {
@@ -90,15 +100,16 @@ mod tests {
                 SolutionDataTypes::Integer(Integer::new(Some(-100), Some(20))),
                 SolutionDataTypes::Real(Real::new(Some(-10.0), Some(20.0))),
             ],
-            objective_function,
+            objective_function: ObjectiveFunction::Numeric(objective_function),
+            ..Default::default()
         }
     }
 
-    fn setup_solutions(problem: &Problem) -> Vec<Solution> {
+    fn setup_solutions(problem: &Problem) -> Vec<Solution<'_>> {
         vec![
             Solution {
                 problem,
-                solution: vec![1.0, 2.0, -3.0, 4.0, 5.0],
+                solution: [1.0, 2.0, -3.0, 4.0, 5.0].map(Gene::Real).to_vec(),
                 objective_fitness_values: Vec::with_capacity(*problem.number_of_objectives()),
                 constraint_values: Vec::with_capacity(*problem.number_of_objectives()),
                 constraint_violation: 0,
@@ -107,7 +118,7 @@ mod tests {
             },
             Solution {
                 problem,
-                solution: vec![12.0, 10.0, -3.0, 4.0, 5.0],
+                solution: [12.0, 10.0, -3.0, 4.0, 5.0].map(Gene::Real).to_vec(),
                 objective_fitness_values: Vec::with_capacity(*problem.number_of_objectives()),
                 constraint_values: Vec::with_capacity(*problem.number_of_objectives()),
                 constraint_violation: 0,
@@ -116,7 +127,7 @@ mod tests {
             },
             Solution {
                 problem,
-                solution: vec![-22.0, 12.0, -3.0, 1.0, 5.0],
+                solution: [-22.0, 12.0, -3.0, 1.0, 5.0].map(Gene::Real).to_vec(),
                 objective_fitness_values: Vec::with_capacity(*problem.number_of_objectives()),
                 constraint_values: Vec::with_capacity(*problem.number_of_objectives()),
                 constraint_violation: 0,
@@ -125,7 +136,7 @@ mod tests {
             },
             Solution {
                 problem,
-                solution: vec![1.0, 2.0, 3.0, 4.0, 5.0],
+                solution: [1.0, 2.0, 3.0, 4.0, 5.0].map(Gene::Real).to_vec(),
                 objective_fitness_values: Vec::with_capacity(*problem.number_of_objectives()),
                 constraint_values: Vec::with_capacity(*problem.number_of_objectives()),
                 constraint_violation: 0,
@@ -196,4 +207,41 @@ mod tests {
         println!("Memory size of a solution: {} bytes", mem::size_of_val(&solutions[0]));
         println!("Winners: {:?}", winners);
     }
+
+    #[test]
+    fn test_tournament_selector_with_weighted_sum() {
+        // Two objectives reduced to the first one: the best on objective 0 must win a large tournament
+        let problem = Problem {
+            solution_length: 2,
+            number_of_objectives: 2,
+            objective_constraint: None,
+            objective_constraint_operands: None,
+            direction: Some(vec![-1, -1]),
+            solution_data_types: vec![
+                SolutionDataTypes::Real(Real::new(Some(-10.0), Some(10.0))),
+                SolutionDataTypes::Real(Real::new(Some(-10.0), Some(10.0))),
+            ],
+            objective_function: ObjectiveFunction::Numeric(|x| x.clone()),
+            ..Default::default()
+        };
+        let mut solutions: Vec<Solution> = [[3.0, 0.0], [1.0, 9.0], [2.0, 1.0], [4.0, -5.0]]
+            .iter()
+            .map(|values| Solution {
+                problem: &problem,
+                solution: values.iter().copied().map(Gene::Real).collect(),
+                objective_fitness_values: Vec::new(),
+                constraint_values: Vec::new(),
+                constraint_violation: 0,
+                feasible: false,
+                evaluated: false,
+            })
+            .collect();
+        evaluate_solutions(&mut solutions);
+
+        let population: Vec<&Solution> = solutions.iter().collect();
+        let dominance = WeightedSumDominance::new(vec![1.0, 0.0]);
+        let mut tournament_selector = TournamentSelector::new(64, Box::new(dominance), Some(7));
+        let winner = tournament_selector.select_one(&population);
+        assert_eq!(winner.solution, vec![Gene::Real(1.0), Gene::Real(9.0)]);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,261 @@
+use crate::core::{Problem, Solution};
+use crate::error::{Error, Result};
+use crate::indicators::{bounds, oriented_front, ReferenceFront};
+use crate::math_utils::euclidean_distance;
+
+/// Convergence indicators of a solution set against a reference front. Both are oriented with the problem's
+/// direction; with normalization every objective is scaled so the reference front spans [0, 1]. The
+/// multiplicative epsilon is the exception, see `multiplicative_epsilon`.
+#[derive(Debug, Clone)]
+pub struct Convergence {
+    pub reference_front: Vec<Vec<f64>>, // minimization form, normalized if requested
+    pub normalization: Option<(Vec<f64>, Vec<f64>)>, // ideal and nadir of the reference front
+    pub raw_reference_front: Vec<Vec<f64>>, // in the problem's own units
+    pub maximized: Vec<bool>, // objectives with direction 1
+}
+
+impl Convergence {
+    pub fn new(problem: &Problem, reference_front: &ReferenceFront, normalize: bool) -> Self {
+        let points = reference_front.oriented(problem);
+        let normalization = if normalize { Some(bounds(&points)) } else { None };
+        let maximized = match &problem.direction {
+            Some(direction) => direction.iter().map(|&dir| dir == 1).collect(),
+            None => vec![false; problem.number_of_objectives],
+        };
+        let mut convergence = Self {
+            reference_front: Vec::new(),
+            normalization,
+            raw_reference_front: reference_front.points.clone(),
+            maximized,
+        };
+        convergence.reference_front = convergence.scale(points);
+        convergence
+    }
+
+    fn scale(&self, points: Vec<Vec<f64>>) -> Vec<Vec<f64>> {
+        match &self.normalization {
+            Some((ideal, nadir)) => points
+                .into_iter()
+                .map(|point| {
+                    point
+                        .iter()
+                        .zip(ideal.iter().zip(nadir.iter()))
+                        .map(|(value, (low, high))| {
+                            let range = high - low;
+                            if range > 0.0 { (value - low) / range } else { value - low }
+                        })
+                        .collect()
+                })
+                .collect(),
+            None => points,
+        }
+    }
+
+    fn front(&self, solutions: &[Solution]) -> Vec<Vec<f64>> {
+        self.scale(oriented_front(solutions))
+    }
+
+    pub fn generational_distance(&self, solutions: &[Solution]) -> f64 {
+        generational_distance(&self.front(solutions), &self.reference_front)
+    }
+
+    pub fn generational_distance_plus(&self, solutions: &[Solution]) -> f64 {
+        generational_distance_plus(&self.front(solutions), &self.reference_front)
+    }
+
+    pub fn inverted_generational_distance(&self, solutions: &[Solution]) -> f64 {
+        inverted_generational_distance(&self.front(solutions), &self.reference_front)
+    }
+
+    pub fn inverted_generational_distance_plus(&self, solutions: &[Solution]) -> f64 {
+        inverted_generational_distance_plus(&self.front(solutions), &self.reference_front)
+    }
+
+    pub fn additive_epsilon(&self, solutions: &[Solution]) -> f64 {
+        additive_epsilon(&self.front(solutions), &self.reference_front)
+    }
+
+    /// Multiplicative epsilon on the objective values in the problem's own units: ratios are only meaningful
+    /// for strictly positive values, which negation and normalization would not preserve. Maximized objectives
+    /// are compared by the inverse ratio.
+    pub fn multiplicative_epsilon(&self, solutions: &[Solution]) -> Result<f64> {
+        let points: Vec<Vec<f64>> = solutions.iter().map(|solution| solution.objective_fitness_values.clone()).collect();
+        ratio_epsilon(&points, &self.raw_reference_front, &self.maximized)
+    }
+}
+
+// Distance counting only the objectives on which the point is worse than the reference point (Ishibuchi et al. 2015)
+fn dominance_distance(point: &[f64], reference: &[f64]) -> f64 {
+    point.iter().zip(reference.iter()).map(|(p, r)| (p - r).max(0.0).powi(2)).sum::<f64>().sqrt()
+}
+
+// Mean over `from` of the distance to the closest point of `to`
+fn mean_closest_distance(from: &[Vec<f64>], to: &[Vec<f64>], distance: fn(&[f64], &[f64]) -> f64) -> f64 {
+    if from.is_empty() || to.is_empty() {
+        panic!("Indicators need a non-empty solution set and reference front");
+    }
+    from.iter()
+        .map(|point| to.iter().map(|other| distance(point, other)).fold(f64::INFINITY, f64::min))
+        .sum::<f64>()
+        / from.len() as f64
+}
+
+/// Mean distance from each point to the closest reference point
+pub fn generational_distance(points: &[Vec<f64>], reference_front: &[Vec<f64>]) -> f64 {
+    mean_closest_distance(points, reference_front, euclidean_distance)
+}
+
+/// Generational distance counting only the objectives on which a point is worse than the reference point
+pub fn generational_distance_plus(points: &[Vec<f64>], reference_front: &[Vec<f64>]) -> f64 {
+    mean_closest_distance(points, reference_front, dominance_distance)
+}
+
+/// Mean distance from each reference point to the closest point
+pub fn inverted_generational_distance(points: &[Vec<f64>], reference_front: &[Vec<f64>]) -> f64 {
+    mean_closest_distance(reference_front, points, euclidean_distance)
+}
+
+/// Inverted generational distance counting only the objectives on which a point is worse than the reference
+/// point; unlike IGD it is weakly Pareto compliant
+pub fn inverted_generational_distance_plus(points: &[Vec<f64>], reference_front: &[Vec<f64>]) -> f64 {
+    mean_closest_distance(reference_front, points, |reference, point| dominance_distance(point, reference))
+}
+
+// Smallest factor by which the points must be shifted so every reference point is weakly dominated; `shift`
+// receives the objective index, the point's value and the reference value
+fn epsilon(points: &[Vec<f64>], reference_front: &[Vec<f64>], shift: impl Fn(usize, f64, f64) -> f64) -> f64 {
+    if points.is_empty() || reference_front.is_empty() {
+        panic!("Indicators need a non-empty solution set and reference front");
+    }
+    reference_front
+        .iter()
+        .map(|reference| {
+            points
+                .iter()
+                .map(|point| {
+                    point
+                        .iter()
+                        .zip(reference.iter())
+                        .enumerate()
+                        .map(|(m, (p, r))| shift(m, *p, *r))
+                        .fold(f64::NEG_INFINITY, f64::max)
+                })
+                .fold(f64::INFINITY, f64::min)
+        })
+        .fold(f64::NEG_INFINITY, f64::max)
+}
+
+/// Smallest amount to subtract from every objective of the points so they weakly dominate the reference front
+pub fn additive_epsilon(points: &[Vec<f64>], reference_front: &[Vec<f64>]) -> f64 {
+    epsilon(points, reference_front, |_, p, r| p - r)
+}
+
+/// Smallest factor to divide every objective of the points by so they weakly dominate the reference front,
+/// all objectives minimized. Only defined for strictly positive objective values.
+pub fn multiplicative_epsilon(points: &[Vec<f64>], reference_front: &[Vec<f64>]) -> Result<f64> {
+    let maximized = vec![false; reference_front.first().map_or(0, Vec::len)];
+    ratio_epsilon(points, reference_front, &maximized)
+}
+
+// Multiplicative epsilon where maximized objectives must be multiplied rather than divided by the factor
+fn ratio_epsilon(points: &[Vec<f64>], reference_front: &[Vec<f64>], maximized: &[bool]) -> Result<f64> {
+    if points.is_empty() || reference_front.is_empty() {
+        return Err(Error::InvalidConfiguration("Indicators need a non-empty solution set and reference front"));
+    }
+    if points.iter().chain(reference_front.iter()).flatten().any(|&value| value <= 0.0 || value.is_nan()) {
+        return Err(Error::InvalidConfiguration("Multiplicative epsilon requires strictly positive objective values"));
+    }
+    Ok(epsilon(points, reference_front, |m, p, r| if maximized.get(m) == Some(&true) { r / p } else { p / r }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{evaluated_solution, setup_problem};
+
+    fn reference_front() -> Vec<Vec<f64>> {
+        vec![vec![0.0, 1.0], vec![0.5, 0.5], vec![1.0, 0.0]]
+    }
+
+    #[test]
+    fn test_distances_to_reference_front() {
+        let points = vec![vec![0.5, 1.0], vec![1.0, 0.5]];
+        let front = reference_front();
+        assert_eq!(generational_distance(&points, &front), 0.5);
+        assert_eq!(generational_distance_plus(&points, &front), 0.5);
+        assert_eq!(inverted_generational_distance(&points, &front), 0.5);
+        assert_eq!(inverted_generational_distance_plus(&points, &front), (0.5 + 0.5 + 0.5) / 3.0);
+        assert_eq!(additive_epsilon(&points, &front), 0.5);
+        // The reference front itself is a perfect approximation
+        assert_eq!(generational_distance(&front, &front), 0.0);
+        assert_eq!(inverted_generational_distance(&front, &front), 0.0);
+        assert_eq!(additive_epsilon(&front, &front), 0.0);
+    }
+
+    #[test]
+    fn test_plus_variants_ignore_better_objectives() {
+        // Dominates the reference front, so it is at no "dominance distance" from it
+        let points = vec![vec![-1.0, -1.0]];
+        let front = reference_front();
+        assert!(inverted_generational_distance(&points, &front) > 1.0);
+        assert_eq!(inverted_generational_distance_plus(&points, &front), 0.0);
+        assert_eq!(generational_distance_plus(&points, &front), 0.0);
+        assert_eq!(additive_epsilon(&points, &front), -1.0);
+    }
+
+    #[test]
+    fn test_multiplicative_epsilon() {
+        let front = vec![vec![1.0, 4.0], vec![2.0, 2.0], vec![4.0, 1.0]];
+        let points = vec![vec![2.0, 8.0], vec![4.0, 4.0], vec![8.0, 2.0]];
+        assert_eq!(multiplicative_epsilon(&points, &front).unwrap(), 2.0);
+        assert_eq!(multiplicative_epsilon(&front, &front).unwrap(), 1.0);
+    }
+
+    #[test]
+    fn test_multiplicative_epsilon_rejects_zero() {
+        let error = multiplicative_epsilon(&[vec![0.0, 1.0]], &reference_front()).unwrap_err();
+        assert_eq!(error.to_string(), "Multiplicative epsilon requires strictly positive objective values");
+    }
+
+    #[test]
+    fn test_multiplicative_epsilon_of_maximized_objective() {
+        // Second objective maximized: the points reach half of the reference value, so the factor is 2
+        let problem = setup_problem(vec![-1, 1]);
+        let front = ReferenceFront::new(vec![vec![1.0, 8.0], vec![2.0, 4.0], vec![4.0, 2.0]]);
+        let solutions = vec![
+            evaluated_solution(&problem, vec![1.0, 4.0]),
+            evaluated_solution(&problem, vec![2.0, 2.0]),
+            evaluated_solution(&problem, vec![4.0, 1.0]),
+        ];
+        let convergence = Convergence::new(&problem, &front, false);
+        assert_eq!(convergence.multiplicative_epsilon(&solutions).unwrap(), 2.0);
+        let exact: Vec<Solution> = front.points.iter().map(|point| evaluated_solution(&problem, point.clone())).collect();
+        assert_eq!(convergence.multiplicative_epsilon(&exact).unwrap(), 1.0);
+    }
+
+    #[test]
+    fn test_multiplicative_epsilon_ignores_normalization() {
+        // Normalization maps the front's minimum to 0, which must not reach the ratios
+        let problem = setup_problem(vec![-1, -1]);
+        let front = ReferenceFront::new(vec![vec![1.0, 4.0], vec![2.0, 2.0], vec![4.0, 1.0]]);
+        let solutions = vec![evaluated_solution(&problem, vec![2.0, 8.0]), evaluated_solution(&problem, vec![8.0, 2.0]), evaluated_solution(&problem, vec![4.0, 4.0])];
+        let normalized = Convergence::new(&problem, &front, true);
+        assert_eq!(normalized.reference_front[0][0], 0.0);
+        assert_eq!(normalized.multiplicative_epsilon(&solutions).unwrap(), 2.0);
+    }
+
+    #[test]
+    fn test_convergence_orients_and_normalizes() {
+        let problem = setup_problem(vec![-1, 1]);
+        // Second objective maximized and ten times larger
+        let front = ReferenceFront::new(vec![vec![0.0, 0.0], vec![0.5, 5.0], vec![1.0, 10.0]]);
+        let solutions = vec![evaluated_solution(&problem, vec![0.5, 0.0]), evaluated_solution(&problem, vec![1.0, 5.0])];
+        let raw = Convergence::new(&problem, &front, false);
+        assert_eq!(raw.additive_epsilon(&solutions), 5.0);
+        let normalized = Convergence::new(&problem, &front, true);
+        assert_eq!(normalized.reference_front, vec![vec![0.0, 1.0], vec![0.5, 0.5], vec![1.0, 0.0]]);
+        assert_eq!(normalized.additive_epsilon(&solutions), 0.5);
+        assert_eq!(normalized.generational_distance(&solutions), 0.5);
+        assert_eq!(normalized.inverted_generational_distance_plus(&solutions), 0.5);
+    }
+}
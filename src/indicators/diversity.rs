@@ -0,0 +1,237 @@
+use crate::core::{Problem, Solution};
+use crate::dominance::{Scalarizing, TchebycheffDominance};
+use crate::indicators::{bounds, oriented_front, ReferenceFront};
+use crate::math_utils::{euclidean_distance, manhattan_distance};
+
+/// Distribution indicators of a solution set. Spread and the normalized maximum spread need the reference front.
+#[derive(Debug, Clone)]
+pub struct Diversity {
+    pub reference_front: Option<Vec<Vec<f64>>>, // minimization form
+}
+
+impl Diversity {
+    pub fn new(problem: &Problem, reference_front: Option<&ReferenceFront>) -> Self {
+        Self { reference_front: reference_front.map(|front| front.oriented(problem)) }
+    }
+
+    fn reference_front(&self) -> &[Vec<f64>] {
+        match &self.reference_front {
+            Some(front) => front,
+            None => panic!("This indicator needs a reference front"),
+        }
+    }
+
+    pub fn spacing(&self, solutions: &[Solution]) -> f64 {
+        spacing(&oriented_front(solutions))
+    }
+
+    pub fn spread(&self, solutions: &[Solution]) -> f64 {
+        spread(&oriented_front(solutions), self.reference_front())
+    }
+
+    /// Maximum spread, normalized by the reference front when there is one
+    pub fn maximum_spread(&self, solutions: &[Solution]) -> f64 {
+        match &self.reference_front {
+            Some(front) => normalized_maximum_spread(&oriented_front(solutions), front),
+            None => maximum_spread(&oriented_front(solutions)),
+        }
+    }
+}
+
+/// R2 indicator: the mean over a set of weight vectors of the best Tchebycheff value reached by the solutions.
+/// Lower is better. The ideal point is given in the problem's own units and defaults to the origin.
+#[derive(Debug)]
+pub struct R2 {
+    pub utility_functions: Vec<TchebycheffDominance>,
+}
+
+impl R2 {
+    pub fn new(problem: &Problem, weights: Vec<Vec<f64>>, ideal_point: Option<Vec<f64>>) -> Self {
+        if weights.is_empty() {
+            panic!("R2 needs at least one weight vector");
+        }
+        let ideal_point = ideal_point
+            .map(|ideal| problem.oriented_objectives(&ideal))
+            .unwrap_or_else(|| vec![0.0; problem.number_of_objectives]);
+        Self {
+            utility_functions: weights
+                .into_iter()
+                .map(|weights| TchebycheffDominance::new(weights, Some(ideal_point.clone())))
+                .collect(),
+        }
+    }
+
+    pub fn calculate(&self, solutions: &[Solution]) -> f64 {
+        r2(&oriented_front(solutions), &self.utility_functions)
+    }
+}
+
+// Distance from every point to its nearest neighbour in the set
+fn nearest_neighbour_distances(points: &[Vec<f64>], distance: fn(&[f64], &[f64]) -> f64) -> Vec<f64> {
+    points
+        .iter()
+        .enumerate()
+        .map(|(i, point)| {
+            points
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, other)| distance(point, other))
+                .fold(f64::INFINITY, f64::min)
+        })
+        .collect()
+}
+
+fn closest_distance(point: &[f64], points: &[Vec<f64>]) -> f64 {
+    points.iter().map(|other| euclidean_distance(point, other)).fold(f64::INFINITY, f64::min)
+}
+
+/// Schott's spacing: standard deviation of the Manhattan distances to the nearest neighbour. 0 means evenly spaced.
+pub fn spacing(points: &[Vec<f64>]) -> f64 {
+    if points.len() < 2 {
+        return 0.0;
+    }
+    let distances = nearest_neighbour_distances(points, manhattan_distance);
+    let mean = distances.iter().sum::<f64>() / distances.len() as f64;
+    (distances.iter().map(|d| (d - mean).powi(2)).sum::<f64>() / (distances.len() - 1) as f64).sqrt()
+}
+
+/// Deb's spread Δ: how evenly the points cover the front between its extremes, which are taken from the
+/// reference front. 0 means evenly spaced points reaching both extremes. For two objectives the gaps between
+/// consecutive points are used (Deb et al. 2002); above that the nearest neighbour distances (Zhou et al. 2006).
+pub fn spread(points: &[Vec<f64>], reference_front: &[Vec<f64>]) -> f64 {
+    if points.is_empty() || reference_front.is_empty() {
+        panic!("Indicators need a non-empty solution set and reference front");
+    }
+    let number_of_objectives = reference_front[0].len();
+    // Best reference point on every objective, ties broken lexicographically
+    let extremes: Vec<&Vec<f64>> = (0..number_of_objectives)
+        .map(|m| {
+            reference_front
+                .iter()
+                .min_by(|a, b| a[m].total_cmp(&b[m]).then_with(|| a.partial_cmp(b).unwrap()))
+                .unwrap()
+        })
+        .collect();
+    let (extreme_distance, distances) = if number_of_objectives == 2 {
+        let mut sorted: Vec<&Vec<f64>> = points.iter().collect();
+        sorted.sort_by(|a, b| a[0].total_cmp(&b[0]));
+        let extreme_distance = euclidean_distance(extremes[0], sorted[0])
+            + euclidean_distance(extremes[1], sorted[sorted.len() - 1]);
+        let gaps: Vec<f64> = sorted.windows(2).map(|pair| euclidean_distance(pair[0], pair[1])).collect();
+        (extreme_distance, gaps)
+    } else {
+        let extreme_distance = extremes.iter().map(|extreme| closest_distance(extreme, points)).sum();
+        let distances = if points.len() < 2 { Vec::new() } else { nearest_neighbour_distances(points, euclidean_distance) };
+        (extreme_distance, distances)
+    };
+    let mean = if distances.is_empty() { 0.0 } else { distances.iter().sum::<f64>() / distances.len() as f64 };
+    let denominator = extreme_distance + distances.len() as f64 * mean;
+    if denominator == 0.0 {
+        return 0.0;
+    }
+    (extreme_distance + distances.iter().map(|d| (d - mean).abs()).sum::<f64>()) / denominator
+}
+
+/// Zitzler's maximum spread: the diagonal of the bounding box of the points
+pub fn maximum_spread(points: &[Vec<f64>]) -> f64 {
+    if points.is_empty() {
+        return 0.0;
+    }
+    let (ideal, nadir) = bounds(points);
+    euclidean_distance(&nadir, &ideal)
+}
+
+/// Maximum spread relative to the reference front: the root mean square over the objectives of the share of
+/// the reference front's range the points cover. 1 means the whole range is covered.
+pub fn normalized_maximum_spread(points: &[Vec<f64>], reference_front: &[Vec<f64>]) -> f64 {
+    if points.is_empty() {
+        return 0.0;
+    }
+    let (ideal, nadir) = bounds(points);
+    let (reference_ideal, reference_nadir) = bounds(reference_front);
+    let coverage: f64 = (0..ideal.len())
+        .map(|m| {
+            let range = reference_nadir[m] - reference_ideal[m];
+            if range <= 0.0 {
+                return 1.0;
+            }
+            let overlap = nadir[m].min(reference_nadir[m]) - ideal[m].max(reference_ideal[m]);
+            (overlap.max(0.0) / range).powi(2)
+        })
+        .sum();
+    (coverage / ideal.len() as f64).sqrt()
+}
+
+/// Mean over the utility functions of the best value any point reaches
+pub fn r2<S: Scalarizing>(points: &[Vec<f64>], utility_functions: &[S]) -> f64 {
+    if points.is_empty() {
+        panic!("Indicators need a non-empty solution set and reference front");
+    }
+    utility_functions
+        .iter()
+        .map(|utility| points.iter().map(|point| utility.scalarize(point)).fold(f64::INFINITY, f64::min))
+        .sum::<f64>()
+        / utility_functions.len() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{evaluated_solution, setup_problem};
+
+    fn reference_front() -> Vec<Vec<f64>> {
+        vec![vec![0.0, 2.0], vec![1.0, 1.0], vec![2.0, 0.0]]
+    }
+
+    #[test]
+    fn test_spacing() {
+        assert_eq!(spacing(&reference_front()), 0.0);
+        let uneven = vec![vec![0.0, 3.0], vec![1.0, 2.0], vec![3.0, 0.0]];
+        assert!((spacing(&uneven) - (4.0_f64 / 3.0).sqrt()).abs() < 1e-12);
+        assert_eq!(spacing(&[vec![1.0, 1.0]]), 0.0);
+    }
+
+    #[test]
+    fn test_spread() {
+        let front = reference_front();
+        assert_eq!(spread(&front, &front), 0.0);
+        // Evenly spaced but short of both extremes
+        let inner = vec![vec![0.5, 1.5], vec![1.5, 0.5]];
+        assert!((spread(&inner, &front) - 0.5).abs() < 1e-12);
+        let corners = vec![vec![1.0, 0.0, 0.0], vec![0.0, 1.0, 0.0], vec![0.0, 0.0, 1.0]];
+        assert_eq!(spread(&corners, &corners), 0.0);
+        assert!(spread(&corners[..2], &corners) > 0.0);
+    }
+
+    #[test]
+    fn test_maximum_spread() {
+        let inner = vec![vec![0.5, 1.5], vec![1.5, 0.5]];
+        assert!((maximum_spread(&inner) - 2.0_f64.sqrt()).abs() < 1e-12);
+        assert_eq!(normalized_maximum_spread(&inner, &reference_front()), 0.5);
+        assert_eq!(normalized_maximum_spread(&reference_front(), &reference_front()), 1.0);
+    }
+
+    #[test]
+    fn test_r2() {
+        let problem = setup_problem(vec![-1, -1]);
+        let weights = vec![vec![1.0, 0.0], vec![0.0, 1.0], vec![0.5, 0.5]];
+        let indicator = R2::new(&problem, weights, None);
+        let extremes = vec![evaluated_solution(&problem, vec![0.0, 2.0]), evaluated_solution(&problem, vec![2.0, 0.0])];
+        assert!((indicator.calculate(&extremes) - 1.0 / 3.0).abs() < 1e-12);
+        let mut front = extremes.clone();
+        front.push(evaluated_solution(&problem, vec![1.0, 1.0]));
+        assert!((indicator.calculate(&front) - 1.0 / 6.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_diversity_orients_objectives() {
+        let problem = setup_problem(vec![-1, 1]);
+        let front = ReferenceFront::new(vec![vec![0.0, -2.0], vec![1.0, -1.0], vec![2.0, 0.0]]);
+        let diversity = Diversity::new(&problem, Some(&front));
+        let solutions = vec![evaluated_solution(&problem, vec![0.5, -1.5]), evaluated_solution(&problem, vec![1.5, -0.5])];
+        assert!((diversity.spread(&solutions) - 0.5).abs() < 1e-12);
+        assert_eq!(diversity.maximum_spread(&solutions), 0.5);
+        assert_eq!(Diversity::new(&problem, None).spacing(&solutions), 0.0);
+    }
+}
@@ -0,0 +1,432 @@
+use std::collections::BTreeMap;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use crate::core::{Problem, Solution};
+use crate::error::{Error, Result};
+use crate::indicators::oriented_front;
+use crate::math_utils::OrderedF64;
+
+// Two-sided 95% quantile of the standard normal distribution
+const Z_95: f64 = 1.959963984540054;
+
+/// Hypervolume indicator: the volume of objective space dominated by a set of solutions and bounded by a
+/// reference point. Objectives and the reference point are oriented with the problem's direction first,
+/// so the reference point is given in the problem's own units and must be worse than the front on every objective.
+#[derive(Debug, Clone)]
+pub struct Hypervolume {
+    pub reference_point: Vec<f64>, // minimization form
+}
+
+/// Monte Carlo estimate of the hypervolume with its 95% confidence interval
+#[derive(Debug, Clone, PartialEq)]
+pub struct HypervolumeEstimate {
+    pub value: f64,
+    pub standard_error: f64,
+    pub lower: f64,
+    pub upper: f64,
+    pub samples: usize,
+}
+
+impl Hypervolume {
+    pub fn new(problem: &Problem, reference_point: Vec<f64>) -> Self {
+        Self::try_new(problem, reference_point).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Like `new`, but reports a reference point of the wrong length as an error
+    pub fn try_new(problem: &Problem, reference_point: Vec<f64>) -> Result<Self> {
+        if reference_point.len() != problem.number_of_objectives {
+            return Err(Error::LengthMismatch {
+                what: "Reference point length does not match number_of_objectives",
+                expected: problem.number_of_objectives,
+                found: reference_point.len(),
+            });
+        }
+        Ok(Self { reference_point: problem.oriented_objectives(&reference_point) })
+    }
+
+    /// Exact hypervolume of the solutions
+    pub fn calculate(&self, solutions: &[Solution]) -> f64 {
+        hypervolume(&oriented_front(solutions), &self.reference_point)
+    }
+
+    /// Hypervolume each solution alone accounts for, in the order given. Dominated and duplicated
+    /// solutions contribute nothing.
+    pub fn contributions(&self, solutions: &[Solution]) -> Vec<f64> {
+        exclusive_contributions(&oriented_front(solutions), &self.reference_point)
+    }
+
+    /// Monte Carlo estimate, for fronts with too many objectives for the exact algorithms (10 and up)
+    pub fn estimate(&self, solutions: &[Solution], samples: usize, seed: Option<u64>) -> HypervolumeEstimate {
+        let mut rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        monte_carlo_hypervolume(&oriented_front(solutions), &self.reference_point, samples, &mut rng)
+    }
+}
+
+/// Exact hypervolume of points in minimization form. Points not strictly better than the reference point on
+/// every objective are ignored. Uses a sweep for 2 objectives, HV3D for 3, a sweep over HV3D for 4 and
+/// WFG (While et al. 2012) above that.
+pub fn hypervolume(points: &[Vec<f64>], reference: &[f64]) -> f64 {
+    let points: Vec<&[f64]> = points
+        .iter()
+        .filter(|point| point.iter().zip(reference.iter()).all(|(p, r)| p < r))
+        .map(|point| point.as_slice())
+        .collect();
+    volume(points, reference)
+}
+
+/// Hypervolume dominated by each point alone, i.e. the loss if it were removed from the set
+pub fn exclusive_contributions(points: &[Vec<f64>], reference: &[f64]) -> Vec<f64> {
+    points
+        .iter()
+        .enumerate()
+        .map(|(i, point)| {
+            if !point.iter().zip(reference.iter()).all(|(p, r)| p < r) {
+                return 0.0;
+            }
+            let limited: Vec<Vec<f64>> = points
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, other)| limit(other, point))
+                .collect();
+            (box_volume(point, reference) - hypervolume(&limited, reference)).max(0.0)
+        })
+        .collect()
+}
+
+/// Estimates the hypervolume by sampling the box between the ideal point of the set and the reference point
+pub fn monte_carlo_hypervolume(points: &[Vec<f64>], reference: &[f64], samples: usize, rng: &mut StdRng) -> HypervolumeEstimate {
+    if samples == 0 {
+        panic!("Number of samples must be greater than zero");
+    }
+    let points: Vec<&Vec<f64>> = points
+        .iter()
+        .filter(|point| point.iter().zip(reference.iter()).all(|(p, r)| p < r))
+        .collect();
+    if points.is_empty() {
+        return HypervolumeEstimate { value: 0.0, standard_error: 0.0, lower: 0.0, upper: 0.0, samples };
+    }
+    let ideal: Vec<f64> = (0..reference.len())
+        .map(|m| points.iter().map(|p| p[m]).fold(f64::INFINITY, f64::min))
+        .collect();
+    let sampled_volume = box_volume(&ideal, reference);
+    let mut sample = vec![0.0; reference.len()];
+    let mut hits = 0usize;
+    for _ in 0..samples {
+        for (m, value) in sample.iter_mut().enumerate() {
+            *value = rng.gen_range(ideal[m]..reference[m]);
+        }
+        if points.iter().any(|p| p.iter().zip(sample.iter()).all(|(a, b)| a <= b)) {
+            hits += 1;
+        }
+    }
+    let fraction = hits as f64 / samples as f64;
+    let standard_error = sampled_volume * (fraction * (1.0 - fraction) / samples as f64).sqrt();
+    let value = sampled_volume * fraction;
+    HypervolumeEstimate {
+        value,
+        standard_error,
+        lower: (value - Z_95 * standard_error).max(0.0),
+        upper: (value + Z_95 * standard_error).min(sampled_volume),
+        samples,
+    }
+}
+
+fn box_volume(point: &[f64], reference: &[f64]) -> f64 {
+    point.iter().zip(reference.iter()).map(|(p, r)| r - p).product()
+}
+
+// Worst of the two points on every objective: the part of `other` that overlaps the box of `point`
+fn limit(other: &[f64], point: &[f64]) -> Vec<f64> {
+    other.iter().zip(point.iter()).map(|(a, b)| a.max(*b)).collect()
+}
+
+// Hypervolume of points already known to lie strictly inside the reference box
+fn volume(points: Vec<&[f64]>, reference: &[f64]) -> f64 {
+    if points.is_empty() {
+        return 0.0;
+    }
+    match reference.len() {
+        1 => reference[0] - points.iter().map(|p| p[0]).fold(f64::INFINITY, f64::min),
+        2 => hypervolume_2d(points, reference),
+        3 => hypervolume_3d(points, reference),
+        4 => hypervolume_4d(points, reference),
+        _ => wfg(points, reference),
+    }
+}
+
+fn hypervolume_2d(mut points: Vec<&[f64]>, reference: &[f64]) -> f64 {
+    points.sort_by(|a, b| a[0].total_cmp(&b[0]).then(a[1].total_cmp(&b[1])));
+    let mut area = 0.0;
+    let mut ceiling = reference[1];
+    for point in points {
+        if point[1] < ceiling {
+            area += (reference[0] - point[0]) * (ceiling - point[1]);
+            ceiling = point[1];
+        }
+    }
+    area
+}
+
+// Sweeps along the third objective, keeping the dominated area of the 2D staircase up to date
+// (Beume et al. 2009), O(n log n)
+fn hypervolume_3d(mut points: Vec<&[f64]>, reference: &[f64]) -> f64 {
+    points.sort_by(|a, b| a[2].total_cmp(&b[2]));
+    let mut staircase = Staircase::new(reference[0], reference[1]);
+    let mut volume = 0.0;
+    for (i, point) in points.iter().enumerate() {
+        staircase.insert(point[0], point[1]);
+        let upper = if i + 1 < points.len() { points[i + 1][2] } else { reference[2] };
+        volume += staircase.area * (upper - point[2]);
+    }
+    volume
+}
+
+// Sweeps along the fourth objective, keeping the non-dominated projections of the points swept so far and
+// computing the 3D volume of every slice from them: O(n) slices of O(n log n) each, O(n^2 log n) in total
+fn hypervolume_4d(mut points: Vec<&[f64]>, reference: &[f64]) -> f64 {
+    points.sort_by(|a, b| a[3].total_cmp(&b[3]));
+    let mut front: Vec<&[f64]> = Vec::new();
+    let mut volume = 0.0;
+    for i in 0..points.len() {
+        let projection = &points[i][..3];
+        if !front.iter().any(|other| weakly_dominates(other, projection)) {
+            front.retain(|other| !weakly_dominates(projection, other));
+            front.push(projection);
+        }
+        let upper = if i + 1 < points.len() { points[i + 1][3] } else { reference[3] };
+        if upper > points[i][3] {
+            volume += hypervolume_3d(front.clone(), &reference[..3]) * (upper - points[i][3]);
+        }
+    }
+    volume
+}
+
+// WFG: with the points sorted from worst to best on the last objective, the hypervolume is the sum of each
+// point's exclusive volume over the points after it. Limiting those to the point's box makes them share its
+// last objective, so the exclusive volume is a slab whose section is one dimension lower.
+fn wfg(points: Vec<&[f64]>, reference: &[f64]) -> f64 {
+    let mut points = nondominated(points);
+    let last = reference.len() - 1;
+    points.sort_by(|a, b| b[last].total_cmp(&a[last]));
+    let mut volume = 0.0;
+    for (i, point) in points.iter().enumerate() {
+        let limited: Vec<Vec<f64>> = points[i + 1..].iter().map(|other| limit(&other[..last], &point[..last])).collect();
+        let section = box_volume(&point[..last], &reference[..last])
+            - volume_of(&limited, &reference[..last]);
+        volume += (reference[last] - point[last]) * section;
+    }
+    volume
+}
+
+fn volume_of(points: &[Vec<f64>], reference: &[f64]) -> f64 {
+    volume(points.iter().map(|p| p.as_slice()).collect(), reference)
+}
+
+fn weakly_dominates(a: &[f64], b: &[f64]) -> bool {
+    a.iter().zip(b.iter()).all(|(x, y)| x <= y)
+}
+
+// Drops points weakly dominated by another one, keeping the first of any duplicates
+fn nondominated(points: Vec<&[f64]>) -> Vec<&[f64]> {
+    points
+        .iter()
+        .enumerate()
+        .filter(|(i, point)| {
+            !points.iter().enumerate().any(|(j, other)| {
+                j != *i && weakly_dominates(other, point) && (other != *point || j < *i)
+            })
+        })
+        .map(|(_, point)| *point)
+        .collect()
+}
+
+// Non-dominated 2D points sorted by the first objective (the second one then decreases) together with the
+// area they dominate up to the reference point
+struct Staircase {
+    steps: BTreeMap<OrderedF64, f64>,
+    reference: (f64, f64),
+    area: f64,
+}
+
+impl Staircase {
+    fn new(reference_x: f64, reference_y: f64) -> Self {
+        Self { steps: BTreeMap::new(), reference: (reference_x, reference_y), area: 0.0 }
+    }
+
+    fn insert(&mut self, x: f64, y: f64) {
+        if let Some((_, &previous_y)) = self.steps.range(..=OrderedF64(x)).next_back() {
+            if previous_y <= y {
+                return;
+            }
+        }
+        // Height of the staircase just left of x; walking right, every dominated step lowers it
+        let mut ceiling = self
+            .steps
+            .range(..OrderedF64(x))
+            .next_back()
+            .map(|(_, &previous_y)| previous_y)
+            .unwrap_or(self.reference.1);
+        let mut left = x;
+        let mut dominated = Vec::new();
+        let mut right = self.reference.0;
+        for (&step_x, &step_y) in self.steps.range(OrderedF64(x)..) {
+            if step_y < y {
+                right = step_x.0;
+                break;
+            }
+            self.area += (step_x.0 - left) * (ceiling - y);
+            left = step_x.0;
+            ceiling = step_y;
+            dominated.push(step_x);
+        }
+        self.area += (right - left) * (ceiling - y);
+        for step_x in dominated {
+            self.steps.remove(&step_x);
+        }
+        self.steps.insert(OrderedF64(x), y);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{evaluated_solution, setup_problem};
+
+    // Slicing along every objective in turn (HSO), slow but straightforward
+    fn slicing_hypervolume(points: &[Vec<f64>], reference: &[f64]) -> f64 {
+        let mut points: Vec<&Vec<f64>> = points
+            .iter()
+            .filter(|point| point.iter().zip(reference.iter()).all(|(p, r)| p < r))
+            .collect();
+        if points.is_empty() {
+            return 0.0;
+        }
+        if reference.len() == 1 {
+            return reference[0] - points.iter().map(|p| p[0]).fold(f64::INFINITY, f64::min);
+        }
+        let last = reference.len() - 1;
+        points.sort_by(|a, b| a[last].total_cmp(&b[last]));
+        let mut volume = 0.0;
+        for i in 0..points.len() {
+            let upper = if i + 1 < points.len() { points[i + 1][last] } else { reference[last] };
+            let slice: Vec<Vec<f64>> = points[..=i].iter().map(|p| p[..last].to_vec()).collect();
+            volume += (upper - points[i][last]) * slicing_hypervolume(&slice, &reference[..last]);
+        }
+        volume
+    }
+
+    fn random_points(number_of_points: usize, number_of_objectives: usize, seed: u64) -> Vec<Vec<f64>> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..number_of_points)
+            .map(|_| (0..number_of_objectives).map(|_| (rng.gen_range(0.0..1.0) * 8.0_f64).round() / 8.0).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_known_volumes() {
+        let points = vec![vec![1.0, 3.0], vec![2.0, 2.0], vec![3.0, 1.0]];
+        assert_eq!(hypervolume(&points, &[4.0, 4.0]), 6.0);
+        assert_eq!(hypervolume(&[vec![0.0, 0.0, 0.0]], &[2.0, 2.0, 2.0]), 8.0);
+        let points = vec![vec![1.0, 2.0, 2.0], vec![2.0, 1.0, 2.0], vec![2.0, 2.0, 1.0]];
+        assert_eq!(hypervolume(&points, &[3.0, 3.0, 3.0]), 4.0);
+        assert_eq!(hypervolume(&[vec![0.0; 6]], &[1.0; 6]), 1.0);
+        // Points outside the reference box are ignored
+        assert_eq!(hypervolume(&[vec![5.0, 0.0], vec![1.0, 1.0]], &[2.0, 2.0]), 1.0);
+        assert_eq!(hypervolume(&[], &[1.0, 1.0]), 0.0);
+    }
+
+    #[test]
+    fn test_exact_algorithms_agree_with_slicing() {
+        for number_of_objectives in 1..=6 {
+            for seed in 0..5 {
+                let points = random_points(25, number_of_objectives, seed);
+                let reference = vec![1.1; number_of_objectives];
+                let expected = slicing_hypervolume(&points, &reference);
+                let actual = hypervolume(&points, &reference);
+                assert!((expected - actual).abs() < 1e-9, "{} objectives: {} != {}", number_of_objectives, actual, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_staircase_area() {
+        let mut staircase = Staircase::new(4.0, 4.0);
+        staircase.insert(2.0, 2.0);
+        assert_eq!(staircase.area, 4.0);
+        staircase.insert(3.0, 3.0);
+        assert_eq!(staircase.area, 4.0);
+        staircase.insert(1.0, 3.0);
+        assert_eq!(staircase.area, 5.0);
+        staircase.insert(3.0, 1.0);
+        assert_eq!(staircase.area, 6.0);
+        // Dominates every step
+        staircase.insert(0.0, 0.0);
+        assert_eq!(staircase.area, 16.0);
+        assert_eq!(staircase.steps.len(), 1);
+    }
+
+    #[test]
+    fn test_exclusive_contributions() {
+        let points = vec![vec![1.0, 3.0], vec![2.0, 2.0], vec![3.0, 1.0], vec![2.5, 2.5], vec![2.0, 2.0]];
+        let contributions = exclusive_contributions(&points, &[4.0, 4.0]);
+        assert_eq!(contributions, vec![1.0, 0.0, 1.0, 0.0, 0.0]);
+        for number_of_objectives in 2..=5 {
+            let points = random_points(15, number_of_objectives, 7);
+            let reference = vec![1.1; number_of_objectives];
+            let total = hypervolume(&points, &reference);
+            for (i, contribution) in exclusive_contributions(&points, &reference).iter().enumerate() {
+                let mut others = points.clone();
+                others.remove(i);
+                assert!((total - hypervolume(&others, &reference) - contribution).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_hypervolume_orients_objectives() {
+        let problem = setup_problem(vec![-1, 1]);
+        let solutions = vec![
+            evaluated_solution(&problem, vec![1.0, 3.0]),
+            evaluated_solution(&problem, vec![2.0, 4.0]),
+            evaluated_solution(&problem, vec![3.0, 5.0]),
+        ];
+        // Maximizing the second objective mirrors the front of test_known_volumes
+        let indicator = Hypervolume::new(&problem, vec![4.0, 2.0]);
+        assert_eq!(indicator.calculate(&solutions), 6.0);
+        assert_eq!(indicator.contributions(&solutions), vec![1.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Reference point length does not match number_of_objectives")]
+    fn test_reference_point_length() {
+        let problem = setup_problem(vec![-1, -1]);
+        Hypervolume::new(&problem, vec![1.0]);
+    }
+
+    #[test]
+    fn test_try_new() {
+        let problem = setup_problem(vec![-1, -1]);
+        assert!(matches!(Hypervolume::try_new(&problem, vec![1.0]), Err(Error::LengthMismatch { expected: 2, found: 1, .. })));
+        assert!(Hypervolume::try_new(&problem, vec![1.0, 1.0]).is_ok());
+    }
+
+    #[test]
+    fn test_monte_carlo_estimate() {
+        let problem = setup_problem(vec![-1; 10]);
+        let solutions: Vec<Solution> = random_points(30, 10, 3)
+            .into_iter()
+            .map(|point| evaluated_solution(&problem, point))
+            .collect();
+        let indicator = Hypervolume::new(&problem, vec![1.1; 10]);
+        let estimate = indicator.estimate(&solutions, 20_000, Some(42));
+        assert_eq!(estimate, indicator.estimate(&solutions, 20_000, Some(42)));
+        assert!(estimate.lower <= estimate.value && estimate.value <= estimate.upper);
+        let points: Vec<Vec<f64>> = solutions.iter().map(|s| s.objective_fitness_values.clone()).collect();
+        let exact = hypervolume(&points, &[1.1; 10]);
+        assert!((estimate.value - exact).abs() < 4.0 * estimate.standard_error, "{:?} vs {}", estimate, exact);
+    }
+}
@@ -0,0 +1,118 @@
+pub mod hypervolume;
+pub mod convergence;
+pub mod diversity;
+
+use std::fs;
+use std::io;
+use std::path::Path;
+use crate::core::{Problem, Solution};
+use crate::error::{Error, Result};
+
+/// Objective vectors of the solutions in minimization form, as every indicator works on them
+pub fn oriented_front(solutions: &[Solution]) -> Vec<Vec<f64>> {
+    solutions
+        .iter()
+        .map(|solution| solution.problem.oriented_objectives(&solution.objective_fitness_values))
+        .collect()
+}
+
+/// Known Pareto front the indicators measure a solution set against, in the problem's own units
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReferenceFront {
+    pub points: Vec<Vec<f64>>,
+}
+
+impl ReferenceFront {
+    pub fn new(points: Vec<Vec<f64>>) -> Self {
+        Self::try_new(points).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Like `new`, but reports an empty or ragged front as an error
+    pub fn try_new(points: Vec<Vec<f64>>) -> Result<Self> {
+        if points.is_empty() {
+            return Err(Error::InvalidConfiguration("Reference front must not be empty"));
+        }
+        if points.iter().any(|point| point.len() != points[0].len()) {
+            return Err(Error::InvalidConfiguration("Reference front points must all have the same number of objectives"));
+        }
+        Ok(Self { points })
+    }
+
+    /// Reads one point per line with the objectives separated by whitespace or commas.
+    /// Blank lines and lines starting with '#' are skipped.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let mut points = Vec::new();
+        for (number, line) in fs::read_to_string(path)?.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let point = line
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|value| !value.is_empty())
+                .map(|value| value.parse::<f64>())
+                .collect::<std::result::Result<Vec<f64>, _>>()
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, format!("line {}: {}", number + 1, error)))?;
+            points.push(point);
+        }
+        if points.is_empty() || points.iter().any(|point| point.len() != points[0].len()) {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "reference front is empty or ragged"));
+        }
+        Ok(Self { points })
+    }
+
+    pub fn number_of_objectives(&self) -> usize {
+        self.points[0].len()
+    }
+
+    /// Points in minimization form
+    pub fn oriented(&self, problem: &Problem) -> Vec<Vec<f64>> {
+        if self.number_of_objectives() != problem.number_of_objectives {
+            panic!("Reference front does not match number_of_objectives");
+        }
+        self.points.iter().map(|point| problem.oriented_objectives(point)).collect()
+    }
+}
+
+// Best and worst value of every objective over the points
+fn bounds(points: &[Vec<f64>]) -> (Vec<f64>, Vec<f64>) {
+    let number_of_objectives = points[0].len();
+    let ideal = (0..number_of_objectives)
+        .map(|m| points.iter().map(|p| p[m]).fold(f64::INFINITY, f64::min))
+        .collect();
+    let nadir = (0..number_of_objectives)
+        .map(|m| points.iter().map(|p| p[m]).fold(f64::NEG_INFINITY, f64::max))
+        .collect();
+    (ideal, nadir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn test_reference_front_from_file() {
+        let path = env::temp_dir().join("rustypus_reference_front.txt");
+        fs::write(&path, "# f1 f2\n0.0 1.0\n0.5, 0.5\n\n1.0\t0.0\n").unwrap();
+        let front = ReferenceFront::from_file(&path).unwrap();
+        assert_eq!(front.points, vec![vec![0.0, 1.0], vec![0.5, 0.5], vec![1.0, 0.0]]);
+        fs::write(&path, "0.0 1.0\n0.5 x\n").unwrap();
+        let error = ReferenceFront::from_file(&path).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Reference front points must all have the same number of objectives")]
+    fn test_ragged_reference_front() {
+        ReferenceFront::new(vec![vec![0.0, 1.0], vec![1.0]]);
+    }
+
+    #[test]
+    fn test_try_new() {
+        assert!(ReferenceFront::try_new(Vec::new()).is_err());
+        assert!(ReferenceFront::try_new(vec![vec![0.0, 1.0], vec![1.0]]).is_err());
+        assert_eq!(ReferenceFront::try_new(vec![vec![0.0, 1.0]]).unwrap().number_of_objectives(), 2);
+    }
+}
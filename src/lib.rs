@@ -3,10 +3,22 @@ pub mod genetic_algorithms_v2;
 pub mod benchmark_objective_functions;
 pub mod gatypes;
 pub mod constraints;
+pub mod conditions;
+pub mod expression_tree;
+pub mod metadata;
+pub mod error;
+pub mod seeds;
 pub mod core;
 pub mod dominance; 
+pub mod nondominated_sort;
+pub mod archive;
+pub mod indicators;
 // pub mod genetic_operators;
 pub mod math_utils;
+pub mod reference_directions;
+pub mod normalization;
 // pub mod genetic_algorithms;
 pub mod genetic_operators;
+#[cfg(test)]
+pub(crate) mod test_utils;
 
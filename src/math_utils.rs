@@ -1,3 +1,5 @@
+use std::cmp::Ordering;
+
 pub fn subtract(a: &[f64], b: &[f64]) -> Vec<f64> {
     a.iter().zip(b.iter()).map(|(ai, bi)| ai - bi).collect()
 }
@@ -14,6 +16,14 @@ pub fn magnitude(vec: &[f64]) -> f64 {
     vec.iter().map(|&vi| vi * vi).sum::<f64>().sqrt()
 }
 
+pub fn euclidean_distance(a: &[f64], b: &[f64]) -> f64 {
+    magnitude(&subtract(a, b))
+}
+
+pub fn manhattan_distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b.iter()).map(|(ai, bi)| (ai - bi).abs()).sum()
+}
+
 pub fn normalize(vec: &[f64]) -> Vec<f64> {
     let mag = magnitude(vec);
     vec.iter().map(|&vi| vi / mag).collect()
@@ -52,6 +62,59 @@ pub fn clip(value: f64, lower: f64, upper: f64) -> f64 {
     }
 }
 
+/// Solves `matrix * x = rhs` by Gaussian elimination with partial pivoting; None when the matrix is singular
+pub fn solve_linear_system(matrix: &[Vec<f64>], rhs: &[f64]) -> Option<Vec<f64>> {
+    let n = rhs.len();
+    let mut augmented: Vec<Vec<f64>> = matrix
+        .iter()
+        .zip(rhs.iter())
+        .map(|(row, &value)| {
+            let mut row = row.clone();
+            row.push(value);
+            row
+        })
+        .collect();
+    for column in 0..n {
+        let pivot = (column..n).max_by(|&a, &b| augmented[a][column].abs().total_cmp(&augmented[b][column].abs()))?;
+        if augmented[pivot][column].abs() < 1e-12 {
+            return None;
+        }
+        augmented.swap(column, pivot);
+        let (upper, lower) = augmented.split_at_mut(column + 1);
+        let pivot_row = &upper[column];
+        for row in lower.iter_mut() {
+            let factor = row[column] / pivot_row[column];
+            for (value, pivot_value) in row[column..].iter_mut().zip(pivot_row[column..].iter()) {
+                *value -= factor * pivot_value;
+            }
+        }
+    }
+    let mut solution = vec![0.0; n];
+    for row in (0..n).rev() {
+        let known: f64 = ((row + 1)..n).map(|k| augmented[row][k] * solution[k]).sum();
+        solution[row] = (augmented[row][n] - known) / augmented[row][row];
+    }
+    Some(solution)
+}
+
+/// Totally ordered f64, for use as a sorted map key
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct OrderedF64(pub(crate) f64);
+
+impl Eq for OrderedF64 {}
+
+impl PartialOrd for OrderedF64 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OrderedF64 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
 // UnitTests
 #[cfg(test)]
 mod tests {
@@ -88,6 +151,24 @@ mod tests {
         assert_eq!(result, 3.7416573867739413);
     }
 
+    #[test]
+    fn test_solve_linear_system() {
+        let matrix = vec![vec![0.0, 2.0, 1.0], vec![1.0, 1.0, 0.0], vec![3.0, 0.0, 1.0]];
+        let solution = solve_linear_system(&matrix, &[5.0, 3.0, 6.0]).unwrap();
+        for (value, expected) in solution.iter().zip([1.4, 1.6, 1.8]) {
+            assert!((value - expected).abs() < 1e-12);
+        }
+        assert_eq!(solve_linear_system(&[vec![1.0, 2.0], vec![2.0, 4.0]], &[1.0, 2.0]), None);
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = vec![1.0, 2.0];
+        let b = vec![4.0, -2.0];
+        assert_eq!(euclidean_distance(&a, &b), 5.0);
+        assert_eq!(manhattan_distance(&a, &b), 7.0);
+    }
+
     #[test]
     fn test_normalize() {
         let vec = vec![1.0, 2.0, 3.0];
@@ -119,7 +200,7 @@ mod tests {
     #[test]
     fn test_is_zero() {
         let vec = vec![0.0, 0.0, 0.0];
-        assert_eq!(is_zero(&vec), true);
+        assert!(is_zero(&vec));
     }
 
     // Test clip
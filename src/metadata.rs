@@ -0,0 +1,74 @@
+// Names, units and descriptions of the variables and objectives of a problem, used to look values up by
+// name and to label solutions wherever they are printed or exported.
+use crate::error::{Error, Result};
+
+/// Name, unit and description of a variable or objective
+#[derive(Debug, Clone, PartialEq)]
+pub struct Metadata {
+    pub name: String,
+    pub unit: Option<String>,
+    pub description: Option<String>,
+}
+
+impl Metadata {
+    pub fn new(name: &str) -> Self {
+        Self::try_new(name).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Like `new`, but reports an empty name as an error
+    pub fn try_new(name: &str) -> Result<Self> {
+        if name.is_empty() {
+            return Err(Error::InvalidConfiguration("Variable and objective names must not be empty"));
+        }
+        Ok(Self { name: name.to_string(), unit: None, description: None })
+    }
+
+    pub fn with_unit(mut self, unit: &str) -> Self {
+        self.unit = Some(unit.to_string());
+        self
+    }
+
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_string());
+        self
+    }
+
+    /// Name followed by the unit, if any, e.g. `thickness [mm]`
+    pub fn label(&self) -> String {
+        match &self.unit {
+            Some(unit) => format!("{} [{}]", self.name, unit),
+            None => self.name.clone(),
+        }
+    }
+}
+
+impl From<&str> for Metadata {
+    fn from(name: &str) -> Self {
+        Metadata::new(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metadata() {
+        let thickness = Metadata::new("thickness").with_unit("mm").with_description("Skin thickness");
+        assert_eq!(thickness.label(), "thickness [mm]");
+        assert_eq!(thickness.description.as_deref(), Some("Skin thickness"));
+        assert_eq!(Metadata::from("mass").label(), "mass");
+    }
+
+    #[test]
+    #[should_panic(expected = "Variable and objective names must not be empty")]
+    fn test_empty_name() {
+        Metadata::new("");
+    }
+
+    #[test]
+    fn test_try_new() {
+        assert!(Metadata::try_new("").is_err());
+        assert_eq!(Metadata::try_new("mass").unwrap(), Metadata::from("mass"));
+    }
+}
@@ -0,0 +1,556 @@
+use rayon::prelude::*;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use crate::core::Solution;
+use crate::dominance::{compare_pareto, Dominance, DominanceResult, ParetoDominance, SortingKey};
+
+/// Population size from which the sorting work is spread over the rayon thread pool
+pub const PARALLEL_THRESHOLD: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortingAlgorithm {
+    /// Divide-and-conquer for up to three objectives, ENS-BS above that, pairwise sorting when the
+    /// dominance relation has no sorting key
+    #[default]
+    Auto,
+    /// Deb's fast non-dominated sort, O(M N^2)
+    FastNonDominated,
+    /// Efficient non-dominated sort with sequential front search (ENS-SS)
+    EfficientSequential,
+    /// Efficient non-dominated sort with binary front search (ENS-BS)
+    EfficientBinary,
+    /// Divide-and-conquer ranking, best suited to two or three objectives
+    DivideAndConquer,
+}
+
+/// Sorts solutions into non-dominated fronts under Pareto dominance (constraint violations first).
+/// Each front lists indices into `solutions` in ascending order.
+pub fn nondominated_sort(solutions: &[Solution]) -> Vec<Vec<usize>> {
+    nondominated_sort_with(solutions, &ParetoDominance, SortingAlgorithm::Auto)
+}
+
+/// Sorts solutions into non-dominated fronts under any dominance relation.
+/// The key-based backends (ENS, divide-and-conquer) need `Dominance::sorting_key`; relations without one
+/// are sorted with Deb's algorithm whatever backend is requested.
+pub fn nondominated_sort_with(
+    solutions: &[Solution],
+    dominance: &dyn Dominance,
+    algorithm: SortingAlgorithm,
+) -> Vec<Vec<usize>> {
+    let parallel = solutions.len() >= PARALLEL_THRESHOLD;
+    if algorithm == SortingAlgorithm::FastNonDominated {
+        return fast_nondominated_sort(solutions, dominance, parallel);
+    }
+    let keys: Option<Vec<SortingKey>> = if parallel {
+        solutions.par_iter().map(|solution| dominance.sorting_key(solution)).collect()
+    } else {
+        solutions.iter().map(|solution| dominance.sorting_key(solution)).collect()
+    };
+    match keys {
+        Some(keys) => sort_by_keys(&keys, algorithm, parallel),
+        None => fast_nondominated_sort(solutions, dominance, parallel),
+    }
+}
+
+/// Rank (index of the front) of every solution, the inverse of the front lists
+pub fn fronts_to_ranks(fronts: &[Vec<usize>], number_of_solutions: usize) -> Vec<usize> {
+    let mut ranks = vec![0; number_of_solutions];
+    for (rank, front) in fronts.iter().enumerate() {
+        for &index in front {
+            ranks[index] = rank;
+        }
+    }
+    ranks
+}
+
+fn fast_nondominated_sort(solutions: &[Solution], dominance: &dyn Dominance, parallel: bool) -> Vec<Vec<usize>> {
+    let n = solutions.len();
+    let mut dominated: Vec<Vec<usize>> = vec![Vec::new(); n];
+    let mut domination_count: Vec<usize> = vec![0; n];
+
+    if parallel {
+        // Every solution is compared against all others independently, doubling the comparisons
+        // but leaving nothing to synchronise
+        let rows: Vec<(Vec<usize>, usize)> = (0..n)
+            .into_par_iter()
+            .map(|i| {
+                let mut row = Vec::new();
+                let mut count = 0;
+                for j in 0..n {
+                    if i == j {
+                        continue;
+                    }
+                    match dominance.compare(&solutions[i], &solutions[j]) {
+                        DominanceResult::Dominates => row.push(j),
+                        DominanceResult::DominatedBy => count += 1,
+                        _ => {}
+                    }
+                }
+                (row, count)
+            })
+            .collect();
+        for (i, (row, count)) in rows.into_iter().enumerate() {
+            dominated[i] = row;
+            domination_count[i] = count;
+        }
+    } else {
+        for i in 0..n {
+            for j in (i + 1)..n {
+                match dominance.compare(&solutions[i], &solutions[j]) {
+                    DominanceResult::Dominates => {
+                        dominated[i].push(j);
+                        domination_count[j] += 1;
+                    }
+                    DominanceResult::DominatedBy => {
+                        dominated[j].push(i);
+                        domination_count[i] += 1;
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    let mut fronts: Vec<Vec<usize>> = Vec::new();
+    let mut current: Vec<usize> = (0..n).filter(|&i| domination_count[i] == 0).collect();
+    while !current.is_empty() {
+        let mut next = Vec::new();
+        for &i in &current {
+            for &j in &dominated[i] {
+                domination_count[j] -= 1;
+                if domination_count[j] == 0 {
+                    next.push(j);
+                }
+            }
+        }
+        next.sort_unstable();
+        fronts.push(current);
+        current = next;
+    }
+    fronts
+}
+
+fn lexicographic(a: &[f64], b: &[f64]) -> Ordering {
+    a.iter()
+        .zip(b.iter())
+        .map(|(x, y)| x.total_cmp(y))
+        .find(|ordering| *ordering != Ordering::Equal)
+        .unwrap_or(Ordering::Equal)
+}
+
+// Solutions in a lower tier dominate every solution in a higher one, so each tier is ranked on its own
+// and its fronts are appended after those of the previous tier.
+fn sort_by_keys(keys: &[SortingKey], algorithm: SortingAlgorithm, parallel: bool) -> Vec<Vec<usize>> {
+    let mut tiers: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for (index, key) in keys.iter().enumerate() {
+        tiers.entry(key.tier).or_default().push(index);
+    }
+    let objectives: Vec<&[f64]> = keys.iter().map(|key| key.objectives.as_slice()).collect();
+    let number_of_objectives = objectives.first().map_or(0, |values| values.len());
+    let algorithm = match algorithm {
+        SortingAlgorithm::Auto if number_of_objectives <= 3 => SortingAlgorithm::DivideAndConquer,
+        SortingAlgorithm::Auto => SortingAlgorithm::EfficientBinary,
+        other => other,
+    };
+
+    let mut fronts: Vec<Vec<usize>> = Vec::new();
+    for (_, mut members) in tiers {
+        if parallel {
+            members.par_sort_by(|&a, &b| lexicographic(objectives[a], objectives[b]));
+        } else {
+            members.sort_by(|&a, &b| lexicographic(objectives[a], objectives[b]));
+        }
+        let mut tier_fronts = match algorithm {
+            SortingAlgorithm::EfficientSequential => efficient_nondominated_sort(&objectives, &members, false),
+            SortingAlgorithm::EfficientBinary => efficient_nondominated_sort(&objectives, &members, true),
+            _ => divide_and_conquer_sort(&objectives, &members),
+        };
+        for front in tier_fronts.iter_mut() {
+            front.sort_unstable();
+        }
+        fronts.extend(tier_fronts);
+    }
+    fronts
+}
+
+// ENS (Zhang et al. 2015): `presorted` is in lexicographic order, so a solution can only be dominated by
+// solutions placed before it and only needs checking against the fronts built so far.
+fn efficient_nondominated_sort(objectives: &[&[f64]], presorted: &[usize], binary: bool) -> Vec<Vec<usize>> {
+    let mut fronts: Vec<Vec<usize>> = Vec::new();
+    let dominated_by_front = |front: &Vec<usize>, candidate: usize| {
+        // The latest members are the most similar to the candidate, so they are checked first
+        front
+            .iter()
+            .rev()
+            .any(|&member| compare_pareto(objectives[member], objectives[candidate]) == DominanceResult::Dominates)
+    };
+    for &candidate in presorted {
+        // Fronts are nested: being dominated by front k implies being dominated by every front before it
+        let position = if binary {
+            let (mut low, mut high) = (0, fronts.len());
+            while low < high {
+                let middle = (low + high) / 2;
+                if dominated_by_front(&fronts[middle], candidate) {
+                    low = middle + 1;
+                } else {
+                    high = middle;
+                }
+            }
+            low
+        } else {
+            fronts
+                .iter()
+                .position(|front| !dominated_by_front(front, candidate))
+                .unwrap_or(fronts.len())
+        };
+        if position == fronts.len() {
+            fronts.push(Vec::new());
+        }
+        fronts[position].push(candidate);
+    }
+    fronts
+}
+
+// Divide-and-conquer ranking after Jensen (2003), generalized to ties and duplicates by
+// Fortin, Grenier and Parizeau (2013). Ranks are raised along dominance chains; `presorted` is in
+// lexicographic order.
+fn divide_and_conquer_sort(objectives: &[&[f64]], presorted: &[usize]) -> Vec<Vec<usize>> {
+    if presorted.is_empty() {
+        return Vec::new();
+    }
+    let number_of_objectives = objectives[presorted[0]].len();
+    let mut ranks = vec![0usize; objectives.len()];
+    if number_of_objectives <= 1 {
+        // Single objective: every new distinct value opens a new front
+        for window in presorted.windows(2) {
+            let (previous, current) = (window[0], window[1]);
+            ranks[current] = ranks[previous] + usize::from(lexicographic(objectives[previous], objectives[current]) == Ordering::Less);
+        }
+    } else {
+        let mut sorter = DivideAndConquer { objectives, ranks: &mut ranks };
+        sorter.helper_a(presorted.to_vec(), number_of_objectives - 1);
+    }
+
+    let mut fronts: Vec<Vec<usize>> = Vec::new();
+    for &index in presorted {
+        let rank = ranks[index];
+        if rank >= fronts.len() {
+            fronts.resize(rank + 1, Vec::new());
+        }
+        fronts[rank].push(index);
+    }
+    fronts
+}
+
+struct DivideAndConquer<'s> {
+    objectives: &'s [&'s [f64]],
+    ranks: &'s mut Vec<usize>,
+}
+
+impl DivideAndConquer<'_> {
+    fn value(&self, index: usize, objective: usize) -> f64 {
+        self.objectives[index][objective]
+    }
+
+    // Weak dominance on objectives 0..=k; higher objectives are already known to be ordered
+    fn weakly_dominates(&self, a: usize, b: usize, k: usize) -> bool {
+        (0..=k).all(|m| self.value(a, m) <= self.value(b, m))
+    }
+
+    fn median(&self, points: &[usize], objective: usize) -> f64 {
+        let mut values: Vec<f64> = points.iter().map(|&p| self.value(p, objective)).collect();
+        let middle = values.len() / 2;
+        *values.select_nth_unstable_by(middle, |a, b| a.total_cmp(b)).1
+    }
+
+    // Splits on objective k around the median; values equal to it all go to the smaller side so that
+    // identical points never end up on different sides. Both sides are non-empty unless all values are equal.
+    fn split(&self, points: &[usize], objective: usize, pivot: f64) -> (Vec<usize>, Vec<usize>) {
+        let below = points.iter().filter(|&&p| self.value(p, objective) < pivot).count();
+        let above = points.iter().filter(|&&p| self.value(p, objective) > pivot).count();
+        let ties_low = below <= above;
+        points.iter().partition(|&&p| {
+            let value = self.value(p, objective);
+            value < pivot || (value == pivot && ties_low)
+        })
+    }
+
+    // Ranks `points` against each other on objectives 0..=k; all points share their higher objectives
+    fn helper_a(&mut self, points: Vec<usize>, k: usize) {
+        if points.len() < 2 {
+            return;
+        }
+        if points.len() == 2 {
+            let (a, b) = (points[0], points[1]);
+            if self.weakly_dominates(a, b, k) && lexicographic(self.objectives[a], self.objectives[b]) != Ordering::Equal {
+                self.ranks[b] = self.ranks[b].max(self.ranks[a] + 1);
+            }
+            return;
+        }
+        if k == 1 {
+            self.sweep_a(&points);
+            return;
+        }
+        let first = self.value(points[0], k);
+        if points.iter().all(|&p| self.value(p, k) == first) {
+            self.helper_a(points, k - 1);
+            return;
+        }
+        let pivot = self.median(&points, k);
+        let (low, high) = self.split(&points, k, pivot);
+        self.helper_a(low.clone(), k);
+        self.helper_b(&low, high.clone(), k - 1);
+        self.helper_a(high, k);
+    }
+
+    // Raises the ranks of `high` from those of `low` on objectives 0..=k, where every point of `low` is
+    // no worse than every point of `high` on the higher objectives and the two sets share no duplicates
+    fn helper_b(&mut self, low: &[usize], high: Vec<usize>, k: usize) {
+        if low.is_empty() || high.is_empty() {
+            return;
+        }
+        if low.len() == 1 || high.len() == 1 {
+            for &h in &high {
+                for &l in low {
+                    if self.weakly_dominates(l, h, k) {
+                        self.ranks[h] = self.ranks[h].max(self.ranks[l] + 1);
+                    }
+                }
+            }
+            return;
+        }
+        if k == 1 {
+            self.sweep_b(low, &high);
+            return;
+        }
+        let low_max = low.iter().map(|&p| self.value(p, k)).fold(f64::NEG_INFINITY, f64::max);
+        let high_min = high.iter().map(|&p| self.value(p, k)).fold(f64::INFINITY, f64::min);
+        if low_max <= high_min {
+            self.helper_b(low, high, k - 1);
+            return;
+        }
+        let low_min = low.iter().map(|&p| self.value(p, k)).fold(f64::INFINITY, f64::min);
+        let high_max = high.iter().map(|&p| self.value(p, k)).fold(f64::NEG_INFINITY, f64::max);
+        if low_min > high_max {
+            return;
+        }
+        let all: Vec<usize> = low.iter().chain(high.iter()).cloned().collect();
+        let pivot = self.median(&all, k);
+        // Ties may go either way here; pick the side that leaves both halves non-empty
+        let below_or_at = all.iter().filter(|&&p| self.value(p, k) <= pivot).count();
+        let ties_low = below_or_at < all.len();
+        let in_low_half = |value: f64| value < pivot || (value == pivot && ties_low);
+        let (low_1, low_2): (Vec<usize>, Vec<usize>) = low.iter().partition(|&&p| in_low_half(self.value(p, k)));
+        let (high_1, high_2): (Vec<usize>, Vec<usize>) = high.iter().partition(|&&p| in_low_half(self.value(p, k)));
+        self.helper_b(&low_1, high_1, k);
+        self.helper_b(&low_1, high_2.clone(), k - 1);
+        self.helper_b(&low_2, high_2, k);
+    }
+
+    // Two-objective base case of helper_a: sweep in lexicographic order, querying the best rank among
+    // earlier points whose second objective is no larger
+    fn sweep_a(&mut self, points: &[usize]) {
+        let mut tree = PrefixMaxTree::new(points.iter().map(|&p| self.value(p, 1)).collect());
+        let mut start = 0;
+        while start < points.len() {
+            // Identical points do not dominate each other: rank the whole group before inserting it
+            let mut end = start + 1;
+            while end < points.len()
+                && self.value(points[end], 0) == self.value(points[start], 0)
+                && self.value(points[end], 1) == self.value(points[start], 1)
+            {
+                end += 1;
+            }
+            for &p in &points[start..end] {
+                if let Some(best) = tree.query(self.value(p, 1)) {
+                    self.ranks[p] = self.ranks[p].max(best + 1);
+                }
+            }
+            for &p in &points[start..end] {
+                tree.insert(self.value(p, 1), self.ranks[p]);
+            }
+            start = end;
+        }
+    }
+
+    // Two-objective base case of helper_b
+    fn sweep_b(&mut self, low: &[usize], high: &[usize]) {
+        let mut tree = PrefixMaxTree::new(low.iter().map(|&p| self.value(p, 1)).collect());
+        let mut merged: Vec<(usize, bool)> = low.iter().map(|&p| (p, true)).chain(high.iter().map(|&p| (p, false))).collect();
+        // Lexicographic on the first two objectives, `low` first on ties since it dominates `high` there
+        merged.sort_by(|&(a, a_low), &(b, b_low)| {
+            self.value(a, 0)
+                .total_cmp(&self.value(b, 0))
+                .then(self.value(a, 1).total_cmp(&self.value(b, 1)))
+                .then(b_low.cmp(&a_low))
+        });
+        for (p, is_low) in merged {
+            if is_low {
+                tree.insert(self.value(p, 1), self.ranks[p]);
+            } else if let Some(best) = tree.query(self.value(p, 1)) {
+                self.ranks[p] = self.ranks[p].max(best + 1);
+            }
+        }
+    }
+}
+
+// Fenwick tree over a fixed set of coordinates answering "largest rank stored at a coordinate <= x"
+struct PrefixMaxTree {
+    coordinates: Vec<f64>,
+    tree: Vec<Option<usize>>,
+}
+
+impl PrefixMaxTree {
+    fn new(mut coordinates: Vec<f64>) -> Self {
+        coordinates.sort_by(|a, b| a.total_cmp(b));
+        coordinates.dedup();
+        let tree = vec![None; coordinates.len() + 1];
+        Self { coordinates, tree }
+    }
+
+    // Number of coordinates <= value
+    fn position(&self, value: f64) -> usize {
+        self.coordinates.partition_point(|c| c.total_cmp(&value) != Ordering::Greater)
+    }
+
+    fn insert(&mut self, coordinate: f64, rank: usize) {
+        let mut i = self.position(coordinate);
+        while i < self.tree.len() {
+            self.tree[i] = Some(self.tree[i].map_or(rank, |current| current.max(rank)));
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    fn query(&self, value: f64) -> Option<usize> {
+        let mut i = self.position(value);
+        let mut best = None;
+        while i > 0 {
+            if let Some(rank) = self.tree[i] {
+                best = Some(best.map_or(rank, |current: usize| current.max(rank)));
+            }
+            i -= i & i.wrapping_neg();
+        }
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Problem;
+    use crate::dominance::{EpsilonDominance, WeightedSumDominance};
+    use crate::test_utils::{constrained_problem, evaluated_solution, minimizing_problem, violating_solution};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    // Coarse values produce plenty of ties and duplicates
+    fn random_population(problem: &Problem, size: usize, seed: u64) -> Vec<Solution<'_>> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..size)
+            .map(|_| {
+                let objectives = (0..problem.number_of_objectives).map(|_| rng.gen_range(0..6) as f64).collect();
+                let violation = if problem.objective_constraint.is_some() { rng.gen_range(0..3) } else { 0 };
+                violating_solution(problem, objectives, violation)
+            })
+            .collect()
+    }
+
+    fn assert_valid_fronts(solutions: &[Solution], fronts: &[Vec<usize>]) {
+        let ranks = fronts_to_ranks(fronts, solutions.len());
+        assert_eq!(fronts.iter().map(|front| front.len()).sum::<usize>(), solutions.len());
+        for i in 0..solutions.len() {
+            for j in 0..solutions.len() {
+                if ParetoDominance.compare(&solutions[i], &solutions[j]) == DominanceResult::Dominates {
+                    assert!(ranks[i] < ranks[j]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_nondominated_sort_small_example() {
+        let problem = minimizing_problem(2);
+        let solutions: Vec<Solution> = [[1.0, 4.0], [2.0, 2.0], [3.0, 3.0], [4.0, 1.0], [2.0, 2.0], [5.0, 5.0]]
+            .iter()
+            .map(|values| evaluated_solution(&problem, values.to_vec()))
+            .collect();
+        let fronts = nondominated_sort(&solutions);
+        assert_eq!(fronts, vec![vec![0, 1, 3, 4], vec![2], vec![5]]);
+    }
+
+    #[test]
+    fn test_backends_agree() {
+        let algorithms = [
+            SortingAlgorithm::FastNonDominated,
+            SortingAlgorithm::EfficientSequential,
+            SortingAlgorithm::EfficientBinary,
+            SortingAlgorithm::DivideAndConquer,
+            SortingAlgorithm::Auto,
+        ];
+        for number_of_objectives in 1..=5 {
+            for constrained in [false, true] {
+                let problem = if constrained { constrained_problem(number_of_objectives) } else { minimizing_problem(number_of_objectives) };
+                let solutions = random_population(&problem, 300, number_of_objectives as u64);
+                let expected = fast_nondominated_sort(&solutions, &ParetoDominance, false);
+                assert_valid_fronts(&solutions, &expected);
+                for algorithm in algorithms {
+                    let fronts = nondominated_sort_with(&solutions, &ParetoDominance, algorithm);
+                    assert_eq!(fronts, expected, "{:?} with {} objectives", algorithm, number_of_objectives);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_parallel_path_matches_sequential() {
+        let problem = constrained_problem(3);
+        let solutions = random_population(&problem, 500, 11);
+        assert_eq!(
+            fast_nondominated_sort(&solutions, &ParetoDominance, true),
+            fast_nondominated_sort(&solutions, &ParetoDominance, false)
+        );
+        let keys: Vec<SortingKey> = solutions.iter().map(|s| ParetoDominance.sorting_key(s).unwrap()).collect();
+        assert_eq!(
+            sort_by_keys(&keys, SortingAlgorithm::EfficientBinary, true),
+            sort_by_keys(&keys, SortingAlgorithm::EfficientBinary, false)
+        );
+    }
+
+    #[test]
+    fn test_large_population_uses_parallel_path() {
+        let problem = minimizing_problem(2);
+        let solutions: Vec<Solution> = (0..PARALLEL_THRESHOLD)
+            .map(|i| {
+                let x = (i % 100) as f64;
+                let layer = (i / 100) as f64;
+                evaluated_solution(&problem, vec![x + layer, 100.0 - x + layer])
+            })
+            .collect();
+        let fronts = nondominated_sort(&solutions);
+        assert_eq!(fronts.len(), 100);
+        assert!(fronts.iter().all(|front| front.len() == 100));
+    }
+
+    #[test]
+    fn test_scalarizing_dominance_sorts_by_scalar() {
+        let problem = minimizing_problem(2);
+        let solutions: Vec<Solution> = [[1.0, 4.0], [2.0, 2.0], [3.0, 0.0], [0.0, 4.0]]
+            .iter()
+            .map(|values| evaluated_solution(&problem, values.to_vec()))
+            .collect();
+        let dominance = WeightedSumDominance::new(vec![1.0, 1.0]);
+        let fronts = nondominated_sort_with(&solutions, &dominance, SortingAlgorithm::Auto);
+        assert_eq!(fronts, vec![vec![2], vec![1, 3], vec![0]]);
+    }
+
+    #[test]
+    fn test_dominance_without_sorting_key_falls_back() {
+        let problem = minimizing_problem(2);
+        let solutions = random_population(&problem, 100, 3);
+        let dominance = EpsilonDominance::new(vec![2.0]);
+        assert_eq!(
+            nondominated_sort_with(&solutions, &dominance, SortingAlgorithm::DivideAndConquer),
+            fast_nondominated_sort(&solutions, &dominance, false)
+        );
+    }
+}
@@ -0,0 +1,214 @@
+use crate::core::{Problem, Solution};
+use crate::math_utils::solve_linear_system;
+use crate::nondominated_sort::nondominated_sort;
+
+/// How the nadir point is estimated from the solutions seen so far
+#[derive(Debug, Clone, PartialEq)]
+pub enum NadirEstimator {
+    /// Worst value of every objective over the current non-dominated set
+    NondominatedWorst,
+    /// Intercepts of the hyperplane through the extreme points, one per objective axis (NSGA-III).
+    /// Falls back to the non-dominated worst when the hyperplane is degenerate.
+    ExtremePoints,
+    /// A fixed nadir point, in the problem's own units
+    Fixed(Vec<f64>),
+}
+
+/// Tracks the ideal point and estimates the nadir point over a run, and maps objective vectors onto [0, 1]
+/// between them. Everything is kept in minimization form, so maximized objectives are handled through
+/// `Problem::direction`; solutions worse than the estimated nadir map above 1.
+#[derive(Debug, Clone)]
+pub struct Normalizer<'a> {
+    pub problem: &'a Problem,
+    pub estimator: NadirEstimator,
+    pub ideal_point: Option<Vec<f64>>,
+    pub nadir_point: Option<Vec<f64>>,
+    pub worst_point: Option<Vec<f64>>, // worst values ever seen
+    extreme_points: Option<Vec<Vec<f64>>>,
+}
+
+impl<'a> Normalizer<'a> {
+    pub fn new(problem: &'a Problem, estimator: NadirEstimator) -> Self {
+        let nadir_point = match &estimator {
+            NadirEstimator::Fixed(nadir) => {
+                if nadir.len() != problem.number_of_objectives {
+                    panic!("Nadir point length does not match number_of_objectives");
+                }
+                Some(problem.oriented_objectives(nadir))
+            }
+            _ => None,
+        };
+        Self { problem, estimator, ideal_point: None, nadir_point, worst_point: None, extreme_points: None }
+    }
+
+    /// Updates the ideal point and the nadir estimate with a population, typically once per generation
+    pub fn update(&mut self, solutions: &[Solution]) {
+        let objectives: Vec<Vec<f64>> = solutions
+            .iter()
+            .filter(|solution| solution.evaluated)
+            .map(|solution| self.problem.oriented_objectives(&solution.objective_fitness_values))
+            .collect();
+        if objectives.is_empty() {
+            return;
+        }
+        self.ideal_point = Some(componentwise(self.ideal_point.as_ref(), &objectives, f64::min));
+        self.worst_point = Some(componentwise(self.worst_point.as_ref(), &objectives, f64::max));
+        if let NadirEstimator::Fixed(_) = self.estimator {
+            return;
+        }
+        let evaluated: Vec<Solution> = solutions.iter().filter(|solution| solution.evaluated).cloned().collect();
+        let front: Vec<Vec<f64>> = nondominated_sort(&evaluated)[0].iter().map(|&i| objectives[i].clone()).collect();
+        let front_worst = componentwise(None, &front, f64::max);
+        let nadir = match self.estimator {
+            NadirEstimator::ExtremePoints => self.hyperplane_nadir(&front).unwrap_or(front_worst),
+            _ => front_worst,
+        };
+        self.nadir_point = Some(nadir);
+    }
+
+    // Nadir from the intercepts of the hyperplane through the extreme points, None when that is degenerate
+    fn hyperplane_nadir(&mut self, front: &[Vec<f64>]) -> Option<Vec<f64>> {
+        let ideal = self.ideal_point.clone().unwrap();
+        let number_of_objectives = ideal.len();
+        let mut candidates: Vec<Vec<f64>> = front.to_vec();
+        if let Some(previous) = &self.extreme_points {
+            candidates.extend(previous.iter().cloned());
+        }
+        // Extreme point of an axis: the one minimizing the achievement scalarizing function along that axis
+        let extreme_points: Vec<Vec<f64>> = (0..number_of_objectives)
+            .map(|axis| {
+                candidates
+                    .iter()
+                    .min_by(|a, b| axis_achievement(a, &ideal, axis).total_cmp(&axis_achievement(b, &ideal, axis)))
+                    .unwrap()
+                    .clone()
+            })
+            .collect();
+        self.extreme_points = Some(extreme_points.clone());
+        let translated: Vec<Vec<f64>> = extreme_points
+            .iter()
+            .map(|point| point.iter().zip(ideal.iter()).map(|(f, z)| f - z).collect())
+            .collect();
+        let plane = solve_linear_system(&translated, &vec![1.0; number_of_objectives])?;
+        let intercepts: Vec<f64> = plane.iter().map(|b| 1.0 / b).collect();
+        if intercepts.iter().any(|a| !a.is_finite() || *a <= 1e-6) {
+            return None;
+        }
+        Some(ideal.iter().zip(intercepts.iter()).map(|(z, a)| z + a).collect())
+    }
+
+    /// Objective values in the problem's own units mapped onto [0, 1] between the ideal and nadir points
+    pub fn normalize(&self, objective_values: &[f64]) -> Vec<f64> {
+        let (ideal, nadir) = match (&self.ideal_point, &self.nadir_point) {
+            (Some(ideal), Some(nadir)) => (ideal, nadir),
+            _ => panic!("Normalizer has not been updated with any evaluated solution"),
+        };
+        self.problem
+            .oriented_objectives(objective_values)
+            .iter()
+            .zip(ideal.iter().zip(nadir.iter()))
+            .map(|(f, (z, n))| {
+                let range = n - z;
+                (f - z) / if range > 1e-10 { range } else { 1e-10 }
+            })
+            .collect()
+    }
+
+    pub fn normalize_solutions(&self, solutions: &[Solution]) -> Vec<Vec<f64>> {
+        solutions.iter().map(|solution| self.normalize(&solution.objective_fitness_values)).collect()
+    }
+}
+
+// Minimum (or maximum) of every objective over the points, combined with a previous value
+fn componentwise(previous: Option<&Vec<f64>>, points: &[Vec<f64>], combine: fn(f64, f64) -> f64) -> Vec<f64> {
+    let mut result = previous.cloned().unwrap_or_else(|| points[0].clone());
+    for point in points {
+        for (value, f) in result.iter_mut().zip(point.iter()) {
+            *value = combine(*value, *f);
+        }
+    }
+    result
+}
+
+// Achievement scalarizing function with weight 1 on the axis and 1e-6 on the other objectives
+fn axis_achievement(point: &[f64], ideal: &[f64], axis: usize) -> f64 {
+    point
+        .iter()
+        .zip(ideal.iter())
+        .enumerate()
+        .map(|(m, (f, z))| (f - z) / if m == axis { 1.0 } else { 1e-6 })
+        .fold(f64::NEG_INFINITY, f64::max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{evaluated_solution, setup_problem};
+
+    fn population<'a>(problem: &'a Problem, objectives: &[Vec<f64>]) -> Vec<Solution<'a>> {
+        objectives.iter().map(|values| evaluated_solution(problem, values.clone())).collect()
+    }
+
+    #[test]
+    fn test_ideal_is_tracked_across_updates() {
+        let problem = setup_problem(vec![-1, -1]);
+        let mut normalizer = Normalizer::new(&problem, NadirEstimator::NondominatedWorst);
+        normalizer.update(&population(&problem, &[vec![2.0, 5.0], vec![4.0, 3.0]]));
+        normalizer.update(&population(&problem, &[vec![3.0, 4.0], vec![6.0, 6.0]]));
+        assert_eq!(normalizer.ideal_point, Some(vec![2.0, 3.0]));
+        assert_eq!(normalizer.worst_point, Some(vec![6.0, 6.0]));
+        // Only the current non-dominated set counts for the nadir
+        assert_eq!(normalizer.nadir_point, Some(vec![3.0, 4.0]));
+    }
+
+    #[test]
+    fn test_scales_objectives_of_different_magnitude() {
+        let problem = setup_problem(vec![-1, -1]);
+        let mut normalizer = Normalizer::new(&problem, NadirEstimator::NondominatedWorst);
+        let solutions = population(&problem, &[vec![0.0, 2.0e6], vec![0.5, 1.0e6], vec![1.0, 0.0]]);
+        normalizer.update(&solutions);
+        assert_eq!(normalizer.normalize_solutions(&solutions), vec![vec![0.0, 1.0], vec![0.5, 0.5], vec![1.0, 0.0]]);
+    }
+
+    #[test]
+    fn test_extreme_point_intercepts() {
+        let problem = setup_problem(vec![-1, -1, -1]);
+        let solutions = population(&problem, &[vec![0.6, 0.4, 0.0], vec![0.0, 0.6, 0.4], vec![0.4, 0.0, 0.6]]);
+        let mut worst = Normalizer::new(&problem, NadirEstimator::NondominatedWorst);
+        worst.update(&solutions);
+        assert_eq!(worst.nadir_point, Some(vec![0.6, 0.6, 0.6]));
+        let mut intercepts = Normalizer::new(&problem, NadirEstimator::ExtremePoints);
+        intercepts.update(&solutions);
+        for value in intercepts.nadir_point.unwrap() {
+            assert!((value - 1.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_degenerate_hyperplane_falls_back_to_worst() {
+        let problem = setup_problem(vec![-1, -1, -1]);
+        // A single point is the extreme point of every axis
+        let solutions = population(&problem, &[vec![1.0, 2.0, 3.0]]);
+        let mut normalizer = Normalizer::new(&problem, NadirEstimator::ExtremePoints);
+        normalizer.update(&solutions);
+        assert_eq!(normalizer.nadir_point, Some(vec![1.0, 2.0, 3.0]));
+    }
+
+    #[test]
+    fn test_fixed_nadir_with_maximized_objective() {
+        let problem = setup_problem(vec![-1, 1]);
+        let mut normalizer = Normalizer::new(&problem, NadirEstimator::Fixed(vec![10.0, 0.0]));
+        normalizer.update(&population(&problem, &[vec![0.0, 100.0], vec![5.0, 50.0]]));
+        assert_eq!(normalizer.ideal_point, Some(vec![0.0, -100.0]));
+        assert_eq!(normalizer.nadir_point, Some(vec![10.0, 0.0]));
+        assert_eq!(normalizer.normalize(&[5.0, 50.0]), vec![0.5, 0.5]);
+        assert_eq!(normalizer.normalize(&[0.0, 100.0]), vec![0.0, 0.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Normalizer has not been updated with any evaluated solution")]
+    fn test_normalize_before_update() {
+        let problem = setup_problem(vec![-1]);
+        Normalizer::new(&problem, NadirEstimator::NondominatedWorst).normalize(&[1.0]);
+    }
+}
@@ -0,0 +1,237 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rand_distr::{Distribution, Exp1};
+use crate::math_utils::{euclidean_distance, normalize};
+
+// Generators of well-spread direction vectors on the unit simplex (non-negative, summing to 1), as used for
+// the weights of decomposition methods and the reference points of NSGA-III style niching.
+
+/// Number of Das-Dennis points for the given number of divisions: C(divisions + M - 1, M - 1)
+pub fn das_dennis_count(number_of_objectives: usize, divisions: usize) -> usize {
+    (1..number_of_objectives).fold(1, |count, k| count * (divisions + k) / k)
+}
+
+/// Das and Dennis (1998) structured points: every point of the simplex whose coordinates are multiples of 1 / divisions
+pub fn das_dennis(number_of_objectives: usize, divisions: usize) -> Vec<Vec<f64>> {
+    if number_of_objectives == 0 {
+        panic!("number_of_objectives must be greater than zero");
+    }
+    if divisions == 0 {
+        panic!("divisions must be greater than zero");
+    }
+    fn fill(point: &mut Vec<usize>, remaining: usize, dimensions: usize, points: &mut Vec<Vec<usize>>) {
+        if point.len() == dimensions - 1 {
+            point.push(remaining);
+            points.push(point.clone());
+            point.pop();
+            return;
+        }
+        for value in (0..=remaining).rev() {
+            point.push(value);
+            fill(point, remaining - value, dimensions, points);
+            point.pop();
+        }
+    }
+    let mut points = Vec::with_capacity(das_dennis_count(number_of_objectives, divisions));
+    fill(&mut Vec::with_capacity(number_of_objectives), divisions, number_of_objectives, &mut points);
+    points
+        .into_iter()
+        .map(|point| point.into_iter().map(|value| value as f64 / divisions as f64).collect())
+        .collect()
+}
+
+/// Two-layer set of Deb and Jain (2014) for many objectives: a boundary layer and an inside layer shrunk towards
+/// the centroid by `inner_scale` (default 0.5), so interior directions exist even with few divisions
+pub fn two_layer(number_of_objectives: usize, outer_divisions: usize, inner_divisions: usize, inner_scale: Option<f64>) -> Vec<Vec<f64>> {
+    let inner_scale = inner_scale.unwrap_or(0.5);
+    if inner_scale <= 0.0 || inner_scale >= 1.0 {
+        panic!("inner_scale must lie strictly between 0 and 1");
+    }
+    let centroid = (1.0 - inner_scale) / number_of_objectives as f64;
+    let mut directions = das_dennis(number_of_objectives, outer_divisions);
+    directions.extend(
+        das_dennis(number_of_objectives, inner_divisions)
+            .into_iter()
+            .map(|point| point.iter().map(|value| centroid + inner_scale * value).collect::<Vec<f64>>()),
+    );
+    directions
+}
+
+/// Points drawn uniformly from the simplex (normalized exponential variates, i.e. a flat Dirichlet distribution)
+pub fn uniform_random(number_of_objectives: usize, number_of_points: usize, seed: Option<u64>) -> Vec<Vec<f64>> {
+    let mut rng = match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+    (0..number_of_points)
+        .map(|_| {
+            let variates: Vec<f64> = (0..number_of_objectives).map(|_| Exp1.sample(&mut rng)).collect();
+            let total: f64 = variates.iter().sum();
+            variates.iter().map(|value| value / total).collect()
+        })
+        .collect()
+}
+
+/// Any number of points spread over the simplex by minimizing their Riesz s-energy, the sum over pairs of
+/// 1 / distance^s (Blank et al. 2021). The corners are kept fixed and the remaining points start uniformly at
+/// random. `s` defaults to the number of objectives and `iterations` to 500.
+pub fn riesz_s_energy(
+    number_of_objectives: usize,
+    number_of_points: usize,
+    s: Option<f64>,
+    iterations: Option<usize>,
+    seed: Option<u64>,
+) -> Vec<Vec<f64>> {
+    if number_of_points < number_of_objectives {
+        panic!("number_of_points must be at least number_of_objectives to include the corners");
+    }
+    let s = s.unwrap_or(number_of_objectives as f64);
+    let fixed = number_of_objectives;
+    let mut points: Vec<Vec<f64>> = (0..number_of_objectives)
+        .map(|m| (0..number_of_objectives).map(|k| if k == m { 1.0 } else { 0.0 }).collect())
+        .collect();
+    points.extend(uniform_random(number_of_objectives, number_of_points - fixed, seed));
+
+    let mut energy = riesz_energy(&points, s);
+    let mut step = 0.1;
+    for _ in 0..iterations.unwrap_or(500) {
+        let candidate = descend(&points, fixed, s, step);
+        let candidate_energy = riesz_energy(&candidate, s);
+        if candidate_energy < energy {
+            points = candidate;
+            energy = candidate_energy;
+            step *= 1.1;
+        } else {
+            step /= 2.0;
+            if step < 1e-10 {
+                break;
+            }
+        }
+    }
+    points
+}
+
+/// Riesz s-energy of a point set; lower means more evenly spread
+pub fn riesz_energy(points: &[Vec<f64>], s: f64) -> f64 {
+    let mut energy = 0.0;
+    for i in 0..points.len() {
+        for j in (i + 1)..points.len() {
+            energy += euclidean_distance(&points[i], &points[j]).max(1e-12).powf(-s);
+        }
+    }
+    energy
+}
+
+// One gradient step on the free points: the energy gradient is projected onto the simplex plane, scaled so the
+// largest move equals `step`, and the moved points are clipped and renormalized back onto the simplex
+fn descend(points: &[Vec<f64>], fixed: usize, s: f64, step: f64) -> Vec<Vec<f64>> {
+    let dimensions = points[0].len();
+    let mut gradients = vec![vec![0.0; dimensions]; points.len()];
+    for i in fixed..points.len() {
+        for (j, other) in points.iter().enumerate() {
+            if i == j {
+                continue;
+            }
+            let distance = euclidean_distance(&points[i], other).max(1e-12);
+            let factor = -s * distance.powf(-s - 2.0);
+            for (gradient, (a, b)) in gradients[i].iter_mut().zip(points[i].iter().zip(other.iter())) {
+                *gradient += factor * (a - b);
+            }
+        }
+        let mean = gradients[i].iter().sum::<f64>() / dimensions as f64;
+        gradients[i].iter_mut().for_each(|gradient| *gradient -= mean);
+    }
+    let largest = gradients
+        .iter()
+        .map(|gradient| gradient.iter().map(|g| g * g).sum::<f64>().sqrt())
+        .fold(0.0, f64::max);
+    if largest == 0.0 {
+        return points.to_vec();
+    }
+    points
+        .iter()
+        .zip(gradients.iter())
+        .map(|(point, gradient)| {
+            let moved: Vec<f64> = point
+                .iter()
+                .zip(gradient.iter())
+                .map(|(value, g)| (value - step * g / largest).max(0.0))
+                .collect();
+            let total: f64 = moved.iter().sum();
+            moved.iter().map(|value| value / total).collect()
+        })
+        .collect()
+}
+
+/// Directions scaled to unit length, e.g. for penalty-based boundary intersection or angle-based niching
+pub fn project_to_sphere(directions: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    directions.iter().map(|direction| normalize(direction)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_on_simplex(points: &[Vec<f64>]) {
+        for point in points {
+            assert!(point.iter().all(|&value| value >= 0.0));
+            assert!((point.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        }
+    }
+
+    fn minimum_distance(points: &[Vec<f64>]) -> f64 {
+        let mut minimum = f64::INFINITY;
+        for i in 0..points.len() {
+            for j in (i + 1)..points.len() {
+                minimum = minimum.min(euclidean_distance(&points[i], &points[j]));
+            }
+        }
+        minimum
+    }
+
+    #[test]
+    fn test_das_dennis() {
+        let points = das_dennis(3, 12);
+        assert_eq!(points.len(), 91);
+        assert_eq!(das_dennis_count(3, 12), 91);
+        assert_eq!(das_dennis_count(8, 3), 120);
+        assert_on_simplex(&points);
+        assert_eq!(points[0], vec![1.0, 0.0, 0.0]);
+        assert_eq!(das_dennis(2, 2), vec![vec![1.0, 0.0], vec![0.5, 0.5], vec![0.0, 1.0]]);
+    }
+
+    #[test]
+    fn test_two_layer() {
+        let points = two_layer(8, 3, 2, None);
+        assert_eq!(points.len(), 120 + 36);
+        assert_on_simplex(&points);
+        // The inside layer never touches the boundary
+        assert!(points[120..].iter().all(|point| point.iter().all(|&value| value >= 0.5 / 8.0)));
+    }
+
+    #[test]
+    fn test_uniform_random() {
+        let points = uniform_random(4, 200, Some(3));
+        assert_eq!(points.len(), 200);
+        assert_on_simplex(&points);
+        assert_eq!(points, uniform_random(4, 200, Some(3)));
+    }
+
+    #[test]
+    fn test_riesz_s_energy() {
+        let points = riesz_s_energy(3, 30, None, None, Some(1));
+        assert_eq!(points.len(), 30);
+        assert_on_simplex(&points);
+        assert_eq!(points[..3], das_dennis(3, 1)[..]);
+        let random = uniform_random(3, 30, Some(1));
+        assert!(riesz_energy(&points, 3.0) < riesz_energy(&random, 3.0));
+        assert!(minimum_distance(&points) > 2.0 * minimum_distance(&random));
+    }
+
+    #[test]
+    fn test_project_to_sphere() {
+        for direction in project_to_sphere(&das_dennis(3, 4)) {
+            assert!((direction.iter().map(|value| value * value).sum::<f64>() - 1.0).abs() < 1e-12);
+        }
+    }
+}
@@ -0,0 +1,583 @@
+// User-provided seed genomes for the initial population: validation against the variables of a problem and
+// reading from CSV or JSON files. Invalid seeds are reported as `SeedError`s rather than panics.
+use std::fmt;
+use std::path::Path;
+use crate::core::Problem;
+use crate::expression_tree::{ExpressionTree, Node};
+use crate::gatypes::{BitString, Gene, SolutionDataTypes};
+
+/// Why a seed or seed file was rejected. Seeds are numbered from 0 in the order given.
+#[derive(Debug)]
+pub enum SeedError {
+    /// The seed does not hold the number of genes of the problem's genome
+    Length { seed: usize, expected: String, found: usize },
+    /// A value does not fit its variable: out of bounds, not an integer, not binary, unknown category, ...
+    InvalidValue { seed: usize, variable: String, reason: String },
+    /// More seeds than the population holds
+    TooManySeeds { seeds: usize, population: usize },
+    /// A column of a seed file names neither a variable nor an objective of the problem
+    UnknownColumn(String),
+    /// Malformed seed file; lines are numbered from 1
+    Parse { line: usize, reason: String },
+    /// Seed files must end in .csv or .json
+    UnsupportedFormat(String),
+    Io(std::io::Error),
+}
+
+impl fmt::Display for SeedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SeedError::Length { seed, expected, found } => write!(f, "seed {} has {} genes, expected {}", seed, found, expected),
+            SeedError::InvalidValue { seed, variable, reason } => write!(f, "seed {}: {} {}", seed, variable, reason),
+            SeedError::TooManySeeds { seeds, population } => {
+                write!(f, "{} seeds do not fit a population of {}", seeds, population)
+            }
+            SeedError::UnknownColumn(column) => write!(f, "column \"{}\" is neither a variable nor an objective", column),
+            SeedError::Parse { line, reason } => write!(f, "line {}: {}", line, reason),
+            SeedError::UnsupportedFormat(path) => write!(f, "{} is neither a .csv nor a .json file", path),
+            SeedError::Io(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for SeedError {}
+
+impl From<std::io::Error> for SeedError {
+    fn from(error: std::io::Error) -> Self {
+        SeedError::Io(error)
+    }
+}
+
+/// Checks a seed genome against the variables of the problem and returns it in the representation of each
+/// variable, snapped to the grid of stepped reals
+pub fn validate_seed(problem: &Problem, seed: usize, genome: &[Gene]) -> Result<Vec<Gene>, SeedError> {
+    let valid_length = match &problem.variable_length {
+        Some(variable_length) => {
+            let unit = problem.solution_data_types.len();
+            let repetitions = genome.len() / unit;
+            genome.len().is_multiple_of(unit) && (variable_length.min_length..=variable_length.max_length).contains(&repetitions)
+        }
+        None => genome.len() == problem.genome_length(),
+    };
+    if !valid_length {
+        let expected = match &problem.variable_length {
+            Some(variable_length) => format!(
+                "{} to {} repetitions of {}",
+                variable_length.min_length,
+                variable_length.max_length,
+                problem.solution_data_types.len()
+            ),
+            None => problem.genome_length().to_string(),
+        };
+        return Err(SeedError::Length { seed, expected, found: genome.len() });
+    }
+    let mut genes = Vec::with_capacity(genome.len());
+    for (index, (range, solution_type)) in problem.variables_in(genome).enumerate() {
+        let values = &genome[range];
+        check_variable(solution_type, values).map_err(|reason| SeedError::InvalidValue {
+            seed,
+            variable: problem.variable_name(index),
+            reason,
+        })?;
+        genes.extend(values.iter().map(|&gene| solution_type.representable(gene)));
+    }
+    Ok(genes)
+}
+
+fn is_integral(gene: Gene) -> bool {
+    let value = gene.as_f64();
+    matches!(gene, Gene::Integer(_) | Gene::Bit(_) | Gene::Element(_) | Gene::Category(_) | Gene::Word(_))
+        || (value.is_finite() && value.fract() == 0.0)
+}
+
+// Reason the genes do not fit the variable
+fn check_variable(solution_type: &SolutionDataTypes, genes: &[Gene]) -> Result<(), String> {
+    match solution_type {
+        SolutionDataTypes::Real(real) => {
+            let value = genes[0].as_f64();
+            if !value.is_finite() || value < real.lower() || value > real.upper() {
+                return Err(format!("= {} is outside [{}, {}]", genes[0], real.lower(), real.upper()));
+            }
+        }
+        SolutionDataTypes::Integer(integer) => {
+            if !is_integral(genes[0]) {
+                return Err(format!("= {} is not an integer", genes[0]));
+            }
+            let value = genes[0].as_i64();
+            if (matches!(genes[0], Gene::Real(_)) && genes[0].as_f64().abs() >= 2f64.powi(63)) || value < integer.lower() || value > integer.upper() {
+                return Err(format!("= {} is outside [{}, {}]", genes[0], integer.lower(), integer.upper()));
+            }
+        }
+        SolutionDataTypes::BitBinary(_) => {
+            if !matches!(genes[0], Gene::Bit(_)) && genes[0].as_f64() != 0.0 && genes[0].as_f64() != 1.0 {
+                return Err(format!("= {} is not a binary value", genes[0]));
+            }
+        }
+        SolutionDataTypes::Categorical(categorical) => {
+            if !is_integral(genes[0]) || genes[0].as_f64() < 0.0 || genes[0].as_usize() >= categorical.choices.len() {
+                return Err(format!("= {} is not one of the {} categories", genes[0], categorical.choices.len()));
+            }
+        }
+        SolutionDataTypes::Permutation(permutation) => {
+            let mut elements: Vec<usize> = genes.iter().map(Gene::as_usize).collect();
+            elements.sort_unstable();
+            if !genes.iter().all(|&gene| is_integral(gene)) || elements != (0..permutation.size).collect::<Vec<usize>>() {
+                return Err(format!("is not a permutation of 0..{}", permutation.size));
+            }
+        }
+        SolutionDataTypes::Subset(subset) => {
+            if !genes.iter().all(|&gene| is_integral(gene) && gene.as_f64() >= 0.0) || !subset.is_valid(genes) {
+                return Err(format!("is not a set of {} distinct elements of 0..{}", subset.size, subset.elements));
+            }
+        }
+        SolutionDataTypes::BitString(bit_string) => {
+            let padding = bit_string.words() * BitString::WORD_BITS - bit_string.bits;
+            let last = genes[genes.len() - 1].as_u32();
+            if !genes.iter().all(|&gene| is_integral(gene) && (0.0..=u32::MAX as f64).contains(&gene.as_f64()))
+                || (padding > 0 && last >> (BitString::WORD_BITS - padding) != 0)
+            {
+                return Err(format!("is not a string of {} bits", bit_string.bits));
+            }
+        }
+        SolutionDataTypes::ExpressionTree(tree) => {
+            let nodes = ExpressionTree::nodes(genes);
+            let complete = nodes.iter().map(Node::arity).sum::<usize>() + 1 == nodes.len();
+            if !genes.iter().all(|gene| matches!(gene, Gene::Node(_))) || nodes.contains(&Node::Unused) || !complete {
+                return Err("is not an expression tree".to_string());
+            }
+            if !tree.fits(&nodes) {
+                return Err(format!("exceeds depth {} or size {}", tree.max_depth, tree.max_size));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Reads seeds from a CSV or JSON file, chosen by extension; see `parse_csv` and `parse_json`
+pub fn read_seeds(problem: &Problem, path: impl AsRef<Path>) -> Result<Vec<Vec<Gene>>, SeedError> {
+    let path = path.as_ref();
+    let extension = path.extension().and_then(|extension| extension.to_str()).map(str::to_lowercase);
+    match extension.as_deref() {
+        Some("csv") => parse_csv(problem, &std::fs::read_to_string(path)?),
+        Some("json") => parse_json(problem, &std::fs::read_to_string(path)?),
+        _ => Err(SeedError::UnsupportedFormat(path.display().to_string())),
+    }
+}
+
+/// Seeds from CSV text whose header names the variables as `Problem::variable_name` does, so that files
+/// written from `Solution::header` and `Solution::record` read back; objective columns are ignored.
+/// Categories are given by label, bit strings as 0/1 text and permutations or subsets as `[0, 2, 1]`.
+pub fn parse_csv(problem: &Problem, text: &str) -> Result<Vec<Vec<Gene>>, SeedError> {
+    let mut lines = text.lines().enumerate().filter(|(_, line)| !line.trim().is_empty());
+    let header = match lines.next() {
+        Some((number, line)) => split_csv_line(line).map_err(|reason| SeedError::Parse { line: number + 1, reason })?,
+        None => return Ok(Vec::new()),
+    };
+    let columns = header.iter().map(|name| column_index(problem, name.trim())).collect::<Result<Vec<_>, _>>()?;
+    let mut seeds = Vec::new();
+    for (seed, (number, line)) in lines.enumerate() {
+        let cells = split_csv_line(line).map_err(|reason| SeedError::Parse { line: number + 1, reason })?;
+        if cells.len() != header.len() {
+            let reason = format!("{} cells for {} columns", cells.len(), header.len());
+            return Err(SeedError::Parse { line: number + 1, reason });
+        }
+        let values = columns
+            .iter()
+            .zip(cells)
+            .filter_map(|(column, cell)| column.map(|index| (index, parse_cell(&cell))))
+            .collect();
+        seeds.push(assemble(problem, seed, values)?);
+    }
+    Ok(seeds)
+}
+
+/// Seeds from a JSON array holding one entry per seed: either an array of variable values in order, or an
+/// object from variable names to values. Values are written as in `parse_csv`, with lists as JSON arrays.
+pub fn parse_json(problem: &Problem, text: &str) -> Result<Vec<Vec<Gene>>, SeedError> {
+    let mut parser = JsonParser { text: text.as_bytes(), position: 0 };
+    let document = parser.document().map_err(|reason| SeedError::Parse { line: parser.line(), reason })?;
+    let entries = match document {
+        Value::List(entries) => entries,
+        _ => return Err(SeedError::Parse { line: 1, reason: "expected an array of seeds".to_string() }),
+    };
+    let mut seeds = Vec::new();
+    for (seed, entry) in entries.into_iter().enumerate() {
+        let values = match entry {
+            Value::List(values) => values.into_iter().enumerate().collect(),
+            Value::Object(fields) => {
+                let mut values = Vec::new();
+                for (name, value) in fields {
+                    if let Some(index) = column_index(problem, &name)? {
+                        values.push((index, value));
+                    }
+                }
+                values
+            }
+            _ => {
+                let reason = format!("seed {} is neither an array nor an object", seed);
+                return Err(SeedError::Parse { line: 1, reason });
+            }
+        };
+        seeds.push(assemble(problem, seed, values)?);
+    }
+    Ok(seeds)
+}
+
+// Variable index of a named column, None for objective columns
+fn column_index(problem: &Problem, name: &str) -> Result<Option<usize>, SeedError> {
+    let variables = match &problem.variable_length {
+        Some(variable_length) => variable_length.max_length * problem.solution_data_types.len(),
+        None => problem.solution_data_types.len(),
+    };
+    if let Some(index) = (0..variables).find(|&index| problem.variable_name(index) == name) {
+        return Ok(Some(index));
+    }
+    if (0..problem.number_of_objectives).any(|index| problem.objective_name(index) == name) {
+        return Ok(None);
+    }
+    Err(SeedError::UnknownColumn(name.to_string()))
+}
+
+// Genome from the values of its variables; the variables of a variable-length genome must be given without gaps
+fn assemble(problem: &Problem, seed: usize, mut values: Vec<(usize, Value)>) -> Result<Vec<Gene>, SeedError> {
+    values.retain(|(_, value)| !matches!(value, Value::Null));
+    values.sort_by_key(|(index, _)| *index);
+    let count = match problem.variable_length {
+        Some(_) => values.len(),
+        None => problem.solution_data_types.len(),
+    };
+    let mut genome = Vec::new();
+    let mut values = values.into_iter().peekable();
+    for index in 0..count {
+        let invalid = |reason: String| SeedError::InvalidValue { seed, variable: problem.variable_name(index), reason };
+        match values.next_if(|(position, _)| *position == index) {
+            Some((_, value)) => genome.extend(genes_from_value(problem.variable_type(index), value).map_err(invalid)?),
+            None => return Err(invalid("is missing".to_string())),
+        }
+    }
+    if let Some((index, _)) = values.next() {
+        let reason = "is given after a missing variable".to_string();
+        return Err(SeedError::InvalidValue { seed, variable: problem.variable_name(index), reason });
+    }
+    Ok(genome)
+}
+
+fn genes_from_value(solution_type: &SolutionDataTypes, value: Value) -> Result<Vec<Gene>, String> {
+    match (solution_type, value) {
+        (SolutionDataTypes::Integer(_), Value::Number(text)) => match text.parse::<i64>() {
+            Ok(value) => Ok(vec![Gene::Integer(value)]),
+            Err(_) => number(&text).map(|value| vec![Gene::Real(value)]),
+        },
+        (SolutionDataTypes::Categorical(categorical), Value::Text(label)) => match categorical.index_of(&label) {
+            Some(index) => Ok(vec![Gene::Category(index)]),
+            None => Err(format!("has no category \"{}\"", label)),
+        },
+        (SolutionDataTypes::BitString(bit_string), Value::Text(text)) => {
+            let bits: Vec<bool> = text.chars().filter_map(|bit| match bit {
+                '0' => Some(false),
+                '1' => Some(true),
+                _ => None,
+            }).collect();
+            if bits.len() != text.chars().count() || bits.len() != bit_string.bits {
+                return Err(format!("= \"{}\" is not a string of {} bits", text, bit_string.bits));
+            }
+            Ok(BitString::from_bits(&bits))
+        }
+        (SolutionDataTypes::Permutation(_) | SolutionDataTypes::Subset(_), Value::List(elements)) => elements
+            .into_iter()
+            .map(|element| match element {
+                Value::Number(text) => number(&text).map(Gene::Real),
+                _ => Err("holds an element that is not a number".to_string()),
+            })
+            .collect(),
+        (SolutionDataTypes::ExpressionTree(_), _) => Err("cannot be read from a seed file".to_string()),
+        (_, Value::Number(text)) => number(&text).map(|value| vec![Gene::Real(value)]),
+        (_, Value::Bool(value)) => Ok(vec![Gene::Bit(value)]),
+        (_, Value::Text(text)) => Err(format!("= \"{}\" is not a number", text)),
+        (_, _) => Err("holds a value of the wrong kind".to_string()),
+    }
+}
+
+fn number(text: &str) -> Result<f64, String> {
+    text.parse::<f64>().map_err(|_| format!("= {} is not a number", text))
+}
+
+// Splits a CSV line on commas outside double quotes; "" inside quotes stands for a quote
+fn split_csv_line(line: &str) -> Result<Vec<String>, String> {
+    let mut cells = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(character) = chars.next() {
+        match character {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                cells.last_mut().unwrap().push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => cells.push(String::new()),
+            _ => cells.last_mut().unwrap().push(character),
+        }
+    }
+    if quoted {
+        return Err("unterminated quote".to_string());
+    }
+    Ok(cells)
+}
+
+// Cell of a CSV file: numbers, booleans and lists are read as in JSON, anything else as text
+fn parse_cell(cell: &str) -> Value {
+    let cell = cell.trim();
+    if cell.is_empty() {
+        return Value::Null;
+    }
+    let mut parser = JsonParser { text: cell.as_bytes(), position: 0 };
+    match parser.document() {
+        Ok(value @ (Value::Number(_) | Value::Bool(_) | Value::List(_))) => value,
+        _ => Value::Text(cell.to_string()),
+    }
+}
+
+// Value of a seed file; numbers keep their text so that integers beyond 2^53 are read exactly
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Null,
+    Bool(bool),
+    Number(String),
+    Text(String),
+    List(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+struct JsonParser<'a> {
+    text: &'a [u8],
+    position: usize,
+}
+
+impl JsonParser<'_> {
+    fn document(&mut self) -> Result<Value, String> {
+        let value = self.value()?;
+        self.skip_whitespace();
+        if self.position < self.text.len() {
+            return Err("unexpected text after the value".to_string());
+        }
+        Ok(value)
+    }
+
+    fn line(&self) -> usize {
+        self.text[..self.position.min(self.text.len())].iter().filter(|&&byte| byte == b'\n').count() + 1
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.text.get(self.position).is_some_and(|byte| byte.is_ascii_whitespace()) {
+            self.position += 1;
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), String> {
+        self.skip_whitespace();
+        if self.text.get(self.position) == Some(&byte) {
+            self.position += 1;
+            Ok(())
+        } else {
+            Err(format!("expected '{}'", byte as char))
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, String> {
+        self.skip_whitespace();
+        match self.text.get(self.position) {
+            Some(b'[') => {
+                self.position += 1;
+                let mut items = Vec::new();
+                self.skip_whitespace();
+                if self.text.get(self.position) == Some(&b']') {
+                    self.position += 1;
+                    return Ok(Value::List(items));
+                }
+                loop {
+                    items.push(self.value()?);
+                    self.skip_whitespace();
+                    match self.text.get(self.position) {
+                        Some(b',') => self.position += 1,
+                        Some(b']') => {
+                            self.position += 1;
+                            return Ok(Value::List(items));
+                        }
+                        _ => return Err("expected ',' or ']'".to_string()),
+                    }
+                }
+            }
+            Some(b'{') => {
+                self.position += 1;
+                let mut fields = Vec::new();
+                self.skip_whitespace();
+                if self.text.get(self.position) == Some(&b'}') {
+                    self.position += 1;
+                    return Ok(Value::Object(fields));
+                }
+                loop {
+                    self.skip_whitespace();
+                    let name = match self.value()? {
+                        Value::Text(name) => name,
+                        _ => return Err("expected a field name".to_string()),
+                    };
+                    self.expect(b':')?;
+                    fields.push((name, self.value()?));
+                    self.skip_whitespace();
+                    match self.text.get(self.position) {
+                        Some(b',') => self.position += 1,
+                        Some(b'}') => {
+                            self.position += 1;
+                            return Ok(Value::Object(fields));
+                        }
+                        _ => return Err("expected ',' or '}'".to_string()),
+                    }
+                }
+            }
+            Some(b'"') => {
+                self.position += 1;
+                let mut text = String::new();
+                loop {
+                    match self.text.get(self.position) {
+                        Some(b'"') => {
+                            self.position += 1;
+                            return Ok(Value::Text(text));
+                        }
+                        Some(b'\\') => {
+                            let escaped = match self.text.get(self.position + 1) {
+                                Some(b'n') => '\n',
+                                Some(b't') => '\t',
+                                Some(&byte @ (b'"' | b'\\' | b'/')) => byte as char,
+                                _ => return Err("unsupported escape in string".to_string()),
+                            };
+                            text.push(escaped);
+                            self.position += 2;
+                        }
+                        Some(_) => {
+                            // Copy a whole UTF-8 character
+                            let rest = std::str::from_utf8(&self.text[self.position..]).map_err(|_| "invalid UTF-8".to_string())?;
+                            let character = rest.chars().next().unwrap();
+                            text.push(character);
+                            self.position += character.len_utf8();
+                        }
+                        None => return Err("unterminated string".to_string()),
+                    }
+                }
+            }
+            Some(_) => {
+                let start = self.position;
+                while self.text.get(self.position).is_some_and(|byte| !b",]}: \t\r\n".contains(byte)) {
+                    self.position += 1;
+                }
+                let word = std::str::from_utf8(&self.text[start..self.position]).map_err(|_| "invalid UTF-8".to_string())?;
+                match word {
+                    "true" => Ok(Value::Bool(true)),
+                    "false" => Ok(Value::Bool(false)),
+                    "null" => Ok(Value::Null),
+                    _ if word.parse::<f64>().is_ok() => Ok(Value::Number(word.to_string())),
+                    _ => Err(format!("unexpected \"{}\"", word)),
+                }
+            }
+            None => Err("unexpected end of input".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gatypes::{BitBinary, Categorical, Integer, Permutation, Real};
+    use crate::metadata::Metadata;
+
+    fn setup_problem() -> Problem {
+        Problem::new(
+            5,
+            1,
+            None,
+            None,
+            None,
+            vec![
+                SolutionDataTypes::Real(Real::new(Some(1.0), Some(5.0))),
+                SolutionDataTypes::Integer(Integer::new(Some(0), Some(10))),
+                SolutionDataTypes::BitBinary(BitBinary::new()),
+                SolutionDataTypes::Categorical(Categorical::new(vec!["aluminium", "titanium"])),
+                SolutionDataTypes::Permutation(Permutation::new(3)),
+            ],
+            |x| vec![x[0]],
+        )
+        .with_variable_metadata(vec!["thickness".into(), "ribs".into(), "stiffened".into(), "material".into(), "order".into()])
+        .with_objective_metadata(vec![Metadata::new("mass").with_unit("kg")])
+    }
+
+    #[test]
+    fn test_validate_seed() {
+        let problem = setup_problem();
+        let genome = [2.5, 4.0, 1.0, 1.0, 2.0, 0.0, 1.0].map(Gene::Real);
+        let genes = validate_seed(&problem, 0, &genome).unwrap();
+        assert_eq!(genes[1], Gene::Integer(4));
+        assert_eq!(genes[2], Gene::Bit(true));
+        assert_eq!(genes[3], Gene::Category(1));
+        assert_eq!(genes[4..], [Gene::Element(2), Gene::Element(0), Gene::Element(1)]);
+        let invalid = |position: usize, value: f64| {
+            let mut genome = genome;
+            genome[position] = Gene::Real(value);
+            validate_seed(&problem, 3, &genome).unwrap_err().to_string()
+        };
+        assert_eq!(invalid(0, 7.5), "seed 3: thickness = 7.5 is outside [1, 5]");
+        assert_eq!(invalid(1, 2.5), "seed 3: ribs = 2.5 is not an integer");
+        assert_eq!(invalid(2, 2.0), "seed 3: stiffened = 2 is not a binary value");
+        assert_eq!(invalid(3, 2.0), "seed 3: material = 2 is not one of the 2 categories");
+        assert_eq!(invalid(4, 0.0), "seed 3: order is not a permutation of 0..3");
+        assert_eq!(
+            validate_seed(&problem, 1, &genome[..6]).unwrap_err().to_string(),
+            "seed 1 has 6 genes, expected 7"
+        );
+    }
+
+    #[test]
+    fn test_parse_csv() {
+        let problem = setup_problem();
+        let text = "thickness,ribs,stiffened,material,order,mass\n2.5,4,1,titanium,\"[2, 0, 1]\",12.5\n\n1,10,false,aluminium,\"[0,1,2]\",\n";
+        let seeds = parse_csv(&problem, text).unwrap();
+        assert_eq!(seeds.len(), 2);
+        let genes = validate_seed(&problem, 0, &seeds[0]).unwrap();
+        assert_eq!(genes[..4], [Gene::Real(2.5), Gene::Integer(4), Gene::Bit(true), Gene::Category(1)]);
+        assert_eq!(validate_seed(&problem, 1, &seeds[1]).unwrap()[2], Gene::Bit(false));
+        let error = |text: &str| parse_csv(&problem, text).unwrap_err().to_string();
+        assert_eq!(error("thickness,span\n1,2\n"), "column \"span\" is neither a variable nor an objective");
+        assert_eq!(error("thickness,ribs\n1,2\n"), "seed 0: stiffened is missing");
+        assert_eq!(
+            error("thickness,ribs,stiffened,material,order\n1,2,0,steel,\"[0,1,2]\"\n"),
+            "seed 0: material has no category \"steel\""
+        );
+        assert_eq!(error("thickness,ribs\n1\n"), "line 2: 1 cells for 2 columns");
+    }
+
+    #[test]
+    fn test_parse_json() {
+        let problem = setup_problem();
+        let text = r#"[
+            [2.5, 4, true, "titanium", [2, 0, 1]],
+            {"order": [0, 1, 2], "material": "aluminium", "stiffened": 0, "ribs": 10, "thickness": 1, "mass": 3}
+        ]"#;
+        let seeds = parse_json(&problem, text).unwrap();
+        assert_eq!(validate_seed(&problem, 0, &seeds[0]).unwrap()[..3], [Gene::Real(2.5), Gene::Integer(4), Gene::Bit(true)]);
+        assert_eq!(validate_seed(&problem, 1, &seeds[1]).unwrap()[3], Gene::Category(0));
+        assert_eq!(parse_json(&problem, "[\n[1, 2,\n").unwrap_err().to_string(), "line 3: unexpected end of input");
+        // Integers beyond 2^53 are read exactly
+        let wide = Problem::new(1, 1, None, None, None, vec![SolutionDataTypes::Integer(Integer::new(None, None))], |x| vec![x[0]]);
+        assert_eq!(parse_json(&wide, "[[1152921504606846977]]").unwrap()[0], vec![Gene::Integer((1 << 60) + 1)]);
+    }
+
+    #[test]
+    fn test_read_seeds() {
+        let problem = setup_problem();
+        let path = std::env::temp_dir().join(format!("rustypus_seeds_{}.csv", std::process::id()));
+        std::fs::write(&path, "thickness,ribs,stiffened,material,order\n3,5,0,titanium,\"[1,0,2]\"\n").unwrap();
+        let seeds = read_seeds(&problem, &path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(seeds.len(), 1);
+        assert!(matches!(read_seeds(&problem, "seeds.txt"), Err(SeedError::UnsupportedFormat(_))));
+        assert!(matches!(read_seeds(&problem, "missing_seeds.json"), Err(SeedError::Io(_))));
+    }
+}
@@ -0,0 +1,53 @@
+// Fixtures shared by the unit tests that compare solutions by their objective values: a problem with a
+// single Real variable and solutions whose objective values are set directly.
+use crate::core::{ObjectiveFunction, Problem, Solution};
+use crate::gatypes::{Gene, Real, SolutionDataTypes};
+
+/// Problem with one objective per entry of `direction` and an identity objective function
+pub(crate) fn setup_problem(direction: Vec<i8>) -> Problem {
+    Problem {
+        solution_length: 1,
+        number_of_objectives: direction.len(),
+        objective_constraint: None,
+        objective_constraint_operands: None,
+        direction: Some(direction),
+        solution_data_types: vec![SolutionDataTypes::Real(Real::new(Some(0.0), Some(1.0)))],
+        objective_function: ObjectiveFunction::Numeric(|x| x.clone()),
+        ..Default::default()
+    }
+}
+
+/// Problem minimizing every objective
+pub(crate) fn minimizing_problem(number_of_objectives: usize) -> Problem {
+    setup_problem(vec![-1; number_of_objectives])
+}
+
+/// Problem minimizing every objective, with each objective constrained to stay below 0.5
+pub(crate) fn constrained_problem(number_of_objectives: usize) -> Problem {
+    let mut problem = minimizing_problem(number_of_objectives);
+    problem.objective_constraint = Some(vec![Some(0.5); number_of_objectives]);
+    problem.objective_constraint_operands = Some(vec![Some("<".to_string()); number_of_objectives]);
+    problem
+}
+
+/// Feasible solution that has been evaluated to `objectives`
+pub(crate) fn evaluated_solution(problem: &Problem, objectives: Vec<f64>) -> Solution<'_> {
+    Solution {
+        problem,
+        solution: vec![Gene::Real(0.0)],
+        objective_fitness_values: objectives,
+        constraint_values: Vec::new(),
+        constraint_violation: 0,
+        feasible: true,
+        evaluated: true,
+    }
+}
+
+/// Evaluated solution violating `constraint_violation` of the objective constraints
+pub(crate) fn violating_solution(problem: &Problem, objectives: Vec<f64>, constraint_violation: usize) -> Solution<'_> {
+    Solution {
+        constraint_violation,
+        feasible: constraint_violation == 0,
+        ..evaluated_solution(problem, objectives)
+    }
+}